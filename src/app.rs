@@ -8,13 +8,18 @@
 //! - prints reports/plots
 //! - writes optional exports
 
+use std::io::IsTerminal;
+
 use clap::Parser;
+use rand::RngCore;
 
-use crate::cli::{Command, FitArgs, PlotArgs};
-use crate::domain::FitConfig;
+use crate::cli::{CheckArgs, Command, CompareDatesArgs, CompareSnapshotArgs, EvalArgs, FitArgs, ListArgs, PlotArgs, ValidateArgs};
+use crate::domain::{FitConfig, RatingBlend};
 use crate::error::AppError;
+use crate::report::{BatchCell, RunOutput};
 
 pub mod pipeline;
+pub mod selftest;
 
 /// Entry point for the `rv` binary.
 pub fn run() -> Result<(), AppError> {
@@ -22,116 +27,715 @@ pub fn run() -> Result<(), AppError> {
     //
     // Clap requires a subcommand name, so we do a small, explicit rewrite of the
     // argv list before parsing. This preserves a clean clap structure while
-    // retaining the requested UX.
-    let argv = rewrite_args(std::env::args().collect());
+    // retaining the requested UX. A non-empty `RV_NO_DEFAULT_TUI` turns the
+    // rewrite off for scripts, so bare `rv` prints help instead.
+    let original: Vec<String> = std::env::args().collect();
+    // `--tui-script` renders off-screen, so it needs the TUI but no terminal.
+    let interactive = has_tui_script(&original)
+        || (std::io::stdin().is_terminal() && std::io::stdout().is_terminal());
+    let argv = if default_command_enabled(std::env::var_os("RV_NO_DEFAULT_TUI").as_deref()) {
+        rewrite_args(original.clone(), interactive)
+    } else {
+        original.clone()
+    };
+    if !interactive && argv.len() != original.len() {
+        eprintln!("note: not a terminal; running `rv fit` instead of the TUI.");
+    }
     let cli = crate::cli::Cli::parse_from(argv);
+    let color = crate::cli::color_enabled(
+        cli.no_color,
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    );
 
     match cli.command {
         Command::Fit(args) => handle_fit(args, OutputMode::Full),
         Command::Rank(args) => handle_fit(args, OutputMode::RankOnly),
         Command::Plot(args) => handle_plot(args),
-        Command::Tui(args) => handle_tui(args),
+        Command::Eval(args) => handle_eval(args),
+        Command::Validate(args) => handle_validate(args),
+        Command::Check(args) => handle_check(args),
+        Command::Tui(args) => handle_tui(args, color),
+        Command::ListRatings(args) => handle_list(args, false),
+        Command::ListSeries(args) => handle_list(args, true),
+        Command::CompareSnapshot(args) => handle_compare_snapshot(args),
+        Command::CompareDates(args) => handle_compare_dates(args),
+        Command::Schema(args) => handle_schema(args),
+        Command::Selftest => handle_selftest(),
+        Command::PrecisionCheck => handle_precision_check(),
+        Command::ExitCodes => {
+            for (code, meaning) in crate::error::EXIT_CODES {
+                println!("{code}  {meaning}");
+            }
+            Ok(())
+        }
     }
 }
 
+/// Run `rv selftest`: print one line per check, exit 1 if any failed.
+fn handle_selftest() -> Result<(), AppError> {
+    let checks = selftest::run_selftest()?;
+    print!("{}", selftest::format_selftest(&checks));
+    let failed = checks.iter().filter(|c| !c.passed).count();
+    if failed > 0 {
+        return Err(AppError::new(1, format!("Selftest failed: {failed} of {} checks out of tolerance.", checks.len())));
+    }
+    Ok(())
+}
+
+/// Taus `rv precision-check` samples, spanning the default tau grids.
+const PRECISION_CHECK_TAUS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0];
+
+/// Run `rv precision-check`: basis continuity at the series switch, exit 1 on a gap.
+fn handle_precision_check() -> Result<(), AppError> {
+    let checks = crate::math::check_branch_continuity(&PRECISION_CHECK_TAUS, 200);
+    print!("{}", crate::report::format_precision_check(&checks));
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    if failed > 0 {
+        return Err(AppError::new(
+            1,
+            format!("Precision check failed: {failed} of {} basis checks exceed {:e}.", checks.len(), crate::math::BRANCH_TOL),
+        ));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OutputMode {
     Full,
     RankOnly,
 }
 
-fn handle_fit(args: FitArgs, mode: OutputMode) -> Result<(), AppError> {
+fn handle_fit(mut args: FitArgs, mode: OutputMode) -> Result<(), AppError> {
+    if args.tui_script.is_some() {
+        return Err(AppError::new(2, "--tui-script only applies to `rv tui`."));
+    }
+    if let Some(seed) = draw_random_seed(&mut args) {
+        eprintln!("seed: {seed} (reproduce with --deterministic-seed {seed})");
+    }
     let config = fit_config_from_args(&args);
+    if config.all_ratings {
+        return handle_batch(&config);
+    }
+    if let Some(n) = config.seed_sweep {
+        let runs = pipeline::run_seed_sweep(&config, n)?;
+        let stability = crate::report::compute_bond_stability(&runs);
+        print!(
+            "{}",
+            crate::report::format_bond_stability(&stability, runs.len(), config.top_n, config.output_unit, config.locale)
+        );
+        return Ok(());
+    }
     let run = pipeline::run_fit(&config)?;
 
+    for warning in &run.warnings {
+        eprintln!("warning: {warning}");
+    }
+    if config.fail_on_warnings && !run.warnings.is_empty() {
+        return Err(warnings_error(run.warnings.len()));
+    }
+
+    if config.output_format == crate::domain::OutputFormat::Ndjson {
+        if config.timing {
+            eprintln!("{}", crate::report::format_timing(&run.timings));
+        }
+        let stdout = std::io::stdout().lock();
+        match crate::io::export::write_residuals_ndjson(stdout, &run.residuals, &run.rankings, &run.ingest.input_spec, config.rank_by) {
+            // The consumer stopped reading (e.g. `| head`); that is not a failure.
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                return Err(AppError::new(2, format!("Failed to write NDJSON: {e}")));
+            }
+            _ => {}
+        }
+        return write_exports(&run, &config);
+    }
+
+    if config.compact {
+        let hash = pipeline::run_hash(&run, &config);
+        println!(
+            "{}",
+            crate::report::format_compact_summary(&run.ingest, &run.selection, &config, hash)
+        );
+        return write_exports(&run, &config);
+    }
+
     // Print terminal output.
     match mode {
         OutputMode::Full => {
             println!(
                 "{}",
-                crate::report::format_run_summary(&run.ingest, &run.selection, &config)
+                crate::report::format_run_summary(&crate::report::build_run_summary(&run, &config), config.locale)
             );
+            if config.timing {
+                println!("{}", crate::report::format_timing(&run.timings));
+            }
+        }
+        OutputMode::RankOnly => {
+            // Stdout stays the rankings table; say which curve they came from.
+            eprintln!("{}", crate::report::format_rank_model(&run.selection, config.model_spec));
+            if config.timing {
+                eprintln!("{}", crate::report::format_timing(&run.timings));
+            }
         }
-        OutputMode::RankOnly => {}
     }
 
     println!(
         "{}",
-        crate::report::format_rankings(&run.rankings, &run.ingest.input_spec)
+        crate::report::format_rankings(
+            &run.rankings,
+            &run.ingest.input_spec,
+            config.rank_by,
+            config.round_tenors,
+            config.locale
+        )
     );
 
     if mode == OutputMode::Full && config.plot {
+        let baseline = config.plot_baseline.then(|| {
+            crate::data::sample::baseline_grid(
+                &run.snapshot,
+                &config.sample_blend(),
+                run.ingest.stats.tenor_min,
+                run.ingest.stats.tenor_max,
+                config.plot_width.max(2),
+                run.ingest.input_spec.y_unit.per_bp(),
+            )
+        });
+        let vol = config.plot_vol.then(|| {
+            crate::data::sample::vol_grid(
+                &run.snapshot,
+                run.ingest.stats.tenor_min,
+                run.ingest.stats.tenor_max,
+                config.plot_width.max(2),
+            )
+        });
         let plot = crate::plot::render_ascii_plot(
             &run.residuals,
             &run.selection.best,
             config.plot_width,
             config.plot_height,
             Some(&run.rankings),
+            run.ingest.input_spec.y_unit,
+            baseline.as_deref(),
+            vol.as_deref(),
+            config.plot_hash,
         );
         println!("{plot}");
     }
 
-    // Optional exports.
+    if config.explain_selection {
+        println!("{}", crate::report::format_selection_explanation(&run.selection));
+    }
+
+    if config.diagnostics {
+        println!("{}", crate::report::format_residual_diagnostics(&run.residuals));
+    }
+
+    if config.compare_models {
+        let rows = crate::fit::selection::compare_models(run.fitted_points(), &run.selection);
+        println!(
+            "{}",
+            crate::report::format_model_comparison(&rows, config.output_unit, config.locale)
+        );
+    }
+
+    if !config.pillars.is_empty() {
+        let table = crate::io::curve::pillar_table(&run, &config)?;
+        println!("{}", crate::report::format_pillars(&table, config.locale));
+        if let Some(path) = &config.pillars_json {
+            crate::io::curve::write_pillars_json(path, &table)?;
+        }
+    }
+
+    if config.influence {
+        let influence =
+            crate::fit::influence::jackknife_influence(run.fitted_points(), &run.selection.best, &config)?;
+        println!("{}", crate::report::format_influence(&influence, config.top_n));
+    }
+
+    if let Some(path) = &config.baseline_curve {
+        let previous = crate::io::curve::read_curve_json(path)?;
+        if previous.unit != run.ingest.input_spec.y_unit {
+            return Err(AppError::new(
+                2,
+                format!(
+                    "Baseline curve '{}' is in {} but this run reports {}; use a matching --output-unit.",
+                    path.display(),
+                    previous.unit.label(),
+                    run.ingest.input_spec.y_unit_label()
+                ),
+            ));
+        }
+        let shifts = crate::report::compute_residual_shifts(&run.residuals, &previous);
+        println!(
+            "{}",
+            crate::report::format_residual_shifts(&shifts, &previous, config.rank_by_shift)
+        );
+    }
+
+    write_exports(&run, &config)
+}
+
+/// Write the optional per-run exports requested in `config`.
+fn write_exports(run: &RunOutput, config: &FitConfig) -> Result<(), AppError> {
     if let Some(path) = &config.export_results {
-        crate::io::export::write_results_csv(path, &run.residuals, &run.ingest.input_spec, &config)?;
+        crate::io::export::write_results_csv(
+            path,
+            &run.residuals,
+            &run.rankings,
+            &run.ingest.input_spec,
+            config.round_tenors,
+            config.export_append,
+        )?;
     }
     if let Some(path) = &config.export_curve {
-        crate::io::curve::write_curve_json(path, &run.selection.best, &run.ingest, &config)?;
+        crate::io::curve::write_curve_json(path, &run.selection.best, &run.ingest, config)?;
+    }
+    if let (Some(path), Some(landscape)) = (&config.export_landscape, &run.selection.landscape) {
+        crate::io::export::write_landscape_csv(path, run.selection.best.model.name, landscape)?;
+    }
+    if let Some(path) = &config.export_timeseries {
+        crate::io::export::append_timeseries_csv(path, run.ingest.input_spec.asof_date, &run.selection.best)?;
+    }
+    if let Some(path) = &config.export_models {
+        let rows = crate::fit::selection::compare_models(run.fitted_points(), &run.selection);
+        crate::io::export::write_model_comparison(path, &rows)?;
+    }
+    if let Some(path) = &config.export_sample {
+        crate::io::export::write_sample_csv(path, &run.sample)?;
+    }
+    if let Some(path) = &config.export_stats {
+        crate::io::export::write_stats_csv(
+            path,
+            &crate::report::residual_stats(&run.residuals),
+            &run.ingest.input_spec,
+            &config.sample_blend().label(),
+            run.selection.best.model.name,
+        )?;
+    }
+    if let Some(path) = &config.export_diagnostics {
+        let summary = crate::report::build_run_summary(run, config);
+        let json = serde_json::to_vec_pretty(&summary)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize diagnostics JSON: {e}")))?;
+        crate::io::export::write_atomic(path, &json, "diagnostics JSON")?;
+    }
+    if let Some(lang) = config.export_formula {
+        let asof = run.ingest.input_spec.asof_date;
+        let path = config.formula_out.clone().unwrap_or_else(|| {
+            crate::io::formula::default_formula_path(lang, config.rating, asof).into()
+        });
+        let formula = crate::io::formula::render_formula(
+            &run.selection.best.model,
+            lang,
+            config.rating,
+            asof,
+            run.ingest.input_spec.y_unit,
+        );
+        crate::io::formula::write_formula(&path, &formula)?;
+        eprintln!("formula: wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Run `--all-ratings`: print the batch table, then per-cell failures.
+fn handle_batch(config: &FitConfig) -> Result<(), AppError> {
+    let batch = pipeline::run_batch(config, &crate::domain::RatingBand::ALL)?;
+    let cells = batch.cells;
+    if !batch.skipped.is_empty() {
+        let names: Vec<&str> = batch.skipped.iter().map(|r| r.display_name()).collect();
+        eprintln!("note: skipped rating(s) missing from FRED: {}", names.join(", "));
+    }
+
+    for cell in &cells {
+        if let Ok(run) = &cell.result {
+            for warning in &run.warnings {
+                eprintln!("warning: [{}] {warning}", cell.rating.display_name());
+            }
+        }
+    }
+    let warning_count: usize = cells.iter().filter_map(|c| c.result.as_ref().ok()).map(|r| r.warnings.len()).sum();
+    if config.fail_on_warnings && warning_count > 0 {
+        return Err(warnings_error(warning_count));
     }
+    print!("{}", crate::report::format_batch_summary(&cells));
 
+    // Cells are written one at a time in (date, rating) order after the parallel
+    // fits, so writes never interleave. The first cell creates the file unless
+    // `--export-append` asked to extend an existing one.
+    if let Some(path) = &config.export_results {
+        let mut append = config.export_append;
+        for run in cells.iter().filter_map(|c| c.result.as_ref().ok()) {
+            crate::io::export::write_results_csv(
+                path,
+                &run.residuals,
+                &run.rankings,
+                &run.ingest.input_spec,
+                config.round_tenors,
+                append,
+            )?;
+            append = true;
+        }
+    }
+
+    let failed: Vec<&BatchCell> = cells.iter().filter(|c| c.result.is_err()).collect();
+    if let Some(first) = failed.first() {
+        for cell in &failed {
+            if let Err(e) = &cell.result {
+                eprintln!("error: [{} {}] {e}", cell.date, cell.rating.display_name());
+            }
+        }
+        let code = first.result.as_ref().err().map(AppError::exit_code).unwrap_or(1);
+        return Err(AppError::new(
+            code,
+            format!("{} of {} batch fits failed.", failed.len(), cells.len()),
+        ));
+    }
     Ok(())
 }
 
-fn handle_tui(args: FitArgs) -> Result<(), AppError> {
-    crate::tui::run(args)
+/// Error returned by `--fail-on-warnings` once the warnings have been printed.
+fn warnings_error(count: usize) -> AppError {
+    AppError::new(3, format!("{count} warning(s) raised with --fail-on-warnings."))
+}
+
+fn handle_list(args: ListArgs, all_series: bool) -> Result<(), AppError> {
+    let map = match &args.series_map {
+        Some(path) => crate::data::SeriesMap::read(path)?,
+        None => crate::data::SeriesMap::default(),
+    };
+    print!("{}", crate::report::format_series_map(&map, all_series));
+    Ok(())
+}
+
+fn handle_compare_snapshot(args: CompareSnapshotArgs) -> Result<(), AppError> {
+    use crate::data::{FredClient, SnapshotOptions};
+
+    let series = match &args.series_map {
+        Some(path) => crate::data::SeriesMap::read(path)?,
+        None => crate::data::SeriesMap::default(),
+    };
+    // Bands missing on either date are left out and reported as n/a.
+    let options = |target_date| SnapshotOptions {
+        target_date,
+        missing_rating: crate::domain::MissingRating::Skip,
+        series: series.clone(),
+        smooth_days: args.smooth_days,
+        ..SnapshotOptions::default()
+    };
+    let client = FredClient::new(args.api_key_file.as_deref())?;
+    let from = client.fetch_snapshot(&options(Some(args.from)))?;
+    let to = client.fetch_snapshot(&options(args.to))?;
+    let diff = crate::data::diff_snapshots(&from, &to);
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&diff)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize snapshot diff: {e}")))?;
+        println!("{json}");
+    } else {
+        print!("{}", crate::report::format_snapshot_diff(&diff));
+    }
+    Ok(())
+}
+
+fn handle_compare_dates(mut args: CompareDatesArgs) -> Result<(), AppError> {
+    use crate::data::{FredClient, SnapshotOptions};
+
+    if args.fit.asof.is_some() {
+        return Err(AppError::new(2, "rv compare-dates takes its dates from --from/--to, not --asof."));
+    }
+    if let Some(seed) = draw_random_seed(&mut args.fit) {
+        eprintln!("seed: {seed} (reproduce with --deterministic-seed {seed})");
+    }
+    let config = fit_config_from_args(&args.fit);
+    let client = FredClient::new(config.api_key_file.as_deref())?;
+    let snapshot = |asof| client.fetch_snapshot(&SnapshotOptions::from_config(&FitConfig { asof, ..config.clone() })?);
+    let pair = pipeline::run_date_pair(&config, snapshot(Some(args.from))?, snapshot(args.to)?)?;
+
+    for (date, run) in [("from", &pair.from), ("to", &pair.to)] {
+        for warning in &run.warnings {
+            eprintln!("warning: [{date}] {warning}");
+        }
+    }
+    let warning_count = pair.from.warnings.len() + pair.to.warnings.len();
+    if config.fail_on_warnings && warning_count > 0 {
+        return Err(warnings_error(warning_count));
+    }
+
+    let moves = crate::report::curve_moves(&pair, &config, &args.tenors)?;
+    if args.json {
+        let json = serde_json::to_string_pretty(&moves)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize curve moves: {e}")))?;
+        println!("{json}");
+    } else {
+        print!("{}", crate::report::format_curve_moves(&moves));
+    }
+    Ok(())
+}
+
+fn handle_schema(args: crate::cli::SchemaArgs) -> Result<(), AppError> {
+    let schema = match args.kind {
+        crate::cli::SchemaKind::Curve => crate::io::curve_schema(),
+    };
+    let json = serde_json::to_string_pretty(&schema)
+        .map_err(|e| AppError::new(1, format!("Failed to serialize schema: {e}")))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn handle_tui(mut args: FitArgs, color: bool) -> Result<(), AppError> {
+    // Drawn once here: the TUI builds its config twice and shows the seed in its status line.
+    draw_random_seed(&mut args);
+    crate::tui::run(args, color)
+}
+
+/// Print the `--max-curve-age-days` warning for a loaded curve, unless disabled.
+fn warn_curve_age(curve: &crate::domain::CurveFile, max_age_days: i64, no_age_check: bool) {
+    if no_age_check {
+        return;
+    }
+    let today = chrono::Local::now().date_naive();
+    if let Some(warning) = crate::io::curve::curve_age_warning(curve, today, max_age_days) {
+        eprintln!("warning: {warning}");
+    }
 }
 
 fn handle_plot(args: PlotArgs) -> Result<(), AppError> {
     let curve = crate::io::curve::read_curve_json(&args.curve)?;
+    warn_curve_age(&curve, args.max_curve_age_days, args.no_age_check);
 
     // For plot-only mode we create a lightweight residual list from the curve grid.
-    let plot = crate::plot::render_ascii_plot_from_curve_file_only(&curve, args.width, args.height);
+    let plot = crate::plot::render_ascii_plot_from_curve_file_only(&curve, args.width, args.height, args.plot_hash);
 
     println!("{plot}");
     Ok(())
 }
 
+fn handle_eval(args: EvalArgs) -> Result<(), AppError> {
+    let curve = crate::io::curve::read_curve_json(&args.curve)?;
+    warn_curve_age(&curve, args.max_curve_age_days, args.no_age_check);
+    let values = crate::io::curve::eval_curve(&curve, &args.tenors, args.from_grid)?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&values)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize curve values: {e}")))?;
+        println!("{json}");
+    } else {
+        print!("{}", crate::report::format_curve_values(&curve, &values));
+    }
+    Ok(())
+}
+
+fn handle_validate(args: ValidateArgs) -> Result<(), AppError> {
+    let curve = crate::io::curve::read_curve_json(&args.curve)?;
+    let problems = crate::io::curve::validate_curve(&curve);
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("invalid: {problem}");
+        }
+        return Err(AppError::new(
+            2,
+            format!("Curve JSON '{}' failed validation ({} problem(s)).", args.curve.display(), problems.len()),
+        ));
+    }
+
+    let shape = crate::models::classify_shape(&curve.grid.y).as_str();
+    match &curve.shape {
+        Some(_) => println!("OK: {} (shape {shape}, matches stored)", args.curve.display()),
+        None => println!("OK: {} (shape {shape}; no stored shape to compare)", args.curve.display()),
+    }
+    Ok(())
+}
+
+fn handle_check(args: CheckArgs) -> Result<(), AppError> {
+    use crate::data::{FredClient, SnapshotOptions};
+    use crate::domain::MissingRating;
+
+    let curve = crate::io::curve::read_curve_json(&args.curve)?;
+    let series = match &args.series_map {
+        Some(path) => crate::data::SeriesMap::read(path)?,
+        None => crate::data::SeriesMap::default(),
+    };
+    let client = FredClient::new(args.api_key_file.as_deref())?;
+    // Only the curve's own band is compared: another band missing on the
+    // snapshot date is skipped, while a missing curve band still fails (exit 4).
+    let options = SnapshotOptions { series, missing_rating: MissingRating::Skip, ..SnapshotOptions::default() };
+    let snapshot = client.fetch_snapshot(&options)?;
+    let drift = crate::report::curve_drift(&curve, &snapshot, args.max_deviation)?;
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&drift)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize curve check: {e}")))?;
+        println!("{json}");
+    } else {
+        print!("{}", crate::report::format_curve_drift(&drift));
+    }
+    if drift.drifted() {
+        let worst = drift.worst().expect("a drifted curve has rows");
+        return Err(AppError::new(
+            crate::report::DRIFT_EXIT_CODE,
+            format!(
+                "Curve '{}' deviates from the {} baseline by {:.prec$} {} at {:.2}y (max {:.prec$}).",
+                args.curve.display(),
+                drift.snapshot_date,
+                worst.deviation.abs(),
+                drift.unit.label(),
+                worst.tenor,
+                drift.max_deviation,
+                prec = drift.unit.decimals()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Resolve `--random-seed` into `--deterministic-seed`, drawing from OS
+/// entropy once so every config built from `args` gets the same sample.
+/// Returns the drawn seed.
+pub fn draw_random_seed(args: &mut FitArgs) -> Option<u64> {
+    if !args.random_seed || args.deterministic_seed.is_some() {
+        return None;
+    }
+    let seed = rand::rngs::OsRng.next_u64();
+    args.deterministic_seed = Some(seed);
+    Some(seed)
+}
+
+/// Build the run config from parsed flags. Pure: `--random-seed` must be
+/// resolved with `draw_random_seed` first, or it falls back to the derived seed.
 pub fn fit_config_from_args(args: &FitArgs) -> FitConfig {
     FitConfig {
-        rating: args.rating,
+        rating: args.rating_blend.as_ref().map_or(args.rating, RatingBlend::dominant),
+        rating_blend: args.rating_blend.clone(),
         sample_count: args.sample_count,
+        asof: args.asof,
+        missing_rating: args.missing_rating,
+        date_policy: args.date_policy,
+        min_vol_obs: args.min_vol_obs,
+        short_vol_history: args.short_vol_history,
+        strict_dates: args.strict_dates,
+        series_map: args.series_map.clone(),
+        api_key_file: args.api_key_file.clone(),
+        smooth_days: args.smooth_days,
+        points_file: args.sample_from_points.clone(),
+        no_prior: args.no_prior,
         sample_seed: args.seed,
+        rng_seed: args.deterministic_seed,
+        noise_seed: None,
+        weight_mode: args.weight_mode,
         model_spec: args.model,
+        basis: args.basis,
         tau_min: args.tau_min,
         tau_max: args.tau_max,
         tau_steps_ns: args.tau_steps_ns,
         tau_steps_nss: args.tau_steps_nss,
         tau_steps_nssc: args.tau_steps_nssc,
+        tau_tiebreak: args.tau_tiebreak,
+        beta_abs_max: args.beta_abs_max,
+        y0_min: args.y0_min,
+        y0_max: args.y0_max,
+        y_transform: args.y_transform,
+        bic_margin: args.bic_margin,
+        splice_at: args.splice_at,
+        splice_window: args.splice_window,
+        min_n_buffer: args.min_n_buffer,
+        min_coverage: args.min_coverage,
+        progress: args.progress,
         tenor_min: args.tenor_min,
         tenor_max: args.tenor_max,
+        fit_min: args.fit_min,
+        fit_max: args.fit_max,
+        tenor_grid_step: args.tenor_grid_sample,
+        max_extrap_years: args.max_extrap_years,
+        round_tenors: args.round_tenors,
+        weight_file: args.weight_file.clone(),
+        on_bad_weight: args.on_bad_weight,
+        issuer_cap: args.issuer_cap,
         top_n: args.top,
+        top_cheap: args.top_cheap,
+        top_rich: args.top_rich,
+        rank_by: args.rank_by,
+        signal_weights: args.signal_weights,
+        output_unit: args.output_unit,
+        locale: args.locale,
+        compact: args.compact,
+        output_format: args.format,
+        timing: args.timing,
         plot: args.plot && !args.no_plot,
         plot_width: args.width,
         plot_height: args.height,
+        plot_baseline: args.plot_baseline,
+        plot_vol: args.plot_vol,
+        plot_hash: args.plot_hash,
         export_results: args.export.clone(),
+        export_append: args.export_append,
         export_curve: args.export_curve.clone(),
+        export_timeseries: args.export_timeseries.clone(),
+        export_landscape: args.export_landscape.clone(),
+        compare_models: args.compare_models,
+        export_models: args.export_models.clone(),
+        export_sample: args.export_sample.clone(),
+        export_stats: args.export_stats.clone(),
+        export_diagnostics: args.export_diagnostics.clone(),
+        embed_inputs: args.embed_inputs,
+        pillars: args.pillars.clone().unwrap_or_default(),
+        pillars_json: args.pillars_json.clone(),
+        export_formula: args.export_formula,
+        formula_out: args.formula_out.clone(),
+        baseline_curve: args.baseline_curve.clone(),
+        rank_by_shift: args.rank_by_shift,
+        fail_on_warnings: args.fail_on_warnings,
+        influence: args.influence,
+        all_ratings: args.all_ratings,
+        threads: args.threads,
+        seed_sweep: args.seed_sweep,
+        explain_selection: args.explain_selection,
+        diagnostics: args.diagnostics,
+        slopes: args.slopes.clone(),
+        resid_bins: args.resid_bins.clone(),
 
         jump_prob_wide: args.jump_prob_wide,
         jump_prob_tight: args.jump_prob_tight,
         jump_k_wide: args.jump_k_wide,
         jump_k_tight: args.jump_k_tight,
+        vol_floor: args.vol_floor,
+        vol_cap: args.vol_cap,
     }
 }
 
+/// Whether `rewrite_args` may insert the default subcommand: off when
+/// `RV_NO_DEFAULT_TUI` is set to a non-empty value (like `NO_COLOR`).
+fn default_command_enabled(no_default_env: Option<&std::ffi::OsStr>) -> bool {
+    no_default_env.is_none_or(|v| v.is_empty())
+}
+
+/// Whether `argv` asks for a scripted TUI render (`--tui-script`).
+fn has_tui_script(argv: &[String]) -> bool {
+    argv.iter().any(|a| a == "--tui-script" || a.starts_with("--tui-script="))
+}
+
+/// Valueless global flags (`Cli`), skipped when looking for the subcommand.
+const GLOBAL_FLAGS: &[&str] = &["--no-color"];
+
 /// Rewrite argv so `rv` defaults to `rv tui`.
 ///
 /// Rules:
 /// - `rv`                      -> `rv tui`
 /// - `rv -r BBB ...`           -> `rv tui -r BBB ...`
 /// - `rv --help/--version/-h`  -> unchanged (show top-level help/version)
-fn rewrite_args(mut argv: Vec<String>) -> Vec<String> {
-    let Some(arg1) = argv.get(1).cloned() else {
-        argv.push("tui".to_string());
+/// - leading global flags (`--no-color`) are skipped: `rv --no-color list-ratings`
+///   is unchanged and `rv --no-color -r BBB` -> `rv --no-color tui -r BBB`
+///
+/// When stdin/stdout are not a terminal (CI, pipes) the implicit default is
+/// `fit` instead, since the TUI cannot run there; `run` still passes
+/// `interactive` for `--tui-script`, which renders without a terminal. An
+/// explicit `rv tui` is never rewritten.
+fn rewrite_args(mut argv: Vec<String>, interactive: bool) -> Vec<String> {
+    let default = if interactive { "tui" } else { "fit" };
+    // Global flags may come before the subcommand (`rv --no-color exit-codes`).
+    let first = 1 + argv.iter().skip(1).take_while(|a| GLOBAL_FLAGS.contains(&a.as_str())).count();
+    let Some(arg1) = argv.get(first).cloned() else {
+        argv.push(default.to_string());
         return argv;
     };
 
@@ -143,17 +747,105 @@ fn rewrite_args(mut argv: Vec<String>) -> Vec<String> {
         return argv;
     }
 
-    let is_subcommand = matches!(arg1.as_str(), "fit" | "rank" | "plot" | "tui");
+    let is_subcommand = matches!(arg1.as_str(), "fit" | "rank" | "plot" | "eval" | "validate" | "check" | "list-ratings" | "list-series" | "compare-snapshot" | "compare-dates" | "schema" | "selftest" | "precision-check" | "exit-codes" | "tui");
     if is_subcommand {
         return argv;
     }
 
     // If the first token is a flag, treat it as "tui flags".
     if arg1.starts_with('-') {
-        argv.insert(1, "tui".to_string());
+        argv.insert(first, default.to_string());
         return argv;
     }
 
     // Otherwise, leave as-is.
     argv
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        std::iter::once("rv").chain(args.iter().copied()).map(String::from).collect()
+    }
+
+    #[test]
+    fn implicit_tui_falls_back_to_fit_without_a_terminal() {
+        assert_eq!(rewrite_args(argv(&[]), true), argv(&["tui"]));
+        assert_eq!(rewrite_args(argv(&[]), false), argv(&["fit"]));
+        assert_eq!(rewrite_args(argv(&["-r", "AA"]), false), argv(&["fit", "-r", "AA"]));
+        // Explicit subcommands (including `tui`) and help are left alone.
+        assert_eq!(rewrite_args(argv(&["tui"]), false), argv(&["tui"]));
+        assert_eq!(rewrite_args(argv(&["--help"]), false), argv(&["--help"]));
+    }
+
+    #[test]
+    fn tui_script_routes_to_the_tui_and_is_rejected_elsewhere() {
+        let script = argv(&["--tui-script", "m,g"]);
+        assert!(has_tui_script(&script));
+        assert!(has_tui_script(&argv(&["--tui-script=m"])));
+        // `run` treats a scripted render as interactive, so the default is `tui`.
+        assert_eq!(rewrite_args(script, true), argv(&["tui", "--tui-script", "m,g"]));
+
+        let args = FitArgs::try_parse_from(argv(&["--tui-script", "m"])).unwrap();
+        assert_eq!(handle_fit(args, OutputMode::Full).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn round_tenors_rejects_non_positive_steps_at_parse_time() {
+        // Parse-time, so `fit`, `--all-ratings` batches and `tui` all reject it.
+        for bad in ["0", "-0.5", "nan", "inf"] {
+            let parsed = FitArgs::try_parse_from(argv(&["--round-tenors", bad]));
+            assert!(parsed.is_err(), "{bad}");
+        }
+        let args = FitArgs::try_parse_from(argv(&["--round-tenors", "0.25"])).unwrap();
+        assert_eq!(args.round_tenors, Some(0.25));
+    }
+
+    #[test]
+    fn leading_global_flags_keep_the_subcommand() {
+        assert_eq!(rewrite_args(argv(&["--no-color", "exit-codes"]), true), argv(&["--no-color", "exit-codes"]));
+        assert_eq!(rewrite_args(argv(&["--no-color"]), false), argv(&["--no-color", "fit"]));
+        assert_eq!(rewrite_args(argv(&["--no-color", "-r", "bbb"]), true), argv(&["--no-color", "tui", "-r", "bbb"]));
+        assert_eq!(rewrite_args(argv(&["--no-color", "--help"]), true), argv(&["--no-color", "--help"]));
+
+        let cli = crate::cli::Cli::try_parse_from(rewrite_args(argv(&["--no-color", "exit-codes"]), false)).unwrap();
+        assert!(cli.no_color && matches!(cli.command, Command::ExitCodes));
+    }
+
+    #[test]
+    fn no_default_tui_env_leaves_bare_rv_to_clap() {
+        use std::ffi::OsStr;
+
+        assert!(default_command_enabled(None));
+        assert!(default_command_enabled(Some(OsStr::new(""))));
+        assert!(!default_command_enabled(Some(OsStr::new("1"))));
+
+        // Without the rewrite, bare `rv` prints help rather than starting anything.
+        let err = crate::cli::Cli::try_parse_from(argv(&[])).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand);
+        assert!(err.to_string().contains("RV_NO_DEFAULT_TUI"), "{err}");
+        // Explicit subcommands parse the same either way.
+        assert!(crate::cli::Cli::try_parse_from(argv(&["fit", "-r", "bbb"])).is_ok());
+    }
+
+    #[test]
+    fn no_color_flag_and_env_disable_colour() {
+        use crate::cli::color_enabled;
+        use std::ffi::OsStr;
+
+        assert!(color_enabled(false, None, true));
+        assert!(!color_enabled(false, None, false));
+        // NO_COLOR wins even on a terminal; an empty value does not count.
+        assert!(!color_enabled(false, Some(OsStr::new("1")), true));
+        assert!(color_enabled(false, Some(OsStr::new("")), true));
+        assert!(!color_enabled(true, None, true));
+
+        // The flag is global: accepted after the subcommand and by the implicit TUI.
+        let cli = crate::cli::Cli::try_parse_from(rewrite_args(argv(&["--no-color", "-r", "bbb"]), true)).unwrap();
+        assert!(cli.no_color && matches!(cli.command, Command::Tui(_)));
+        let cli = crate::cli::Cli::try_parse_from(argv(&["fit", "--no-color"])).unwrap();
+        assert!(cli.no_color);
+    }
+}
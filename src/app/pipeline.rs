@@ -5,61 +5,669 @@
 //!
 //! The CLI and the TUI can then focus on presentation (printing vs widgets).
 
-use crate::data::{FredClient, FredSnapshot, SampleData, generate_sample};
-use crate::domain::{BondResidual, FitConfig};
+use std::time::Instant;
+
+use rayon::prelude::*;
+
+use crate::data::{FredClient, FredSnapshot, SnapshotOptions, generate_sample};
+use crate::domain::{BondPoint, CurveModel, FitConfig, RatingBand};
 use crate::error::AppError;
-use crate::fit::selection::FitSelection;
 use crate::io::ingest::IngestedData;
-use crate::report::Rankings;
-
-/// All computed outputs of a single `rv fit` run.
-#[derive(Debug, Clone)]
-pub struct RunOutput {
-    pub ingest: IngestedData,
-    pub selection: FitSelection,
-    pub residuals: Vec<BondResidual>,
-    pub rankings: Rankings,
-    pub sample: SampleData,
-    pub snapshot: FredSnapshot,
+use crate::math::Fnv1a;
+use crate::report::{BatchCell, BatchRun, DatePair, PhaseTimings, RunOutput};
+
+/// `FitConfig` fields that only shape output (tables, plots, exports, progress)
+/// and are left out of `run_hash`. Every other field is hashed, so a new
+/// setting is part of the run identity unless it is listed here.
+const RUN_HASH_IGNORED: &[&str] = &[
+    "api_key_file",
+    "progress",
+    "top_n",
+    "top_cheap",
+    "top_rich",
+    "rank_by",
+    "signal_weights",
+    "locale",
+    "compact",
+    "output_format",
+    "timing",
+    "plot",
+    "plot_width",
+    "plot_height",
+    "plot_baseline",
+    "plot_vol",
+    "plot_hash",
+    "export_results",
+    "export_append",
+    "export_curve",
+    "export_landscape",
+    "export_timeseries",
+    "compare_models",
+    "export_models",
+    "export_sample",
+    "export_stats",
+    "export_diagnostics",
+    "embed_inputs",
+    "pillars",
+    "pillars_json",
+    "export_formula",
+    "formula_out",
+    "baseline_curve",
+    "rank_by_shift",
+    "fail_on_warnings",
+    "influence",
+    "all_ratings",
+    "threads",
+    "seed_sweep",
+    "explain_selection",
+    "diagnostics",
+    "slopes",
+    "resid_bins",
+    "round_tenors",
+    "max_extrap_years",
+];
+
+/// Short hash identifying a run: the sample seed (snapshot + sampling settings)
+/// combined with the serialized `FitConfig`, minus `RUN_HASH_IGNORED`.
+///
+/// FNV-1a over explicit bytes, so identical inputs produce the same hash on
+/// any build or platform.
+pub fn run_hash(run: &RunOutput, config: &FitConfig) -> u64 {
+    let mut settings = serde_json::to_value(config).expect("FitConfig serializes to JSON");
+    if let Some(fields) = settings.as_object_mut() {
+        for key in RUN_HASH_IGNORED {
+            fields.remove(*key);
+        }
+    }
+    let mut hasher = Fnv1a::new();
+    hasher.write(&run.sample.seed.to_le_bytes());
+    hasher.write(settings.to_string().as_bytes());
+    hasher.finish()
 }
 
+/// Requested vs resolved as-of gap (days) above which we warn.
+const ASOF_WARN_DAYS: i64 = 4;
+
 /// Execute the full fitting pipeline and return the computed outputs.
 pub fn run_fit(config: &FitConfig) -> Result<RunOutput, AppError> {
     // 1) Fetch FRED data.
-    let client = FredClient::from_env()?;
-    let snapshot = client.fetch_snapshot(None)?;
+    let started = Instant::now();
+    let client = FredClient::new(config.api_key_file.as_deref())?;
+    let snapshot = client.fetch_snapshot(&SnapshotOptions::from_config(config)?)?;
+    let fetch = started.elapsed();
 
-    run_fit_with_snapshot(config, snapshot)
+    let mut run = run_fit_with_snapshot(config, snapshot)?;
+    run.timings.fetch = Some(fetch);
+    Ok(run)
 }
 
 /// Execute the fitting pipeline with a pre-fetched snapshot.
 ///
 /// This is useful for the TUI where we want to refit without re-fetching.
 pub fn run_fit_with_snapshot(config: &FitConfig, snapshot: FredSnapshot) -> Result<RunOutput, AppError> {
-    // 2) Generate synthetic sample from FRED data.
-    let sample = generate_sample(&snapshot, config)?;
+    let mut warnings = Vec::new();
+    if let Some(requested) = config.asof {
+        let gap = (requested - snapshot.date).num_days();
+        if gap.abs() > ASOF_WARN_DAYS {
+            warnings.push(format!(
+                "Requested as-of {requested} resolved to FRED observation date {} ({gap} days apart).",
+                snapshot.date
+            ));
+        }
+    }
+
+    if !snapshot.forward_filled.is_empty() {
+        let fills: Vec<String> = snapshot
+            .forward_filled
+            .iter()
+            .map(|(series, from)| format!("{series} from {from}"))
+            .collect();
+        warnings.push(format!(
+            "--date-policy latest-overall: forward-filled to {}: {}.",
+            snapshot.date,
+            fills.join(", ")
+        ));
+    }
+
+    if snapshot.interpolated_ratings.contains(&config.rating) {
+        warnings.push(format!(
+            "Rating {} is missing from FRED; its level ({:.1}bp) was interpolated from neighbouring bands (--missing-rating interp).",
+            config.rating.display_name(),
+            snapshot.ratings_bp.get(&config.rating).copied().unwrap_or(f64::NAN)
+        ));
+    }
+
+    // Volatility fallbacks that feed this rating's noise model.
+    let ids = &snapshot.series;
+    let mut relevant = vec![ids.rating(config.rating)];
+    relevant.extend(ids.buckets());
+    for series in &snapshot.volatility.fallback_series {
+        if relevant.contains(&series.as_str()) {
+            warnings.push(format!(
+                "Volatility for series {series} could not be computed; using fallback {:.0}% daily.",
+                crate::data::fred::FALLBACK_VOL * 100.0
+            ));
+        }
+    }
+
+    // 2) Generate synthetic sample from FRED data (or load the real bonds).
+    let mut timings = PhaseTimings::default();
+    let started = Instant::now();
+    let sample = match &config.points_file {
+        Some(path) => {
+            let points = crate::io::points::load_bond_points(path, snapshot.date)?;
+            crate::data::sample::sample_from_points(&snapshot, config, points)?
+        }
+        None => generate_sample(&snapshot, config)?,
+    };
+    if sample.vol_capped > 0 || sample.vol_floored > 0 {
+        warnings.push(format!(
+            "Injected noise clamped for {} of {} bond(s): {} at --vol-cap, {} at --vol-floor.",
+            sample.vol_capped + sample.vol_floored,
+            sample.points.len(),
+            sample.vol_capped,
+            sample.vol_floored
+        ));
+    }
 
     // 3) Convert to IngestedData for the fit pipeline.
-    let ingest = IngestedData::from_sample(
+    let mut ingest = IngestedData::from_sample(
         sample.points.clone(),
         sample.spec.clone(),
         sample.stats.clone(),
     );
 
-    // 4) Fit curves and select the best model per config.
-    let selection =
-        crate::fit::selection::fit_and_select(&ingest.points, &ingest.input_spec, config)?;
+    // Only in-bound, finite points reach the fit; the drops are reported.
+    ingest.retain_usable(config.tenor_min, config.tenor_max)?;
+
+    // Present y-values in the requested unit (the fit is unit-invariant).
+    ingest.convert_unit(config.output_unit);
+
+    // Optional per-tenor weight multipliers.
+    if let Some(path) = &config.weight_file {
+        let ranges = crate::io::weights::read_weight_file(path)?;
+        crate::io::weights::apply_tenor_weights(&mut ingest.points, &ranges);
+    }
+    let dropped = ingest.retain_valid_weights(config.on_bad_weight)?;
+    if dropped > 0 {
+        warnings.push(format!(
+            "Dropped {dropped} bond(s) with a non-finite or non-positive weight (--on-bad-weight drop)."
+        ));
+    }
+    if let Some(cap) = config.issuer_cap {
+        let capped = crate::io::weights::apply_issuer_cap(&mut ingest.points, cap)?;
+        if capped.issuers > 0 {
+            warnings.push(format!(
+                "--issuer-cap {cap}: downweighted {} issuer(s) ({} bond(s)) to {:.1}% of the total weight each.",
+                capped.issuers,
+                capped.bonds,
+                cap * 100.0
+            ));
+        }
+    }
+
+    timings.sample = started.elapsed();
+
+    // 4) Fit curves and select the best model per config, on the fit window
+    //    only when one is set (its bounds stand in for the coverage guard's).
+    let started = Instant::now();
+    let windowed = fit_window_points(&ingest.points, config)?;
+    let (fit_points, fit_config) = match &windowed {
+        Some((points, config)) => (points.as_slice(), config),
+        None => (ingest.points.as_slice(), config),
+    };
+    ingest.accounting.outside_fit_window = ingest.points.len() - fit_points.len();
+    if let Some((lo, hi)) = config.fit_window().filter(|_| fit_points.is_empty()) {
+        return Err(AppError::new(
+            3,
+            format!("No bonds to fit in the fit window [{lo}, {hi}]y: {}.", ingest.accounting.drop_summary()),
+        ));
+    }
+    let mut selection = crate::fit::selection::fit_and_select(fit_points, &ingest.input_spec, fit_config)?;
+    if let Some(at) = config.splice_at {
+        selection = crate::fit::splice::splice_selection(fit_points, &ingest.input_spec, fit_config, at, selection)?;
+    }
+    if let Some((lo, hi)) = config.fit_window() {
+        selection.explanation.push(format!(
+            "Fit window [{lo}, {hi}]y: fitted {} of {} bond(s); all are ranked against the curve.",
+            fit_points.len(),
+            ingest.points.len()
+        ));
+    }
+    timings.fit = started.elapsed();
+    timings.models = selection.durations.clone();
+    warnings.extend(selection.warnings.iter().cloned());
 
     // 5) Compute residuals and rankings.
-    let residuals = crate::report::compute_residuals(&ingest.points, &selection.best)?;
-    let rankings = crate::report::rank_cheap_rich(&residuals, config.top_n);
+    let started = Instant::now();
+    let mut residuals = crate::report::compute_residuals(&ingest.points, &selection.best)?;
+    crate::report::assign_signals(&mut residuals, &config.signal_weights);
+    let (top_cheap, top_rich) = config.top_counts();
+    let rankings = crate::report::rank_cheap_rich(&residuals, top_cheap, top_rich, config.rank_by);
+    timings.rank = started.elapsed();
 
     Ok(RunOutput {
         ingest,
+        fit_points: windowed.map(|(points, _)| points),
         selection,
         residuals,
         rankings,
         sample,
         snapshot,
+        warnings,
+        timings,
+    })
+}
+
+/// Bonds inside `config.fit_window()` and the config to fit them with, or
+/// `None` when no window is set.
+fn fit_window_points(points: &[BondPoint], config: &FitConfig) -> Result<Option<(Vec<BondPoint>, FitConfig)>, AppError> {
+    let Some((lo, hi)) = config.fit_window() else {
+        return Ok(None);
+    };
+    if !(lo.is_finite() && hi.is_finite() && lo >= 0.0 && lo < hi) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid fit window [{lo}, {hi}]: --fit-min must be >= 0 and below --fit-max."),
+        ));
+    }
+    let inside: Vec<BondPoint> = points.iter().filter(|p| (lo..=hi).contains(&p.tenor)).cloned().collect();
+    Ok(Some((inside, FitConfig { tenor_min: lo, tenor_max: hi, ..config.clone() })))
+}
+
+/// Fit `config` on both snapshots with one sample seed, so the bonds share
+/// tenors and noise draws and only the market data differs.
+///
+/// The seed is `config.rng_seed` when pinned, else the one `from` derives.
+pub fn run_date_pair(config: &FitConfig, from: FredSnapshot, to: FredSnapshot) -> Result<DatePair, AppError> {
+    let from = run_fit_with_snapshot(config, from)?;
+    let pinned = FitConfig { rng_seed: Some(from.sample.seed), ..config.clone() };
+    let to = run_fit_with_snapshot(&pinned, to)?;
+
+    let model = &from.selection.best.model;
+    let held = match model.splice {
+        Some(_) => None,
+        None => {
+            let windowed = fit_window_points(&to.ingest.points, config)?;
+            let points = windowed.as_ref().map_or(to.ingest.points.as_slice(), |(points, _)| points.as_slice());
+            let transformed = crate::fit::selection::transform_points(points, config.y_transform)?;
+            let points = transformed.as_deref().unwrap_or(points);
+            let taus = std::slice::from_ref(&model.taus);
+            let fit = crate::fit::fitter::fit_model(model.name, points, taus, &Default::default())?;
+            Some(CurveModel { betas: fit.betas, ..model.clone() })
+        }
+    };
+    Ok(DatePair { from, to, held })
+}
+
+/// Fit several ratings in parallel against a single FRED snapshot.
+pub fn run_batch(config: &FitConfig, ratings: &[RatingBand]) -> Result<BatchRun, AppError> {
+    let client = FredClient::new(config.api_key_file.as_deref())?;
+    let snapshot = client.fetch_snapshot(&SnapshotOptions::from_config(config)?)?;
+    run_batch_with_snapshot(config, &snapshot, ratings)
+}
+
+/// Fit several ratings in parallel against a pre-fetched snapshot.
+///
+/// All work (the batch loop and each fit's inner tau search) runs on one Rayon
+/// pool sized by `config.threads`, so total thread usage respects the budget.
+/// A failing cell is captured in its `BatchCell` rather than aborting the batch.
+/// Cells are returned sorted by date, then rating. Ratings absent from the
+/// snapshot (only possible with `--missing-rating skip`) are reported in
+/// `BatchRun::skipped` instead of as failed cells.
+pub fn run_batch_with_snapshot(
+    config: &FitConfig,
+    snapshot: &FredSnapshot,
+    ratings: &[RatingBand],
+) -> Result<BatchRun, AppError> {
+    let (ratings, skipped): (Vec<RatingBand>, Vec<RatingBand>) =
+        ratings.iter().partition(|r| snapshot.ratings_bp.contains_key(r));
+
+    let pool = thread_pool(config)?;
+    let mut cells: Vec<BatchCell> = pool.install(|| {
+        ratings
+            .par_iter()
+            .map(|&rating| {
+                // Concurrent progress lines would interleave; the table is the feedback.
+                let cell_config = FitConfig {
+                    rating,
+                    progress: false,
+                    ..config.clone()
+                };
+                BatchCell {
+                    date: snapshot.date,
+                    rating,
+                    result: run_fit_with_snapshot(&cell_config, snapshot.clone()),
+                }
+            })
+            .collect()
+    });
+
+    cells.sort_by_key(|c| (c.date, c.rating));
+    Ok(BatchRun { cells, skipped })
+}
+
+/// Rayon pool for multi-fit runs, sized by `config.threads`.
+fn thread_pool(config: &FitConfig) -> Result<rayon::ThreadPool, AppError> {
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(n) = config.threads {
+        builder = builder.num_threads(n);
+    }
+    builder
+        .build()
+        .map_err(|e| AppError::new(1, format!("Failed to start batch thread pool: {e}")))
+}
+
+/// Noise seed for sweep iteration `i`: a fixed odd-stride walk from the base seed
+/// (the explicit `--deterministic-seed`, else `--seed`).
+pub fn sweep_seed(config: &FitConfig, i: usize) -> u64 {
+    let base = config.rng_seed.unwrap_or(config.sample_seed);
+    base.wrapping_add((i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Fetch once, then refit the same rating over `n` derived seeds.
+pub fn run_seed_sweep(config: &FitConfig, n: usize) -> Result<Vec<RunOutput>, AppError> {
+    let client = FredClient::new(config.api_key_file.as_deref())?;
+    let snapshot = client.fetch_snapshot(&SnapshotOptions::from_config(config)?)?;
+    run_seed_sweep_with_snapshot(config, &snapshot, n)
+}
+
+/// Refit `n` resampled copies of the run in parallel (on the `--threads` pool).
+///
+/// Only the noise is redrawn: every seed keeps the base seed's tenors, so a
+/// bond ID names the same bond in every run. Runs are returned in seed order;
+/// the first failing seed aborts the sweep.
+pub fn run_seed_sweep_with_snapshot(
+    config: &FitConfig,
+    snapshot: &FredSnapshot,
+    n: usize,
+) -> Result<Vec<RunOutput>, AppError> {
+    let pool = thread_pool(config)?;
+    pool.install(|| {
+        (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let seed_config = FitConfig {
+                    noise_seed: Some(sweep_seed(config, i)),
+                    progress: false,
+                    ..config.clone()
+                };
+                run_fit_with_snapshot(&seed_config, snapshot.clone())
+            })
+            .collect()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::sample::tests::test_snapshot;
+    use crate::fit::selection::tests::make_test_config;
+
+    #[test]
+    fn batch_sorts_cells_and_isolates_failures() {
+        let mut snapshot = test_snapshot();
+        // AA has an invalid level (per-cell failure); A is absent (skipped).
+        snapshot.ratings_bp.insert(RatingBand::AA, 0.0);
+        snapshot.ratings_bp.remove(&RatingBand::A);
+
+        let mut config = make_test_config();
+        config.sample_count = 60;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+        config.threads = Some(2);
+
+        let ratings = [RatingBand::BBB, RatingBand::A, RatingBand::AA, RatingBand::AAA];
+        let batch = run_batch_with_snapshot(&config, &snapshot, &ratings).unwrap();
+        assert_eq!(batch.skipped, vec![RatingBand::A]);
+        let cells = batch.cells;
+
+        let order: Vec<RatingBand> = cells.iter().map(|c| c.rating).collect();
+        assert_eq!(order, vec![RatingBand::AAA, RatingBand::AA, RatingBand::BBB]);
+        assert!(cells[0].result.is_ok());
+        assert!(cells[1].result.is_err());
+        assert!(cells[2].result.is_ok());
+        assert_eq!(cells[2].result.as_ref().unwrap().ingest.points.len(), 60);
+    }
+
+    #[test]
+    fn compact_line_is_stable_and_hash_tracks_config() {
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.sample_count = 60;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        let again = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        assert_eq!(run_hash(&run, &config), run_hash(&again, &config));
+        // Timings are recorded but are not part of the run identity.
+        assert!(run.timings.fetch.is_none());
+        assert!(run.timings.total() >= run.timings.fit);
+
+        let line = crate::report::format_compact_summary(&run.ingest, &run.selection, &config, run_hash(&run, &config));
+        let keys: Vec<&str> = line.split(' ').map(|kv| kv.split('=').next().unwrap()).collect();
+        assert_eq!(keys, vec!["date", "rating", "model", "n", "rmse", "bic", "run"]);
+
+        let base = run_hash(&run, &config);
+        // Output-only settings leave the hash alone; anything the fit reads changes it.
+        let mut exported = config.clone();
+        exported.export_curve = Some("curve.json".into());
+        exported.plot = true;
+        exported.round_tenors = Some(0.25);
+        exported.max_extrap_years = Some(5.0);
+        assert_eq!(base, run_hash(&run, &exported));
+        for change in [
+            |c: &mut FitConfig| c.tau_steps_ns += 1,
+            |c: &mut FitConfig| c.smooth_days += 1,
+            |c: &mut FitConfig| c.on_bad_weight = crate::domain::BadWeight::Error,
+            |c: &mut FitConfig| c.missing_rating = crate::domain::MissingRating::Skip,
+            |c: &mut FitConfig| c.no_prior = true,
+        ] {
+            let mut changed = config.clone();
+            change(&mut changed);
+            assert_ne!(base, run_hash(&run, &changed), "{changed:?}");
+        }
+    }
+
+    #[test]
+    fn decimal_output_unit_scales_betas_and_keeps_fit() {
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.sample_count = 80;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+
+        let bp = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        config.output_unit = crate::domain::YUnit::Decimal;
+        let dec = run_fit_with_snapshot(&config, snapshot).unwrap();
+
+        assert_eq!(dec.ingest.input_spec.y_unit_label(), "decimal");
+        assert_eq!(bp.selection.best.model.name, dec.selection.best.model.name);
+        assert_eq!(bp.selection.best.model.taus, dec.selection.best.model.taus);
+        for (a, b) in bp.selection.best.model.betas.iter().zip(&dec.selection.best.model.betas) {
+            assert!((a * 1e-4 - b).abs() <= 1e-9 * a.abs().max(1.0), "{a} vs {b}");
+        }
+        let ids = |r: &RunOutput| r.rankings.cheap.iter().map(|x| x.point.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&bp), ids(&dec));
+    }
+
+    #[test]
+    fn points_file_replaces_the_synthetic_sample() {
+        let path = std::env::temp_dir().join(format!("rv-points-{}.csv", std::process::id()));
+        let mut csv = String::from("id,tenor,oas\n");
+        for i in 0..30 {
+            let t = 0.5 + i as f64 * 0.6;
+            csv.push_str(&format!("R{i},{t},{}\n", 80.0 + 40.0 * (1.0 - (-t / 3.0_f64).exp())));
+        }
+        std::fs::write(&path, csv).unwrap();
+
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.tenor_min = 0.25;
+        config.tenor_max = 30.0;
+        config.points_file = Some(path.clone());
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        assert_eq!(run.ingest.points.len(), 30);
+        assert!(run.residuals.iter().all(|r| r.point.id.starts_with('R')));
+        assert_eq!(run.sample.baseline.len(), 30);
+        assert_eq!(run.ingest.input_spec.asof_date, snapshot.date);
+
+        config.no_prior = true;
+        let bare = run_fit_with_snapshot(&config, snapshot).unwrap();
+        assert!(bare.sample.baseline.is_empty());
+        assert_eq!(bare.selection.best.model.betas, run.selection.best.model.betas);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn single_model_spec_fits_only_that_model() {
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.model_spec = crate::domain::ModelSpec::Nss;
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        assert_eq!(run.selection.fits.len(), 1);
+        assert_eq!(run.timings.models.len(), 1);
+        assert_eq!(
+            crate::report::format_rank_model(&run.selection, config.model_spec),
+            "model: NSS (--model nss; no other models fitted)"
+        );
+
+        config.model_spec = crate::domain::ModelSpec::All;
+        let all = run_fit_with_snapshot(&config, snapshot).unwrap();
+        let line = crate::report::format_rank_model(&all.selection, config.model_spec);
+        assert!(line.ends_with(&format!("(selected by BIC from {} fitted)", all.selection.fits.len())), "{line}");
+    }
+
+    #[test]
+    fn fit_window_fits_the_belly_but_ranks_every_bond() {
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.model_spec = crate::domain::ModelSpec::Ns;
+        (config.tenor_min, config.tenor_max) = (0.25, 30.0);
+        config.fit_min = Some(2.0);
+        config.fit_max = Some(10.0);
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+
+        let inside = run.ingest.points.iter().filter(|p| (2.0..=10.0).contains(&p.tenor)).count();
+        assert!(inside < run.ingest.points.len());
+        assert_eq!(run.selection.best.quality.n, inside);
+        assert_eq!(run.residuals.len(), run.ingest.points.len());
+        assert!(run.residuals.iter().any(|r| r.point.tenor > 10.0));
+
+        let summary = crate::report::build_run_summary(&run, &config);
+        let text = crate::report::format_run_summary(&summary, config.locale);
+        assert!(text.contains(&format!("Fit window: tenor=[2.00, 10.00]y | fitted={inside} of ")), "{text}");
+        let unwindowed = make_test_config();
+        assert_ne!(run_hash(&run, &config), run_hash(&run, &unwindowed));
+
+        config.fit_max = Some(1.0);
+        assert_eq!(run_fit_with_snapshot(&config, snapshot).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn fit_window_diagnostics_use_only_the_fitted_bonds() {
+        let mut config = make_test_config();
+        config.model_spec = crate::domain::ModelSpec::Ns;
+        (config.tenor_min, config.tenor_max) = (0.25, 30.0);
+        (config.fit_min, config.fit_max) = (Some(2.0), Some(10.0));
+        let run = run_fit_with_snapshot(&config, test_snapshot()).unwrap();
+
+        let inside = run.fitted_points().len();
+        assert_eq!(inside, run.selection.best.quality.n);
+        let acc = run.ingest.accounting;
+        assert_eq!((acc.used(), acc.ranked()), (inside, run.ingest.points.len()));
+        assert_eq!(acc.outside_fit_window, run.ingest.points.len() - inside);
+
+        let rows = crate::fit::selection::compare_models(run.fitted_points(), &run.selection);
+        assert!(rows.iter().all(|r| r.n == inside), "{rows:?}");
+        let influence = crate::fit::influence::jackknife_influence(run.fitted_points(), &run.selection.best, &config).unwrap();
+        assert_eq!(influence.len(), inside);
+        assert!(influence.iter().all(|b| (2.0..=10.0).contains(&b.tenor)));
+
+        let summary = crate::report::build_run_summary(&run, &config);
+        let text = crate::report::format_run_summary(&summary, config.locale);
+        assert!(text.contains(&format!("| outside fit window={} | used={inside}\n", acc.outside_fit_window)), "{text}");
+    }
+
+    #[test]
+    fn empty_fit_window_itemizes_the_drops() {
+        let mut config = make_test_config();
+        (config.tenor_min, config.tenor_max) = (0.25, 30.0);
+        (config.fit_min, config.fit_max) = (Some(40.0), Some(50.0));
+        let err = run_fit_with_snapshot(&config, test_snapshot()).unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+        let text = err.to_string();
+        assert!(text.starts_with("No bonds to fit in the fit window [40, 50]y: 100 generated; dropped "), "{text}");
+        assert!(text.ends_with("0 by issuer cap (reweights only), 100 outside the fit window."), "{text}");
+    }
+
+    #[test]
+    fn exported_sample_refits_to_the_same_curve() {
+        let path = std::env::temp_dir().join(format!("rv-sample-{}.csv", std::process::id()));
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.weight_mode = crate::domain::WeightMode::InverseVar;
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        crate::io::export::write_sample_csv(&path, &run.sample).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let first = text.lines().nth(1).unwrap();
+        assert!(first.ends_with(&format!(",{}", run.sample.baseline[0])), "{first}");
+
+        config.points_file = Some(path.clone());
+        let refit = run_fit_with_snapshot(&config, snapshot).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(refit.sample.points.len(), run.sample.points.len());
+        assert_eq!(refit.sample.baseline, run.sample.baseline);
+        assert_eq!(refit.selection.best.model.name, run.selection.best.model.name);
+        assert_eq!(refit.selection.best.model.betas, run.selection.best.model.betas);
+        assert_eq!(refit.selection.best.model.taus, run.selection.best.model.taus);
+    }
+
+    #[test]
+    fn vol_fallback_for_fitted_rating_is_a_warning() {
+        let mut snapshot = test_snapshot();
+        snapshot.volatility.fallback_series = vec![RatingBand::CCC.series_id().to_string()];
+        let mut config = make_test_config();
+        config.sample_count = 40;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+
+        // Another rating's fallback does not affect a BBB run.
+        let run = run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        assert!(run.warnings.is_empty(), "{:?}", run.warnings);
+
+        config.rating = RatingBand::CCC;
+        let run = run_fit_with_snapshot(&config, snapshot).unwrap();
+        assert_eq!(run.warnings.len(), 1);
+        assert!(run.warnings[0].contains("BAMLH0A3HYC"), "{:?}", run.warnings);
+    }
+
+    #[test]
+    fn seed_sweep_is_deterministic_and_varies_samples() {
+        let snapshot = test_snapshot();
+        let mut config = make_test_config();
+        config.sample_count = 40;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+        config.threads = Some(2);
+
+        let runs = run_seed_sweep_with_snapshot(&config, &snapshot, 3).unwrap();
+        let again = run_seed_sweep_with_snapshot(&config, &snapshot, 3).unwrap();
+        let obs = |runs: &[RunOutput]| -> Vec<f64> { runs.iter().map(|r| r.ingest.points[0].y_obs).collect() };
+        assert_eq!(obs(&runs), obs(&again));
+        assert_ne!(runs[0].ingest.points[0].y_obs, runs[1].ingest.points[0].y_obs);
+        // Tenors are held across seeds, so an ID is the same bond in every run.
+        let tenors = |run: &RunOutput| -> Vec<f64> { run.sample.points.iter().map(|p| p.tenor).collect() };
+        assert!(runs.iter().all(|r| tenors(r) == tenors(&runs[0])));
+
+        let stability = crate::report::compute_bond_stability(&runs);
+        assert_eq!(stability.len(), 40);
+        assert!(stability.windows(2).all(|w| w[0].score.abs() >= w[1].score.abs()));
+        assert!(stability.iter().all(|b| b.cheap_hits + b.rich_hits <= 3));
+        let first = &runs[0].sample.points;
+        assert!(stability.iter().all(|b| first.iter().any(|p| p.id == b.id && p.tenor == b.tenor)));
+    }
+}
+
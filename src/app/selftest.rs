@@ -0,0 +1,101 @@
+//! `rv selftest`: offline self-consistency check for CI.
+//!
+//! Fits a fixed synthetic sample drawn from `canned_snapshot` (no FRED access,
+//! no API key) and checks that the selected curve recovers the generating
+//! baseline (`baseline_curve`) at a handful of tenors. The sample seed is
+//! pinned, so the outcome is deterministic; the tolerances leave room for the
+//! injected noise and for the NS family not matching the piecewise baseline
+//! exactly, while still catching a broken sampler, fitter, or selector.
+
+use clap::Parser;
+
+use crate::cli::FitArgs;
+use crate::data::sample::{baseline_curve, canned_snapshot};
+use crate::domain::RatingBand;
+use crate::error::AppError;
+
+/// Tenors (years) at which the fitted curve is compared with the baseline.
+pub const SELFTEST_TENORS: [f64; 7] = [1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 20.0];
+
+/// Maximum allowed `|fitted - baseline| / baseline` at each checked tenor.
+pub const SELFTEST_REL_TOL: f64 = 0.08;
+
+/// Synthetic bonds drawn for the check (more than the default, to tame noise).
+pub const SELFTEST_SAMPLE_COUNT: usize = 400;
+
+/// Pinned RNG seed for the sample.
+pub const SELFTEST_SEED: u64 = 20_250_102;
+
+/// One tenor comparison.
+#[derive(Debug, Clone)]
+pub struct SelftestCheck {
+    pub tenor: f64,
+    pub fitted: f64,
+    pub baseline: f64,
+    pub rel_err: f64,
+    pub passed: bool,
+}
+
+/// Run the fit on the canned snapshot and compare against the baseline.
+pub fn run_selftest() -> Result<Vec<SelftestCheck>, AppError> {
+    let args = FitArgs::try_parse_from(["rv"])
+        .map_err(|e| AppError::new(1, format!("Selftest could not build default arguments: {e}")))?;
+    let mut config = crate::app::fit_config_from_args(&args);
+    config.rating = RatingBand::BBB;
+    config.sample_count = SELFTEST_SAMPLE_COUNT;
+    config.rng_seed = Some(SELFTEST_SEED);
+    config.progress = false;
+
+    let snapshot = canned_snapshot();
+    let run = crate::app::pipeline::run_fit_with_snapshot(&config, snapshot.clone())?;
+    let model = &run.selection.best.model;
+    let scale = run.ingest.input_spec.y_unit.per_bp();
+
+    SELFTEST_TENORS
+        .iter()
+        .map(|&tenor| {
+            let baseline = baseline_curve(&snapshot, config.rating, tenor)? * scale;
+            let fitted = model.eval(tenor);
+            let rel_err = (fitted - baseline).abs() / baseline.abs();
+            Ok(SelftestCheck {
+                tenor,
+                fitted,
+                baseline,
+                rel_err,
+                passed: rel_err <= SELFTEST_REL_TOL,
+            })
+        })
+        .collect()
+}
+
+/// One `PASS`/`FAIL` line per check plus a summary line.
+pub fn format_selftest(checks: &[SelftestCheck]) -> String {
+    let mut out = String::new();
+    for c in checks {
+        out.push_str(&format!(
+            "{} tenor={:>5.2}y fitted={:>9.3} baseline={:>9.3} rel_err={:.4} (tol {SELFTEST_REL_TOL})\n",
+            if c.passed { "PASS" } else { "FAIL" },
+            c.tenor,
+            c.fitted,
+            c.baseline,
+            c.rel_err
+        ));
+    }
+    let failed = checks.iter().filter(|c| !c.passed).count();
+    out.push_str(&format!("selftest: {} passed, {failed} failed\n", checks.len() - failed));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selftest_passes_on_the_canned_snapshot() {
+        let checks = run_selftest().unwrap();
+        assert_eq!(checks.len(), SELFTEST_TENORS.len());
+        let report = format_selftest(&checks);
+        assert!(checks.iter().all(|c| c.passed), "{report}");
+        assert!(report.ends_with("7 passed, 0 failed\n"), "{report}");
+    }
+}
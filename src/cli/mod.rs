@@ -5,18 +5,51 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand, ValueEnum};
 
-use crate::domain::{ModelSpec, RatingBand};
+use crate::domain::{
+    BadWeight, Basis, BinEdges, DatePolicy, FormulaLang, GridExtrapolation, MissingRating, ModelSpec, NumberLocale, OutputFormat, RankBy, RatingBand, RatingBlend, ShortVolHistory, SignalWeights, TauTiebreak, TenorPair, WeightMode, YTransform, YUnit,
+};
 
 pub mod picker;
 
 /// Top-level CLI.
 #[derive(Debug, Parser)]
-#[command(name = "rv", version, about = "Fixed-Income RV Curve Fitter (FRED-based)")]
+#[command(
+    name = "rv",
+    version,
+    about = "Fixed-Income RV Curve Fitter (FRED-based)",
+    arg_required_else_help = true,
+    after_help = "Bare `rv` (or `rv` followed by flags) runs `rv tui`, or `rv fit` when not on a terminal.\n\
+                  Set RV_NO_DEFAULT_TUI=1 to disable this and print help instead."
+)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
+
+    /// Disable ANSI colour everywhere (also implied by a non-empty `NO_COLOR`
+    /// environment variable, or by stdout not being a terminal).
+    #[arg(long, global = true)]
+    pub no_color: bool,
+}
+
+/// Whether to emit ANSI colour: off under `--no-color`, off whenever
+/// `NO_COLOR` is set to a non-empty value (regardless of TTY), otherwise on
+/// only when writing to a terminal.
+pub fn color_enabled(no_color: bool, no_color_env: Option<&std::ffi::OsStr>, is_tty: bool) -> bool {
+    if no_color || no_color_env.is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    is_tty
+}
+
+/// Value parser for a step or length in years that must be finite and > 0.
+fn positive_years(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(v) if v.is_finite() && v > 0.0 => Ok(v),
+        _ => Err(format!("'{s}': must be a positive number of years")),
+    }
 }
 
 /// CLI subcommands.
@@ -28,6 +61,34 @@ pub enum Command {
     Rank(FitArgs),
     /// Plot a previously exported curve JSON.
     Plot(PlotArgs),
+    /// Evaluate a previously exported curve JSON at given tenors.
+    Eval(EvalArgs),
+    /// Check a curve JSON for consistency (grid sanity, stored shape vs grid).
+    Validate(ValidateArgs),
+    /// Compare a curve JSON with today's FRED baseline for its rating (exits 5
+    /// when it deviates by more than --max-deviation).
+    Check(CheckArgs),
+    /// List rating bands and their FRED series IDs.
+    ListRatings(ListArgs),
+    /// List every FRED series the fit uses (overall, buckets, ratings).
+    ListSeries(ListArgs),
+    /// Fetch FRED snapshots on two dates and show how levels and vols moved.
+    CompareSnapshot(CompareSnapshotArgs),
+    /// Fit the same config on two dates and compare the curve move with the
+    /// input move, per tenor.
+    CompareDates(CompareDatesArgs),
+    /// Print the JSON Schema of an output file format.
+    Schema(SchemaArgs),
+    /// Fit a built-in synthetic snapshot offline and check the curve recovers its
+    /// generating baseline (for CI; exits 1 on failure).
+    Selftest,
+    /// Print the exit-code reference table.
+    #[command(hide = true)]
+    ExitCodes,
+    /// Check the f1/f2 basis functions are continuous across their small-x
+    /// series switch (exits 1 on failure).
+    #[command(hide = true)]
+    PrecisionCheck,
     /// Launch the interactive TUI.
     ///
     /// This uses the same underlying fit pipeline as `rv fit`, but renders results
@@ -42,18 +103,115 @@ pub struct FitArgs {
     #[arg(short = 'r', long, value_enum, default_value_t = RatingBand::BBB)]
     pub rating: RatingBand,
 
+    /// Draw the sample from a weighted blend of bands, e.g. `A:0.5,BBB:0.5`:
+    /// levels and vols are mixed by weight (non-negative, summing to 1).
+    /// Replaces `--rating`; labels use the heaviest band.
+    #[arg(long, value_name = "BAND:W,...", conflicts_with_all = ["rating", "all_ratings"])]
+    pub rating_blend: Option<RatingBlend>,
+
     /// Number of synthetic bonds to generate.
     #[arg(short = 'n', long, default_value_t = 100)]
     pub sample_count: usize,
 
+    /// Requested as-of date (YYYY-MM-DD), passed to FRED as `observation_end`.
+    ///
+    /// FRED resolves this to the latest date on or before it where all series have
+    /// data; the date actually used is reported in the summary and exports.
+    #[arg(long, value_name = "DATE")]
+    pub asof: Option<NaiveDate>,
+
+    /// Error if `--asof` is not a business day or FRED resolves it to a different
+    /// observation date (by default the earlier date is used, with a warning when
+    /// it is far from the request).
+    #[arg(long, requires = "asof")]
+    pub strict_dates: bool,
+
+    /// JSON file overriding FRED series IDs, e.g. for other credit indices:
+    /// `{"overall": ..., "bucket_13y": ..., "ratings": {"BBB": ...}}`. Any key may
+    /// be omitted to keep the ICE BofA default.
+    #[arg(long, value_name = "JSON")]
+    pub series_map: Option<PathBuf>,
+
+    /// Read the FRED API key from this file (surrounding whitespace trimmed).
+    ///
+    /// Precedence: this flag, then the file named by `FRED_API_KEY_FILE`, then
+    /// `FRED_API_KEY`.
+    #[arg(long, value_name = "PATH")]
+    pub api_key_file: Option<PathBuf>,
+
+    /// Use the trailing N-observation average of each FRED level (overall,
+    /// buckets, ratings) instead of the single as-of value. Volatility is still
+    /// estimated from raw daily returns; 1 uses the as-of value unchanged. A
+    /// series with fewer than N observations fails the run (exit 4).
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub smooth_days: usize,
+
+    /// Fit real bonds from this CSV (`id,tenor,oas` plus optional
+    /// `weight,rating,issuer`) instead of a synthetic sample. FRED still sets
+    /// the as-of date and the baseline curve used as the reference prior.
+    #[arg(long, value_name = "CSV", conflicts_with_all = ["all_ratings", "seed_sweep", "weight_mode"])]
+    pub sample_from_points: Option<PathBuf>,
+
+    /// With `--sample-from-points`, do not build the FRED baseline prior (no
+    /// baseline values, no baseline overlay).
+    #[arg(long, requires = "sample_from_points", conflicts_with = "plot_baseline")]
+    pub no_prior: bool,
+
+    /// What to do when a rating band has no FRED value on the snapshot date:
+    /// `error` (default), `interp` (synthesize from neighbouring bands, flagged in
+    /// warnings), or `skip` (leave it out; `--all-ratings` skips the band).
+    /// A failed FRED request (network, auth, unknown series ID) is always an
+    /// error.
+    #[arg(long, value_enum, default_value_t = MissingRating::Error)]
+    pub missing_rating: MissingRating,
+
+    /// How to pick the snapshot date: `common` (default; latest date present
+    /// in every series) or `latest-overall` (the overall index's latest date,
+    /// forward-filling lagging rating/bucket series from their most recent
+    /// prior value; each fill is listed in warnings).
+    #[arg(long, value_enum, default_value_t = DatePolicy::Common)]
+    pub date_policy: DatePolicy,
+
+    /// Require at least N usable daily returns per FRED series before trusting
+    /// its realized volatility. Shorter series fall back to the default 1%
+    /// daily vol with a warning, or fail the run with `--short-vol-history error`.
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub min_vol_obs: Option<usize>,
+
+    /// What to do with a series below `--min-vol-obs`: `warn` (fall back, the
+    /// default) or `error` (exit 4).
+    #[arg(long, value_enum, default_value_t = ShortVolHistory::Warn, requires = "min_vol_obs")]
+    pub short_vol_history: ShortVolHistory,
+
     /// Random seed for sample generation (combined with FRED data for reproducibility).
     #[arg(long, default_value_t = 42)]
     pub seed: u64,
 
+    /// Seed the sample RNG from OS entropy for a fresh sample; the seed used is
+    /// printed to stderr (`rv tui`: the status line) so the run can be
+    /// reproduced with `--deterministic-seed`.
+    #[arg(long, conflicts_with = "deterministic_seed")]
+    pub random_seed: bool,
+
+    /// Seed the sample RNG directly with this value (bypasses the snapshot/config
+    /// hash). Feed back a seed printed by `--random-seed` to reproduce its sample.
+    #[arg(long, value_name = "SEED")]
+    pub deterministic_seed: Option<u64>,
+
+    /// Base weighting of generated bonds: `uniform` (1.0 each) or `inverse-var`
+    /// (1/sigma_ln^2 of the injected noise, normalized to mean 1).
+    #[arg(long, value_enum, default_value_t = WeightMode::Uniform)]
+    pub weight_mode: WeightMode,
+
     /// Which model(s) to fit.
     #[arg(long, value_enum, default_value_t = ModelSpec::Auto)]
     pub model: ModelSpec,
 
+    /// Decay parameterization for reported parameters: `standard` (τ, years) or
+    /// `diebold-li` (λ = 1/τ, per year). Fitted curves are identical.
+    #[arg(long, value_enum, default_value_t = Basis::Standard)]
+    pub basis: Basis,
+
     /// Minimum tau (years) for grid search.
     #[arg(long, default_value_t = 0.05)]
     pub tau_min: f64,
@@ -74,6 +232,65 @@ pub struct FitArgs {
     #[arg(long, default_value_t = 15)]
     pub tau_steps_nssc: usize,
 
+    /// Tie-break among tau candidates with equal SSE.
+    #[arg(long, value_enum, default_value_t = TauTiebreak::LowIndex)]
+    pub tau_tiebreak: TauTiebreak,
+
+    /// Reject tau tuples whose solved betas exceed this magnitude (fitting
+    /// scale, output unit), steering the search away from huge offsetting
+    /// betas that extrapolate wildly. Falls back to the unbounded fit, with a
+    /// warning, if no tuple qualifies. Unbounded by default.
+    #[arg(long, value_name = "ABS")]
+    pub beta_abs_max: Option<f64>,
+
+    /// Reject tau tuples whose fitted level at t -> 0 (`β0 + β1`, mapped back
+    /// from --y-transform; output unit) is below this. Applied after --beta-abs-max, with the same
+    /// fallback and warning. Off by default.
+    #[arg(long, value_name = "LEVEL", allow_negative_numbers = true)]
+    pub y0_min: Option<f64>,
+
+    /// Reject tau tuples whose fitted level at t -> 0 is above this (output
+    /// unit). Off by default.
+    #[arg(long, value_name = "LEVEL", allow_negative_numbers = true)]
+    pub y0_max: Option<f64>,
+
+    /// Fit the curve to `y` (`none`) or to `ln(y)` (`log`, multiplicative
+    /// spreads). Residuals, plots, and exports stay on the original scale;
+    /// `log` rejects non-positive observations.
+    #[arg(long, value_enum, default_value_t = YTransform::None)]
+    pub y_transform: YTransform,
+
+    /// Prefer a simpler model whose BIC is within this many points of the minimum (0 = pure minimum BIC).
+    #[arg(long, value_name = "POINTS", default_value_t = 2.0)]
+    pub bic_margin: f64,
+
+    /// Refit the selected model separately to bonds below and at/above this
+    /// tenor (years) and splice the two curves, for kinks the NS family can't
+    /// follow. Falls back to the single fit (with a warning) when either side
+    /// has too few bonds for the model or its fit fails.
+    #[arg(long, value_name = "YEARS", conflicts_with_all = ["influence", "export_formula"])]
+    pub splice_at: Option<f64>,
+
+    /// Width (years, > 0) of the linear crossfade centred on `--splice-at`.
+    #[arg(long, value_name = "YEARS", default_value_t = 1.0, requires = "splice_at")]
+    pub splice_window: f64,
+
+    /// Show tau-search progress on stderr (ignored when stderr is not a terminal).
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Extra observations required beyond a model's parameter count (`n >= k + N`).
+    ///
+    /// Models that fail this guard are skipped as underdetermined.
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub min_n_buffer: usize,
+
+    /// Skip models beyond NS when the observed tenors span less than this
+    /// fraction (0-1) of `[--tenor-min, --tenor-max]`. The default only trips
+    /// on tightly clustered samples.
+    #[arg(long, value_name = "FRACTION", default_value_t = 0.1)]
+    pub min_coverage: f64,
+
     /// Minimum tenor (years) for generated samples.
     #[arg(long, default_value_t = 0.25)]
     pub tenor_min: f64,
@@ -82,10 +299,91 @@ pub struct FitArgs {
     #[arg(long, default_value_t = 30.0)]
     pub tenor_max: f64,
 
+    /// Fit the curve only to bonds with tenor >= this (years). Bonds outside
+    /// the fit window are still residual-ranked and plotted.
+    #[arg(long, value_name = "YEARS")]
+    pub fit_min: Option<f64>,
+
+    /// Fit the curve only to bonds with tenor <= this (years). Bonds outside
+    /// the fit window are still residual-ranked and plotted.
+    #[arg(long, value_name = "YEARS")]
+    pub fit_max: Option<f64>,
+
+    /// Place sample bonds on a fixed tenor grid with this spacing (years)
+    /// instead of drawing tenors uniformly; cycles through the grid when there
+    /// are more bonds than grid points. Level noise is unchanged.
+    #[arg(long, value_name = "STEP")]
+    pub tenor_grid_sample: Option<f64>,
+
+    /// Never let the curve JSON grid run more than this many years past the
+    /// longest observed tenor. The fit itself is unaffected.
+    #[arg(long, value_name = "YEARS")]
+    pub max_extrap_years: Option<f64>,
+
+    /// Round each bond's tenor to the nearest STEP years in the cheap/rich
+    /// tables and add a `tenor_rounded` column to `--export`. Cosmetic only:
+    /// the fit and residuals use the unrounded tenor.
+    #[arg(long, value_name = "STEP", value_parser = positive_years)]
+    pub round_tenors: Option<f64>,
+
     /// Show top-N cheap and rich names.
     #[arg(long, default_value_t = 20)]
     pub top: usize,
 
+    /// Number of cheap names to list and highlight (defaults to --top).
+    #[arg(long, value_name = "N")]
+    pub top_cheap: Option<usize>,
+
+    /// Number of rich names to list and highlight (defaults to --top). When
+    /// the two lists together exceed the sample, a bond already listed as
+    /// cheap is not listed as rich too.
+    #[arg(long, value_name = "N")]
+    pub top_rich: Option<usize>,
+
+    /// Order and show the cheap/rich tables by raw residual or by residual in
+    /// units of the fit's residual standard error.
+    #[arg(long, value_enum, default_value_t = RankBy::Residual)]
+    pub rank_by: RankBy,
+
+    /// Weights `R,Z,D` of the composite per-bond signal (`--rank-by signal`,
+    /// results CSV): |residual| relative to the largest, |z-score| capped at
+    /// 3 SE, and DV01 relative to the largest. Terms a bond lacks (DV01 for
+    /// synthetic bonds) are dropped and the rest reweighted.
+    #[arg(long, value_name = "R,Z,D", default_value = "1,1,1")]
+    pub signal_weights: SignalWeights,
+
+    /// Unit for reported and exported y-values (`decimal` = bp / 10,000).
+    #[arg(long, value_enum, default_value_t = YUnit::Bp)]
+    pub output_unit: YUnit,
+
+    /// Number formatting for printed tables and the summary: `plain` (1234.5),
+    /// `us` (1,234.5) or `eu` (1.234,5). CSV/JSON exports are never localized.
+    #[arg(long, value_enum, default_value_t = NumberLocale::Plain)]
+    pub locale: NumberLocale,
+
+    /// Print one greppable `key=value` line (date, rating, model, n, rmse, bic, run
+    /// hash) instead of the summary, rankings, and plot. Exports are still written.
+    #[arg(long, conflicts_with_all = ["explain_selection", "diagnostics", "influence", "baseline_curve", "all_ratings"])]
+    pub compact: bool,
+
+    /// Standard output format. `ndjson` streams one JSON object per bond
+    /// residual (cheapest to richest, with a `side` field) instead of the
+    /// summary, rankings, and plot; each line is flushed as it is written.
+    /// Warnings stay on stderr and exports are still written.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        conflicts_with_all = ["compact", "explain_selection", "diagnostics", "compare_models", "pillars", "influence", "baseline_curve", "all_ratings", "seed_sweep"]
+    )]
+    pub format: OutputFormat,
+
+    /// Print a wall-clock breakdown (FRED fetch, sample, fit, rank) after the
+    /// summary (to stderr for `rv rank`) and add it to `--export-diagnostics`
+    /// as `timings`. Results are unaffected.
+    #[arg(long, conflicts_with_all = ["compact", "all_ratings"])]
+    pub timing: bool,
+
     /// Render an ASCII plot in the terminal (enabled by default).
     #[arg(long, default_value_t = true)]
     pub plot: bool,
@@ -102,14 +400,213 @@ pub struct FitArgs {
     #[arg(long, default_value_t = 25)]
     pub height: usize,
 
+    /// Overlay the baseline curve the synthetic bonds were generated from as a
+    /// dotted line (ASCII plot and TUI chart), to see how well the fit recovers it.
+    #[arg(long)]
+    pub plot_baseline: bool,
+
+    /// Overlay the bucket volatility term structure (daily log-vol, %) on its
+    /// own right-hand axis (`~` in the ASCII plot, magenta in the TUI chart).
+    #[arg(long)]
+    pub plot_vol: bool,
+
+    /// Append the chosen curve's fingerprint (`hash=xxxxxxxx`) to the ASCII
+    /// plot header; identical parameters always give the same hash.
+    #[arg(long)]
+    pub plot_hash: bool,
+
+    /// TUI only: refit the curve when the terminal is resized. Without it a
+    /// resize just resamples the drawn curve for the new width, which is
+    /// usually all that is needed.
+    #[arg(long)]
+    pub refit_on_resize: bool,
+
+    /// TUI testing hook: feed these comma-separated keys (`m`, `g`, `up`,
+    /// `down`, `left`, `right`, `enter`, `esc`, `backspace`, any single
+    /// character) through the normal key handler, then render once to an
+    /// off-screen buffer and print it instead of opening the terminal UI.
+    #[arg(long, value_name = "KEYS", hide = true)]
+    pub tui_script: Option<String>,
+
+    /// CSV of per-tenor weight multipliers (`tenor_min,tenor_max,multiplier`).
+    ///
+    /// Applied multiplicatively to each bond's weight before fitting; bonds outside
+    /// every range keep multiplier 1.0.
+    #[arg(long, value_name = "CSV")]
+    pub weight_file: Option<PathBuf>,
+
+    /// Bonds whose final weight is non-finite or <= 0: `drop` fits without them
+    /// (with a warning and count), `error` fails the run.
+    #[arg(long, value_enum, default_value_t = BadWeight::Drop)]
+    pub on_bad_weight: BadWeight,
+
+    /// Cap each issuer's share of the total weight at FRACTION (0-1], scaling
+    /// an over-cap issuer's bonds down proportionally. Uses the `issuer`
+    /// column of `--sample-from-points`; bonds without one count as their own
+    /// issuer. Off by default.
+    #[arg(long, value_name = "FRACTION")]
+    pub issuer_cap: Option<f64>,
+
     /// Export per-bond results to CSV.
+    ///
+    /// With `--all-ratings`, every successful cell's rows go into this one file
+    /// (distinguished by the `asof_date` and `rating` columns).
     #[arg(long)]
     pub export: Option<PathBuf>,
 
+    /// Append to the `--export` CSV instead of overwriting it; the header is
+    /// written only when the file is new, and must match when it is not.
+    #[arg(long, requires = "export")]
+    pub export_append: bool,
+
     /// Export curve (model + params + fitted grid) to JSON.
     #[arg(long = "export-curve")]
     pub export_curve: Option<PathBuf>,
 
+    /// Export the selected model's tau-search landscape to CSV: one row per
+    /// evaluated candidate (`tau1..,sse,best`), with the winner flagged.
+    ///
+    /// Candidates are only retained when this flag is set.
+    #[arg(long, value_name = "CSV")]
+    pub export_landscape: Option<PathBuf>,
+
+    /// Append one row per fit (`date,model,n,rmse,bic,beta0..,tau1..`) to a CSV.
+    ///
+    /// The column set is the union of all models' parameters, so rows stay
+    /// rectangular across dates; repeated runs accumulate a time series.
+    #[arg(long, value_name = "CSV")]
+    pub export_timeseries: Option<PathBuf>,
+
+    /// Print a side-by-side table of every model (n, params, SSE, RMSE, MAE,
+    /// BIC, AIC, R²) with the selected one marked; skipped models are listed
+    /// with their reason. Most useful with `--model all`.
+    #[arg(long, conflicts_with_all = ["compact", "all_ratings", "seed_sweep"])]
+    pub compare_models: bool,
+
+    /// Export the model comparison table: JSON when PATH ends in `.json`,
+    /// CSV otherwise.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["all_ratings", "seed_sweep"])]
+    pub export_models: Option<PathBuf>,
+
+    /// Write the bonds the fit was drawn from (id, tenor, oas, weight, rating,
+    /// baseline) as a CSV that `--sample-from-points` reads back, to refit the
+    /// identical sample.
+    #[arg(long, value_name = "CSV", conflicts_with_all = ["all_ratings", "seed_sweep"])]
+    pub export_sample: Option<PathBuf>,
+
+    /// Write a one-row CSV summarizing the residuals: n, unweighted RMSE
+    /// (`rmse_unweighted`; the summary's RMSE uses the fit weights), MAE, mean,
+    /// std, skew, excess kurtosis, and the worst cheap and rich bond. Moments
+    /// the sample is too small for are left blank.
+    #[arg(long, value_name = "CSV", conflicts_with_all = ["all_ratings", "seed_sweep"])]
+    pub export_stats: Option<PathBuf>,
+
+    /// Write the run summary (sample, point accounting, per-model diagnostics,
+    /// chosen curve) as JSON to PATH.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["all_ratings", "seed_sweep"])]
+    pub export_diagnostics: Option<PathBuf>,
+
+    /// Embed the FRED inputs (overall, bucket, and rating levels and vols, after
+    /// smoothing and interpolation) in the `--export-diagnostics` JSON.
+    #[arg(long, requires = "export_diagnostics")]
+    pub embed_inputs: bool,
+
+    /// Print the fitted level at key tenors (years), labelled with the unit
+    /// and as-of date. Without a list: 1,2,3,5,7,10,20,30. Pillars outside the
+    /// observed tenor range are evaluated on the model and flagged.
+    #[arg(
+        long,
+        value_name = "TENORS",
+        value_delimiter = ',',
+        num_args = 0..=1,
+        default_missing_value = "1,2,3,5,7,10,20,30",
+        conflicts_with_all = ["compact", "all_ratings", "seed_sweep"]
+    )]
+    pub pillars: Option<Vec<f64>>,
+
+    /// Also write the `--pillars` table as JSON to PATH.
+    #[arg(long, value_name = "PATH", requires = "pillars")]
+    pub pillars_json: Option<PathBuf>,
+
+    /// Write the selected curve as a ready-to-paste formula (fitted betas/taus
+    /// substituted, `f1`/`f2` included), labelled with model and as-of date.
+    #[arg(long, value_enum, value_name = "LANG", conflicts_with_all = ["all_ratings", "seed_sweep"])]
+    pub export_formula: Option<FormulaLang>,
+
+    /// Output file for `--export-formula` (default: `rv-formula-<RATING>-<DATE>.py`
+    /// or `.txt` in the current directory).
+    #[arg(long, value_name = "PATH", requires = "export_formula")]
+    pub formula_out: Option<PathBuf>,
+
+    /// Previous curve JSON to compare against: reports each bond's residual shift
+    /// (today's residual minus its residual against the previous curve).
+    #[arg(long, value_name = "JSON")]
+    pub baseline_curve: Option<PathBuf>,
+
+    /// Order the residual-shift report by shift (largest cheapening first)
+    /// instead of by today's residual.
+    #[arg(long, requires = "baseline_curve")]
+    pub rank_by_shift: bool,
+
+    /// Exit with code 3 if any warning fired (warnings are printed first).
+    ///
+    /// Useful for CI gating: catches fallbacks such as default volatility or a
+    /// distant as-of resolution.
+    #[arg(long)]
+    pub fail_on_warnings: bool,
+
+    /// Compute leave-one-out influence per bond (one refit per bond; slow).
+    #[arg(long)]
+    pub influence: bool,
+
+    /// Fit every rating band in parallel against one shared FRED snapshot and print
+    /// a batch summary table (`--rating` is ignored).
+    ///
+    /// A failure in one rating is reported at the end without aborting the others.
+    #[arg(
+        long,
+        conflicts_with_all = ["export_curve", "export_timeseries", "export_landscape", "baseline_curve", "influence"]
+    )]
+    pub all_ratings: bool,
+
+    /// Refit over N deterministically derived noise seeds (one FRED fetch, bond
+    /// tenors held fixed) and report, per bond ID, how often it lands in the
+    /// cheap/rich top-N, ranked by stability-adjusted signal. Replaces the
+    /// normal output.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(2..),
+        conflicts_with_all = ["all_ratings", "compact", "random_seed", "export", "export_curve", "export_landscape", "export_timeseries", "baseline_curve", "influence"]
+    )]
+    pub seed_sweep: Option<usize>,
+
+    /// Maximum worker threads for batch runs and seed sweeps (covers both the
+    /// outer loop and the inner tau searches). Defaults to one per core.
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub threads: Option<usize>,
+
+    /// Print residual diagnostics: lag-1 autocorrelation and a runs test on
+    /// residuals in tenor order, flagging strong serial correlation.
+    #[arg(long)]
+    pub diagnostics: bool,
+
+    /// Slope ("box") metrics to report, as comma-separated tenor pairs, e.g.
+    /// `2-10,5-30` prints `y(10) - y(2)` and `y(30) - y(5)` under those labels.
+    /// Tenors outside the fitted range are flagged as extrapolated.
+    #[arg(long, value_name = "PAIRS", value_delimiter = ',')]
+    pub slopes: Vec<TenorPair>,
+
+    /// Tenor bin edges (years) for the worst-fit region line in the summary;
+    /// each bin's weighted RMSE uses the fit's weights, and empty bins are skipped.
+    #[arg(long, value_name = "EDGES", default_value = "0,2,5,10,20,30")]
+    pub resid_bins: BinEdges,
+
+    /// Print step by step why the selected model was chosen (BIC values, minimum,
+    /// simplicity threshold, and which rule fired).
+    #[arg(long)]
+    pub explain_selection: bool,
+
     /// Probability of generating a wide (cheap) outlier.
     #[arg(long, default_value_t = 0.05)]
     pub jump_prob_wide: f64,
@@ -125,6 +622,15 @@ pub struct FitArgs {
     /// Jump magnitude multiplier for tight outliers.
     #[arg(long, default_value_t = 2.5)]
     pub jump_k_tight: f64,
+
+    /// Lower bound on each bond's injected log-volatility (`sigma_ln`).
+    #[arg(long, value_name = "SIGMA")]
+    pub vol_floor: Option<f64>,
+
+    /// Upper bound on each bond's injected log-volatility (`sigma_ln`); how
+    /// many bonds it capped is reported in warnings.
+    #[arg(long, value_name = "SIGMA")]
+    pub vol_cap: Option<f64>,
 }
 
 /// Options for plotting a saved curve.
@@ -141,4 +647,157 @@ pub struct PlotArgs {
     /// Plot height (rows).
     #[arg(long, default_value_t = 25)]
     pub height: usize,
+
+    /// Append the curve's fingerprint (`hash=xxxxxxxx`) to the plot header.
+    #[arg(long)]
+    pub plot_hash: bool,
+
+    /// Warn (on stderr) when the curve's as-of date is more than this many
+    /// days before today.
+    #[arg(long, value_name = "DAYS", default_value_t = crate::io::curve::DEFAULT_MAX_CURVE_AGE_DAYS)]
+    pub max_curve_age_days: i64,
+
+    /// Skip the curve age warning (for intentional historical analysis).
+    #[arg(long)]
+    pub no_age_check: bool,
+}
+
+/// Options for evaluating a saved curve at specific tenors.
+#[derive(Debug, Parser)]
+pub struct EvalArgs {
+    /// Curve JSON file produced by `rv fit --export-curve`.
+    #[arg(long, value_name = "JSON")]
+    pub curve: PathBuf,
+
+    /// Comma-separated tenors in years (e.g. "0.5,2,5,10").
+    ///
+    /// Tenors outside the curve's fitted grid range are flagged as extrapolated.
+    #[arg(long, value_delimiter = ',', required = true, num_args = 1..)]
+    pub tenors: Vec<f64>,
+
+    /// Interpolate the stored grid instead of evaluating the model; outside the
+    /// grid, `clamp` holds the end values and `linear` extends the end segments.
+    #[arg(long, value_enum, value_name = "POLICY")]
+    pub from_grid: Option<GridExtrapolation>,
+
+    /// Print results as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Warn (on stderr) when the curve's as-of date is more than this many
+    /// days before today.
+    #[arg(long, value_name = "DAYS", default_value_t = crate::io::curve::DEFAULT_MAX_CURVE_AGE_DAYS)]
+    pub max_curve_age_days: i64,
+
+    /// Skip the curve age warning (for intentional historical analysis).
+    #[arg(long)]
+    pub no_age_check: bool,
+}
+
+/// Options for checking a saved curve against live data.
+#[derive(Debug, Parser)]
+pub struct CheckArgs {
+    /// Curve JSON file produced by `rv fit --export-curve`.
+    #[arg(long, value_name = "JSON")]
+    pub curve: PathBuf,
+
+    /// Largest allowed |curve - baseline| in bp (converted to the curve's
+    /// unit); beyond it `rv check` exits 5.
+    #[arg(long, value_name = "BP", default_value_t = 25.0)]
+    pub max_deviation: f64,
+
+    /// JSON file overriding FRED series IDs (as in `rv fit --series-map`).
+    #[arg(long, value_name = "JSON")]
+    pub series_map: Option<PathBuf>,
+
+    /// Read the FRED API key from this file (as in `rv fit --api-key-file`).
+    #[arg(long, value_name = "PATH")]
+    pub api_key_file: Option<PathBuf>,
+
+    /// Print the comparison as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Options for the `list-*` introspection commands.
+#[derive(Debug, Parser)]
+pub struct ListArgs {
+    /// Series-map JSON to apply, so the listing shows the IDs actually in effect.
+    #[arg(long, value_name = "JSON")]
+    pub series_map: Option<PathBuf>,
+}
+
+/// File formats `rv schema` can describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaKind {
+    /// Curve JSON written by `--export-curve`.
+    Curve,
+}
+
+/// Options for printing a JSON Schema.
+#[derive(Debug, Parser)]
+pub struct SchemaArgs {
+    /// Which file format to describe.
+    #[arg(value_enum)]
+    pub kind: SchemaKind,
+}
+
+/// Options for diffing two FRED snapshots.
+#[derive(Debug, Parser)]
+pub struct CompareSnapshotArgs {
+    /// Earlier as-of date (YYYY-MM-DD); resolved like `rv fit --asof`.
+    #[arg(long, value_name = "DATE")]
+    pub from: NaiveDate,
+
+    /// Later as-of date (YYYY-MM-DD); defaults to the most recent observation.
+    #[arg(long, value_name = "DATE")]
+    pub to: Option<NaiveDate>,
+
+    /// JSON file overriding FRED series IDs (as in `rv fit --series-map`).
+    #[arg(long, value_name = "JSON")]
+    pub series_map: Option<PathBuf>,
+
+    /// Read the FRED API key from this file (as in `rv fit --api-key-file`).
+    #[arg(long, value_name = "PATH")]
+    pub api_key_file: Option<PathBuf>,
+
+    /// Compare trailing N-observation averages instead of single-day levels.
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub smooth_days: usize,
+
+    /// Print the diff as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Options for comparing fits on two dates.
+#[derive(Debug, Parser)]
+pub struct CompareDatesArgs {
+    /// Earlier as-of date (YYYY-MM-DD); resolved like `rv fit --asof`.
+    #[arg(long, value_name = "DATE")]
+    pub from: NaiveDate,
+
+    /// Later as-of date (YYYY-MM-DD); defaults to the most recent observation.
+    #[arg(long, value_name = "DATE")]
+    pub to: Option<NaiveDate>,
+
+    /// Tenors (years) to compare the curves at.
+    #[arg(long, value_name = "TENORS", value_delimiter = ',', default_value = "1,2,3,5,7,10,20,30")]
+    pub tenors: Vec<f64>,
+
+    /// Print the comparison as JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Fit settings shared by both dates (`--asof` is replaced by --from/--to).
+    #[command(flatten)]
+    pub fit: FitArgs,
+}
+
+/// Options for validating a saved curve.
+#[derive(Debug, Parser)]
+pub struct ValidateArgs {
+    /// Curve JSON file produced by `rv fit --export-curve`.
+    #[arg(long, value_name = "JSON")]
+    pub curve: PathBuf,
 }
@@ -1,22 +1,210 @@
 //! FRED API integration for ICE BofA OAS series.
+//!
+//! The default series are the ICE BofA US corporate indices; `SeriesMap` lets a
+//! JSON file (`--series-map`) point any of the inputs at other FRED series.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 
 use chrono::NaiveDate;
 use reqwest::blocking::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::domain::RatingBand;
+use crate::domain::{DatePolicy, FitConfig, MissingRating, RatingBand, ShortVolHistory};
 use crate::error::AppError;
 
 const BASE_URL: &str = "https://api.stlouisfed.org/fred/series/observations";
 const OBS_LIMIT: usize = 10000;
 
-const SERIES_OVERALL: &str = "BAMLC0A0CM";
-const SERIES_13Y: &str = "BAMLC1A0C13Y";
-const SERIES_35Y: &str = "BAMLC2A0C35Y";
-const SERIES_57Y: &str = "BAMLC3A0C57Y";
-const SERIES_710Y: &str = "BAMLC4A0C710Y";
+pub const SERIES_OVERALL: &str = "BAMLC0A0CM";
+pub const SERIES_13Y: &str = "BAMLC1A0C13Y";
+pub const SERIES_35Y: &str = "BAMLC2A0C35Y";
+pub const SERIES_57Y: &str = "BAMLC3A0C57Y";
+pub const SERIES_710Y: &str = "BAMLC4A0C710Y";
+
+/// FRED series IDs feeding a snapshot: the overall index, the four maturity
+/// buckets, and one series per rating band.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeriesMap {
+    pub overall: String,
+    pub bucket_13y: String,
+    pub bucket_35y: String,
+    pub bucket_57y: String,
+    pub bucket_710y: String,
+    pub ratings: HashMap<RatingBand, String>,
+}
+
+impl Default for SeriesMap {
+    fn default() -> Self {
+        Self {
+            overall: SERIES_OVERALL.to_string(),
+            bucket_13y: SERIES_13Y.to_string(),
+            bucket_35y: SERIES_35Y.to_string(),
+            bucket_57y: SERIES_57Y.to_string(),
+            bucket_710y: SERIES_710Y.to_string(),
+            ratings: RatingBand::ALL.iter().map(|&b| (b, b.series_id().to_string())).collect(),
+        }
+    }
+}
+
+/// On-disk `--series-map` layout; every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SeriesMapFile {
+    overall: Option<String>,
+    bucket_13y: Option<String>,
+    bucket_35y: Option<String>,
+    bucket_57y: Option<String>,
+    bucket_710y: Option<String>,
+    #[serde(default)]
+    ratings: HashMap<RatingBand, String>,
+}
+
+impl SeriesMap {
+    /// Series ID for a rating band (the override, else `RatingBand::series_id`).
+    pub fn rating(&self, band: RatingBand) -> &str {
+        self.ratings.get(&band).map(String::as_str).unwrap_or(band.series_id())
+    }
+
+    /// Bucket series IDs, shortest maturity first.
+    pub fn buckets(&self) -> [&str; 4] {
+        [&self.bucket_13y, &self.bucket_35y, &self.bucket_57y, &self.bucket_710y]
+    }
+
+    /// Load a JSON series map. Keys left out keep their defaults; unknown keys
+    /// and malformed IDs are rejected.
+    pub fn read(path: &Path) -> Result<Self, AppError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AppError::new(2, format!("Failed to read series map '{}': {e}", path.display())))?;
+        Self::from_json(&text)
+            .map_err(|e| AppError::new(2, format!("Invalid series map '{}': {e}", path.display())))
+    }
+
+    fn from_json(text: &str) -> Result<Self, String> {
+        let file: SeriesMapFile = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let mut map = Self::default();
+        for (slot, value) in [
+            (&mut map.overall, file.overall),
+            (&mut map.bucket_13y, file.bucket_13y),
+            (&mut map.bucket_35y, file.bucket_35y),
+            (&mut map.bucket_57y, file.bucket_57y),
+            (&mut map.bucket_710y, file.bucket_710y),
+        ] {
+            if let Some(id) = value {
+                *slot = id;
+            }
+        }
+        map.ratings.extend(file.ratings);
+
+        let all = [&map.overall, &map.bucket_13y, &map.bucket_35y, &map.bucket_57y, &map.bucket_710y]
+            .into_iter()
+            .chain(map.ratings.values());
+        for id in all {
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("'{id}' is not a valid FRED series ID"));
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// One input's level and volatility on two snapshot dates (`rv compare-snapshot`).
+///
+/// A side is `None` when that snapshot has no value for the input (a rating
+/// band left out, or a volatility that fell back to `FALLBACK_VOL`); the delta
+/// is then `None` too.
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDelta {
+    /// Input label: `overall`, `bucket 1-3y`, ..., or the rating band.
+    pub input: String,
+    pub series: String,
+    pub level_from: Option<f64>,
+    pub level_to: Option<f64>,
+    /// `level_to - level_from` (bp).
+    pub level_delta: Option<f64>,
+    pub vol_from: Option<f64>,
+    pub vol_to: Option<f64>,
+    pub vol_delta: Option<f64>,
+}
+
+/// Level/volatility moves between two snapshots, overall first, then the
+/// maturity buckets, then the rating bands in rating order.
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub rows: Vec<SnapshotDelta>,
+}
+
+/// One FRED series as it entered the fit.
+#[derive(Debug, Clone, Serialize)]
+pub struct InputSeries {
+    pub series: String,
+    /// Level (bp), after `--smooth-days` averaging.
+    pub level_bp: f64,
+    /// Daily log-return std dev.
+    pub vol: f64,
+    /// `vol` is `FALLBACK_VOL` because the history was unusable.
+    pub vol_fallback: bool,
+}
+
+/// The snapshot values that drove a fit (`--embed-inputs`).
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotInputs {
+    pub date: NaiveDate,
+    /// Trailing observations averaged into each level (`--smooth-days`).
+    pub smooth_days: usize,
+    pub overall: InputSeries,
+    /// Maturity buckets keyed `1-3y`, `3-5y`, `5-7y`, `7-10y`.
+    pub buckets: BTreeMap<&'static str, InputSeries>,
+    /// Rating bands present in the snapshot, in `RatingBand::ALL` order.
+    #[serde(serialize_with = "crate::domain::serialize_rating_map")]
+    pub ratings: HashMap<RatingBand, InputSeries>,
+    /// Bands whose level was synthesized by `--missing-rating interp`.
+    pub interpolated_ratings: Vec<RatingBand>,
+    /// Series forward-filled under `--date-policy latest-overall`, with the
+    /// date of the observation carried forward.
+    pub forward_filled: Vec<(String, NaiveDate)>,
+}
+
+impl FredSnapshot {
+    /// Levels and vols of every input series, as used by the fit.
+    pub fn inputs(&self, smooth_days: usize) -> SnapshotInputs {
+        let input = |id: &str, level_bp: f64, vol: f64| InputSeries {
+            series: id.to_string(),
+            level_bp,
+            vol,
+            vol_fallback: self.volatility.fallback_series.iter().any(|f| f == id),
+        };
+        let b = &self.buckets;
+        let bv = &self.volatility.buckets_vol;
+        let labels = ["1-3y", "3-5y", "5-7y", "7-10y"];
+        let levels = [(b.y_13y, bv.y_13y), (b.y_35y, bv.y_35y), (b.y_57y, bv.y_57y), (b.y_710y, bv.y_710y)];
+        let buckets = labels
+            .into_iter()
+            .zip(self.series.buckets())
+            .zip(levels)
+            .map(|((label, id), (level, vol))| (label, input(id, level, vol)))
+            .collect();
+        let ratings = self
+            .ratings_bp
+            .iter()
+            .map(|(&band, &level)| {
+                let vol = self.volatility.ratings_vol.get(&band).copied().unwrap_or(FALLBACK_VOL);
+                (band, input(self.series.rating(band), level, vol))
+            })
+            .collect();
+        SnapshotInputs {
+            date: self.date,
+            smooth_days: smooth_days.max(1),
+            overall: input(&self.series.overall, self.overall_bp, self.volatility.overall_vol),
+            buckets,
+            ratings,
+            interpolated_ratings: self.interpolated_ratings.clone(),
+            forward_filled: self.forward_filled.clone(),
+        }
+    }
+}
 
 /// Bucket-level OAS values (point-in-time).
 #[derive(Debug, Clone)]
@@ -47,8 +235,14 @@ pub struct FredVolatility {
     pub overall_vol: f64,
     /// Number of observations used for volatility calculation.
     pub n_obs: usize,
+    /// Series whose volatility could not be computed and fell back to
+    /// `FALLBACK_VOL` (surfaced as run warnings).
+    pub fallback_series: Vec<String>,
 }
 
+/// Daily log-return volatility assumed when a series has too little history.
+pub const FALLBACK_VOL: f64 = 0.01;
+
 #[derive(Debug, Clone)]
 pub struct FredSnapshot {
     pub date: NaiveDate,
@@ -57,6 +251,72 @@ pub struct FredSnapshot {
     pub ratings_bp: HashMap<RatingBand, f64>,
     /// Realized volatility from full historical series.
     pub volatility: FredVolatility,
+    /// Bands whose level was synthesized by `--missing-rating interp`.
+    pub interpolated_ratings: Vec<RatingBand>,
+    /// Series forward-filled under `--date-policy latest-overall`, with the
+    /// date of the observation carried forward.
+    pub forward_filled: Vec<(String, NaiveDate)>,
+    /// Series IDs the snapshot was built from.
+    pub series: SeriesMap,
+}
+
+/// How `fetch_snapshot` resolves the as-of date and missing rating bands.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotOptions {
+    /// Latest observation date to use (`None` = most recent).
+    pub target_date: Option<NaiveDate>,
+    /// Policy for rating bands with no value on the common date.
+    pub missing_rating: MissingRating,
+    /// How the snapshot date is resolved across series.
+    pub date_policy: DatePolicy,
+    /// Reject a `target_date` that is not a business day or that FRED would
+    /// resolve to an earlier observation date.
+    pub strict_dates: bool,
+    /// Series IDs to fetch.
+    pub series: SeriesMap,
+    /// Average each level over this many trailing observations (0 or 1 = the
+    /// common-date value itself).
+    pub smooth_days: usize,
+    /// Usable daily returns a series needs for its volatility estimate (0 or
+    /// 1 = any); shorter series fall back to `FALLBACK_VOL`.
+    pub min_vol_obs: usize,
+    /// Whether a fetched series below `min_vol_obs` falls back or fails.
+    pub short_vol_history: ShortVolHistory,
+}
+
+impl SnapshotOptions {
+    /// Build options from the run config, loading `--series-map` if given.
+    pub fn from_config(config: &FitConfig) -> Result<Self, AppError> {
+        let series = match &config.series_map {
+            Some(path) => SeriesMap::read(path)?,
+            None => SeriesMap::default(),
+        };
+        Ok(Self {
+            target_date: config.asof,
+            missing_rating: config.missing_rating,
+            date_policy: config.date_policy,
+            strict_dates: config.strict_dates,
+            series,
+            smooth_days: config.smooth_days,
+            min_vol_obs: config.min_vol_obs.unwrap_or(0),
+            short_vol_history: config.short_vol_history,
+        })
+    }
+}
+
+/// Read an API key file, trimming surrounding whitespace (e.g. a trailing newline).
+fn read_api_key_file(path: &Path) -> Result<String, AppError> {
+    let raw = std::fs::read_to_string(path).map_err(|e| {
+        AppError::new(2, format!("Failed to read FRED API key file '{}': {e}", path.display()))
+    })?;
+    let key = raw.trim();
+    if key.is_empty() {
+        return Err(AppError::new(
+            2,
+            format!("FRED API key file '{}' is empty.", path.display()),
+        ));
+    }
+    Ok(key.to_string())
 }
 
 pub struct FredClient {
@@ -65,20 +325,40 @@ pub struct FredClient {
 }
 
 impl FredClient {
-    pub fn from_env() -> Result<Self, AppError> {
+    /// Build a client, taking the API key from (in order) `api_key_file`
+    /// (`--api-key-file`), the file named by `FRED_API_KEY_FILE`, or
+    /// `FRED_API_KEY`. Environment variables may come from `.env`.
+    ///
+    /// The key itself never appears in errors or output.
+    pub fn new(api_key_file: Option<&Path>) -> Result<Self, AppError> {
         dotenvy::dotenv().ok();
-        let api_key = std::env::var("FRED_API_KEY")
-            .map_err(|_| AppError::new(2, "Missing FRED_API_KEY in environment (.env)."))?;
+        let api_key = match api_key_file {
+            Some(path) => read_api_key_file(path)?,
+            None => match std::env::var_os("FRED_API_KEY_FILE") {
+                Some(path) => read_api_key_file(Path::new(&path))?,
+                None => std::env::var("FRED_API_KEY").map_err(|_| {
+                    AppError::new(
+                        2,
+                        "Missing FRED_API_KEY in environment (.env); set it, FRED_API_KEY_FILE, or --api-key-file.",
+                    )
+                })?,
+            },
+        };
         Ok(Self {
             client: Client::new(),
             api_key,
         })
     }
 
-    pub fn fetch_snapshot(&self, target_date: Option<NaiveDate>) -> Result<FredSnapshot, AppError> {
-        let mut series_ids: Vec<&str> = vec![SERIES_OVERALL, SERIES_13Y, SERIES_35Y, SERIES_57Y, SERIES_710Y];
+    pub fn fetch_snapshot(&self, options: &SnapshotOptions) -> Result<FredSnapshot, AppError> {
+        let target_date = options.target_date;
+        let tolerate_missing = options.missing_rating != MissingRating::Error;
+
+        let ids = &options.series;
+        let mut series_ids: Vec<&str> = vec![ids.overall.as_str()];
+        series_ids.extend(ids.buckets());
         for band in RatingBand::ALL {
-            series_ids.push(band.series_id());
+            series_ids.push(ids.rating(band));
         }
 
         // Fetch full historical series for each, storing as Vec for volatility calc.
@@ -86,23 +366,50 @@ impl FredClient {
         let mut maps: HashMap<&str, HashMap<NaiveDate, f64>> = HashMap::new();
 
         for &series_id in &series_ids {
-            let obs = self.fetch_series(series_id, target_date)?;
-            if obs.is_empty() {
-                return Err(AppError::new(
-                    4,
-                    format!("No observations returned for series {series_id}."),
-                ));
-            }
+            let is_rating = RatingBand::ALL.iter().any(|&b| ids.rating(b) == series_id);
+            let fetched = self.fetch_series(series_id, target_date);
+            let Some(obs) = usable_observations(series_id, fetched, is_rating && tolerate_missing)? else {
+                continue;
+            };
             series_data.insert(series_id, obs.clone());
             maps.insert(series_id, obs.into_iter().collect());
         }
 
-        let common_date = latest_common_date(&maps)
-            .ok_or_else(|| AppError::new(4, "No common observation date across series."))?;
+        let (common_date, forward_filled) = match options.date_policy {
+            DatePolicy::Common => (
+                latest_common_date(&maps)
+                    .ok_or_else(|| AppError::new(4, "No common observation date across series."))?,
+                Vec::new(),
+            ),
+            DatePolicy::LatestOverall => {
+                let date = maps
+                    .get(ids.overall.as_str())
+                    .and_then(|m| m.keys().max().copied())
+                    .ok_or_else(|| AppError::new(4, "No observations for the overall series."))?;
+                (date, forward_fill(&mut maps, date))
+            }
+        };
+        if let (true, Some(requested)) = (options.strict_dates, target_date) {
+            check_strict_asof(requested, common_date)?;
+        }
+
+        // Level of a series on the common date, or its trailing mean under
+        // `--smooth-days` (volatility below still uses the raw history). A
+        // value missing on the date is `None`; a history too short to smooth
+        // is an error of its own, never left to the missing-rating policy.
+        let level = |series_id: &str| -> Result<Option<f64>, AppError> {
+            let Some(&on_date) = maps.get(series_id).and_then(|m| m.get(&common_date)) else {
+                return Ok(None);
+            };
+            match series_data.get(series_id) {
+                Some(series) if options.smooth_days > 1 => {
+                    trailing_mean(series_id, series, common_date, options.smooth_days).map(Some)
+                }
+                _ => Ok(Some(on_date)),
+            }
+        };
 
-        let overall_bp = *maps
-            .get(SERIES_OVERALL)
-            .and_then(|m| m.get(&common_date))
+        let overall_bp = level(&ids.overall)?
             .ok_or_else(|| AppError::new(4, "Missing overall OAS value for common date."))?;
 
         if !(overall_bp.is_finite() && overall_bp > 0.0) {
@@ -110,44 +417,45 @@ impl FredClient {
         }
 
         let buckets = BucketSeries {
-            y_13y: *maps
-                .get(SERIES_13Y)
-                .and_then(|m| m.get(&common_date))
+            y_13y: level(&ids.bucket_13y)?
                 .ok_or_else(|| AppError::new(4, "Missing 1-3y OAS value."))?,
-            y_35y: *maps
-                .get(SERIES_35Y)
-                .and_then(|m| m.get(&common_date))
+            y_35y: level(&ids.bucket_35y)?
                 .ok_or_else(|| AppError::new(4, "Missing 3-5y OAS value."))?,
-            y_57y: *maps
-                .get(SERIES_57Y)
-                .and_then(|m| m.get(&common_date))
+            y_57y: level(&ids.bucket_57y)?
                 .ok_or_else(|| AppError::new(4, "Missing 5-7y OAS value."))?,
-            y_710y: *maps
-                .get(SERIES_710Y)
-                .and_then(|m| m.get(&common_date))
+            y_710y: level(&ids.bucket_710y)?
                 .ok_or_else(|| AppError::new(4, "Missing 7-10y OAS value."))?,
         };
 
         let mut ratings_bp = HashMap::new();
         for band in RatingBand::ALL {
-            let series_id = band.series_id();
-            let value = *maps
-                .get(series_id)
-                .and_then(|m| m.get(&common_date))
-                .ok_or_else(|| AppError::new(4, format!("Missing rating series {series_id} value.")))?;
-            ratings_bp.insert(band, value);
+            let series_id = ids.rating(band);
+            match level(series_id)? {
+                Some(value) => {
+                    ratings_bp.insert(band, value);
+                }
+                None if tolerate_missing => {}
+                None => {
+                    return Err(AppError::new(4, format!("Missing rating series {series_id} value.")));
+                }
+            }
         }
 
         // Compute realized volatility from full historical series.
-        let volatility = compute_volatility(&series_data)?;
+        let volatility = compute_volatility(&series_data, ids, options.min_vol_obs, options.short_vol_history)?;
 
-        Ok(FredSnapshot {
+        let mut snapshot = FredSnapshot {
             date: common_date,
             overall_bp,
             buckets,
             ratings_bp,
             volatility,
-        })
+            interpolated_ratings: Vec::new(),
+            forward_filled,
+            series: ids.clone(),
+        };
+        apply_missing_rating_policy(&mut snapshot, options.missing_rating)?;
+        Ok(snapshot)
     }
 
     fn fetch_series(
@@ -175,9 +483,15 @@ impl FredClient {
             .map_err(|e| AppError::new(4, format!("FRED request failed: {e}")))?;
 
         if !resp.status().is_success() {
+            // FRED answers 400 for a series ID it does not know.
+            let hint = if resp.status() == reqwest::StatusCode::BAD_REQUEST {
+                " (unknown series ID? check --series-map)"
+            } else {
+                ""
+            };
             return Err(AppError::new(
                 4,
-                format!("FRED request failed with status {}.", resp.status()),
+                format!("FRED request for series {series_id} failed with status {}{hint}.", resp.status()),
             ));
         }
 
@@ -201,6 +515,28 @@ impl FredClient {
     }
 }
 
+/// Observations of a fetched series, or `None` for a series that may be
+/// missing (`tolerate_empty`: a rating band under `--missing-rating
+/// interp|skip`) and has no values up to the as-of date.
+///
+/// Request failures always propagate, tolerated or not: a network or auth
+/// error, or the HTTP 400 FRED sends for a mistyped `--series-map` ID, is not
+/// a band that happens to have no data.
+fn usable_observations(
+    series_id: &str,
+    fetched: Result<Vec<(NaiveDate, f64)>, AppError>,
+    tolerate_empty: bool,
+) -> Result<Option<Vec<(NaiveDate, f64)>>, AppError> {
+    let obs = fetched?;
+    if !obs.is_empty() {
+        return Ok(Some(obs));
+    }
+    if tolerate_empty {
+        return Ok(None);
+    }
+    Err(AppError::new(4, format!("No observations returned for series {series_id}.")))
+}
+
 #[derive(Debug, Deserialize)]
 struct ObservationsResponse {
     observations: Vec<Observation>,
@@ -225,6 +561,91 @@ fn parse_value(raw: &str) -> Option<f64> {
     }
 }
 
+/// Compare two snapshots input by input (see `SnapshotDiff`).
+pub fn diff_snapshots(from: &FredSnapshot, to: &FredSnapshot) -> SnapshotDiff {
+    // (input label, series id, level, vol) for every input of a snapshot.
+    type Inputs = Vec<(String, String, Option<f64>, Option<f64>)>;
+    fn inputs(s: &FredSnapshot) -> Inputs {
+        let vol = |id: &str, v: f64| (!s.volatility.fallback_series.iter().any(|f| f == id)).then_some(v);
+        let b = &s.buckets;
+        let bv = &s.volatility.buckets_vol;
+        let mut out = vec![(
+            "overall".to_string(),
+            s.series.overall.clone(),
+            Some(s.overall_bp),
+            vol(&s.series.overall, s.volatility.overall_vol),
+        )];
+        let labels = ["bucket 1-3y", "bucket 3-5y", "bucket 5-7y", "bucket 7-10y"];
+        let levels = [(b.y_13y, bv.y_13y), (b.y_35y, bv.y_35y), (b.y_57y, bv.y_57y), (b.y_710y, bv.y_710y)];
+        for ((label, id), (level, v)) in labels.iter().zip(s.series.buckets()).zip(levels) {
+            out.push((label.to_string(), id.to_string(), Some(level), vol(id, v)));
+        }
+        for band in RatingBand::ALL {
+            let id = s.series.rating(band);
+            let level = s.ratings_bp.get(&band).copied();
+            let v = level.and(s.volatility.ratings_vol.get(&band)).and_then(|&v| vol(id, v));
+            out.push((band.display_name().to_string(), id.to_string(), level, v));
+        }
+        out
+    }
+
+    let delta = |a: Option<f64>, b: Option<f64>| Some(b? - a?);
+    let rows = inputs(from)
+        .into_iter()
+        .zip(inputs(to))
+        .map(|((input, from_id, level_from, vol_from), (_, to_id, level_to, vol_to))| SnapshotDelta {
+            input,
+            // Both sides normally share a series map; show both IDs if not.
+            series: if from_id == to_id { from_id } else { format!("{from_id} -> {to_id}") },
+            level_from,
+            level_to,
+            level_delta: delta(level_from, level_to),
+            vol_from,
+            vol_to,
+            vol_delta: delta(vol_from, vol_to),
+        })
+        .collect();
+    SnapshotDiff { from_date: from.date, to_date: to.date, rows }
+}
+
+/// Mean of the last `n` observations dated on or before `end`.
+///
+/// Fails (exit 4, naming `series_id`) when the series has fewer than `n` such
+/// observations, so a short history is reported rather than silently averaged
+/// over fewer days or mistaken for a missing value.
+fn trailing_mean(series_id: &str, series: &[(NaiveDate, f64)], end: NaiveDate, n: usize) -> Result<f64, AppError> {
+    let mut window: Vec<(NaiveDate, f64)> = series.iter().copied().filter(|(d, _)| *d <= end).collect();
+    if window.len() < n {
+        return Err(AppError::new(
+            4,
+            format!(
+                "--smooth-days {n}: series {series_id} has only {} observation(s) on or before {end}.",
+                window.len()
+            ),
+        ));
+    }
+    window.sort_by_key(|(d, _)| std::cmp::Reverse(*d));
+    Ok(window[..n].iter().map(|(_, v)| v).sum::<f64>() / n as f64)
+}
+
+/// `--strict-dates` check: the requested as-of must be a business day and must
+/// be the observation date FRED actually resolved to.
+fn check_strict_asof(requested: NaiveDate, resolved: NaiveDate) -> Result<(), AppError> {
+    use chrono::{Datelike, Weekday};
+
+    let reason = if matches!(requested.weekday(), Weekday::Sat | Weekday::Sun) {
+        format!("{requested} is a {} (not a business day)", requested.weekday())
+    } else if resolved != requested {
+        format!("FRED has no common observation on {requested}")
+    } else {
+        return Ok(());
+    };
+    Err(AppError::new(
+        2,
+        format!("--strict-dates: {reason}; it would resolve to {resolved}. Pass --asof {resolved} or drop --strict-dates."),
+    ))
+}
+
 fn latest_common_date(maps: &HashMap<&str, HashMap<NaiveDate, f64>>) -> Option<NaiveDate> {
     let mut common: Option<HashSet<NaiveDate>> = None;
     for map in maps.values() {
@@ -240,18 +661,131 @@ fn latest_common_date(maps: &HashMap<&str, HashMap<NaiveDate, f64>>) -> Option<N
     common.and_then(|set| set.into_iter().max())
 }
 
+/// Carry each series' most recent observation before `date` forward to `date`
+/// where it has none, returning `(series_id, source_date)` for every fill,
+/// sorted by series ID. Series with no observation on or before `date` are
+/// left as they are (and surface as missing downstream).
+fn forward_fill(maps: &mut HashMap<&str, HashMap<NaiveDate, f64>>, date: NaiveDate) -> Vec<(String, NaiveDate)> {
+    let mut filled = Vec::new();
+    for (series_id, map) in maps.iter_mut() {
+        if map.contains_key(&date) {
+            continue;
+        }
+        let prior = map.iter().filter(|(d, _)| **d < date).max_by_key(|(d, _)| **d).map(|(d, v)| (*d, *v));
+        if let Some((source, value)) = prior {
+            map.insert(date, value);
+            filled.push((series_id.to_string(), source));
+        }
+    }
+    filled.sort();
+    filled
+}
+
+/// Resolve rating bands absent from `snapshot.ratings_bp` per `policy`.
+///
+/// - `Error`: any missing band is an error.
+/// - `Skip`: missing bands stay absent (batch runs leave them out).
+/// - `Interp`: each missing band's level (and volatility) is interpolated
+///   log-linearly by position in `RatingBand::ALL` from the nearest available
+///   bands on either side, or extrapolated from the two nearest on one side at the
+///   ends of the scale. Synthesized bands are listed in `interpolated_ratings`.
+///
+/// Interpolation only uses observed bands, so the result does not depend on the
+/// order in which missing bands are filled.
+pub fn apply_missing_rating_policy(snapshot: &mut FredSnapshot, policy: MissingRating) -> Result<(), AppError> {
+    let missing: Vec<RatingBand> = RatingBand::ALL
+        .into_iter()
+        .filter(|b| !snapshot.ratings_bp.contains_key(b))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    match policy {
+        MissingRating::Error => {
+            let names: Vec<&str> = missing.iter().map(|b| b.display_name()).collect();
+            Err(AppError::new(
+                4,
+                format!(
+                    "Rating band(s) {} missing from FRED snapshot (use --missing-rating interp|skip).",
+                    names.join(", ")
+                ),
+            ))
+        }
+        MissingRating::Skip => Ok(()),
+        MissingRating::Interp => {
+            let observed = snapshot.ratings_bp.clone();
+            let observed_vol = snapshot.volatility.ratings_vol.clone();
+            for band in missing {
+                let level = interp_band(&observed, band).ok_or_else(|| {
+                    AppError::new(
+                        4,
+                        format!(
+                            "Cannot interpolate missing rating {}: need at least two observed bands.",
+                            band.display_name()
+                        ),
+                    )
+                })?;
+                snapshot.ratings_bp.insert(band, level);
+                if let Some(vol) = interp_band(&observed_vol, band) {
+                    snapshot.volatility.ratings_vol.insert(band, vol);
+                    let series_id = snapshot.series.rating(band).to_string();
+                    snapshot.volatility.fallback_series.retain(|s| *s != series_id);
+                }
+                snapshot.interpolated_ratings.push(band);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Log-linear interpolation of a band's value from observed neighbours.
+fn interp_band(values: &HashMap<RatingBand, f64>, band: RatingBand) -> Option<f64> {
+    let idx = RatingBand::ALL.iter().position(|&b| b == band)?;
+    let known: Vec<(f64, f64)> = RatingBand::ALL
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| values.get(b).filter(|v| v.is_finite() && **v > 0.0).map(|v| (i as f64, v.ln())))
+        .collect();
+
+    let x = idx as f64;
+    let below = known.iter().rev().find(|(i, _)| *i < x);
+    let above = known.iter().find(|(i, _)| *i > x);
+    let (a, b) = match (below, above) {
+        (Some(a), Some(b)) => (*a, *b),
+        // At an end of the scale: extrapolate from the two nearest on one side.
+        (Some(_), None) => {
+            let n = known.len();
+            (*known.get(n.checked_sub(2)?)?, known[n - 1])
+        }
+        (None, Some(_)) => (*known.first()?, *known.get(1)?),
+        (None, None) => return None,
+    };
+    let frac = (x - a.0) / (b.0 - a.0);
+    Some((a.1 + frac * (b.1 - a.1)).exp())
+}
+
 /// Compute realized volatility from full historical series using log-returns.
+///
+/// A fetched series with fewer than `min_returns` usable log-returns (at least
+/// one is always needed) falls back to `FALLBACK_VOL`, or fails the fetch under
+/// `ShortVolHistory::Error`. Series that were not fetched at all (a rating band
+/// left out by `--missing-rating`) always fall back.
 fn compute_volatility(
     series_data: &HashMap<&str, Vec<(NaiveDate, f64)>>,
+    ids: &SeriesMap,
+    min_returns: usize,
+    short_history: ShortVolHistory,
 ) -> Result<FredVolatility, AppError> {
-    // Helper: compute std dev of log-returns from a time series.
-    fn log_return_std(series: &[(NaiveDate, f64)]) -> Option<f64> {
+    // Helper: compute std dev of log-returns from a time series, or the number
+    // of usable returns when there are fewer than `min_returns`.
+    fn log_return_std(series: &[(NaiveDate, f64)], min_returns: usize) -> Result<f64, usize> {
         if series.len() < 2 {
-            return None;
+            return Err(0);
         }
 
         // Sort by date ascending for proper return calculation.
-        let mut sorted: Vec<_> = series.iter().cloned().collect();
+        let mut sorted: Vec<_> = series.to_vec();
         sorted.sort_by_key(|(d, _)| *d);
 
         // Compute log-returns.
@@ -264,8 +798,8 @@ fn compute_volatility(
             }
         }
 
-        if log_returns.is_empty() {
-            return None;
+        if log_returns.is_empty() || log_returns.len() < min_returns {
+            return Err(log_returns.len());
         }
 
         // Compute mean.
@@ -275,35 +809,57 @@ fn compute_volatility(
         // Compute variance (sample variance with n-1 denominator).
         let variance = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
 
-        Some(variance.sqrt())
+        Ok(variance.sqrt())
     }
 
-    // Extract volatility for a series, defaulting to a small value if missing.
-    let get_vol = |series_id: &str| -> f64 {
-        series_data
-            .get(series_id)
-            .and_then(|s| log_return_std(s))
-            .unwrap_or(0.01) // 1% daily vol as fallback
+    // Extract volatility for a series, defaulting to a small value if missing
+    // (and recording the fallback so the run can warn about it).
+    let mut fallback_series = Vec::new();
+    let mut short = Vec::new();
+    let mut get_vol = |series_id: &str| -> f64 {
+        let Some(series) = series_data.get(series_id) else {
+            fallback_series.push(series_id.to_string());
+            return FALLBACK_VOL;
+        };
+        match log_return_std(series, min_returns) {
+            Ok(vol) => vol,
+            Err(usable) => {
+                short.push(format!("{series_id} ({usable})"));
+                fallback_series.push(series_id.to_string());
+                FALLBACK_VOL
+            }
+        }
     };
 
-    let overall_vol = get_vol(SERIES_OVERALL);
+    let overall_vol = get_vol(&ids.overall);
 
     let buckets_vol = BucketVolatility {
-        y_13y: get_vol(SERIES_13Y),
-        y_35y: get_vol(SERIES_35Y),
-        y_57y: get_vol(SERIES_57Y),
-        y_710y: get_vol(SERIES_710Y),
+        y_13y: get_vol(&ids.bucket_13y),
+        y_35y: get_vol(&ids.bucket_35y),
+        y_57y: get_vol(&ids.bucket_57y),
+        y_710y: get_vol(&ids.bucket_710y),
     };
 
     let mut ratings_vol = HashMap::new();
     for band in RatingBand::ALL {
-        let vol = get_vol(band.series_id());
+        let vol = get_vol(ids.rating(band));
         ratings_vol.insert(band, vol);
     }
 
+    if short_history == ShortVolHistory::Error && !short.is_empty() {
+        return Err(AppError::new(
+            4,
+            format!(
+                "Too little history to estimate volatility (usable daily returns < --min-vol-obs {}): {}.",
+                min_returns.max(1),
+                short.join(", ")
+            ),
+        ));
+    }
+
     // Get observation count from overall series.
     let n_obs = series_data
-        .get(SERIES_OVERALL)
+        .get(ids.overall.as_str())
         .map(|s| s.len())
         .unwrap_or(0);
 
@@ -312,6 +868,7 @@ fn compute_volatility(
         buckets_vol,
         overall_vol,
         n_obs,
+        fallback_series,
     })
 }
 
@@ -319,6 +876,22 @@ fn compute_volatility(
 mod tests {
     use super::*;
 
+    #[test]
+    fn api_key_file_is_trimmed_and_empty_file_rejected() {
+        let path = std::env::temp_dir().join(format!("rv-api-key-{}", std::process::id()));
+        std::fs::write(&path, "  abc123\n").unwrap();
+        assert_eq!(read_api_key_file(&path).unwrap(), "abc123");
+
+        std::fs::write(&path, "\n").unwrap();
+        let err = read_api_key_file(&path).unwrap_err();
+        assert!(err.to_string().contains("is empty"), "{err}");
+        std::fs::remove_file(&path).unwrap();
+
+        let err = read_api_key_file(&path).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().starts_with("Failed to read FRED API key file"), "{err}");
+    }
+
     #[test]
     fn log_return_std_computes_correctly() {
         // Helper function exposed for testing.
@@ -326,7 +899,7 @@ mod tests {
             if series.len() < 2 {
                 return None;
             }
-            let mut sorted: Vec<_> = series.iter().cloned().collect();
+            let mut sorted: Vec<_> = series.to_vec();
             sorted.sort_by_key(|(d, _)| *d);
             let mut log_returns = Vec::with_capacity(sorted.len() - 1);
             for i in 1..sorted.len() {
@@ -362,4 +935,188 @@ mod tests {
         // Std = sqrt(0.01816) ≈ 0.1348
         assert!(vol > 0.13 && vol < 0.14, "Expected vol around 0.135, got {vol}");
     }
+
+    #[test]
+    fn missing_rating_policy_interpolates_and_flags() {
+        let mut snapshot = crate::data::sample::tests::test_snapshot();
+        snapshot.ratings_bp.insert(RatingBand::BB, 200.0);
+        snapshot.ratings_bp.insert(RatingBand::CCC, 800.0);
+        snapshot.ratings_bp.remove(&RatingBand::B);
+
+        let err = apply_missing_rating_policy(&mut snapshot.clone(), MissingRating::Error).unwrap_err();
+        assert!(err.to_string().contains("B missing"), "{err}");
+
+        let mut skipped = snapshot.clone();
+        apply_missing_rating_policy(&mut skipped, MissingRating::Skip).unwrap();
+        assert!(!skipped.ratings_bp.contains_key(&RatingBand::B));
+
+        // Log-linear midpoint between BB (200) and CCC (800) is 400.
+        apply_missing_rating_policy(&mut snapshot, MissingRating::Interp).unwrap();
+        assert!((snapshot.ratings_bp[&RatingBand::B] - 400.0).abs() < 1e-9);
+        assert_eq!(snapshot.interpolated_ratings, vec![RatingBand::B]);
+
+        // End of the scale: extrapolate from the two nearest bands.
+        let mut end = crate::data::sample::tests::test_snapshot();
+        end.ratings_bp.insert(RatingBand::B, 400.0);
+        end.ratings_bp.insert(RatingBand::BB, 200.0);
+        end.ratings_bp.remove(&RatingBand::CCC);
+        apply_missing_rating_policy(&mut end, MissingRating::Interp).unwrap();
+        assert!((end.ratings_bp[&RatingBand::CCC] - 800.0).abs() < 1e-9);
+
+        // Only an empty series counts as missing; a failed request still fails.
+        assert!(usable_observations("X", Ok(Vec::new()), true).unwrap().is_none());
+        assert_eq!(usable_observations("X", Ok(Vec::new()), false).unwrap_err().exit_code(), 4);
+        let unknown = AppError::new(4, "FRED request for series TYPO failed with status 400 Bad Request.");
+        let err = usable_observations("TYPO", Err(unknown), true).unwrap_err();
+        assert!(err.to_string().contains("TYPO"), "{err}");
+    }
+
+    #[test]
+    fn strict_asof_rejects_weekends_and_resolution_gaps() {
+        let fri = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        let sat = NaiveDate::from_ymd_opt(2025, 1, 4).unwrap();
+        let thu = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+
+        assert!(check_strict_asof(fri, fri).is_ok());
+        let err = check_strict_asof(sat, fri).unwrap_err().to_string();
+        assert!(err.contains("not a business day") && err.contains("resolve to 2025-01-03"), "{err}");
+        let err = check_strict_asof(fri, thu).unwrap_err().to_string();
+        assert!(err.contains("no common observation") && err.contains("resolve to 2025-01-02"), "{err}");
+    }
+
+    #[test]
+    fn series_map_overrides_partially_and_rejects_unknown_keys() {
+        let map = SeriesMap::from_json(r#"{"overall": "EUIG0", "ratings": {"BBB": "EUIGBBB"}}"#).unwrap();
+        assert_eq!(map.overall, "EUIG0");
+        assert_eq!(map.rating(RatingBand::BBB), "EUIGBBB");
+        assert_eq!(map.rating(RatingBand::AA), RatingBand::AA.series_id());
+        assert_eq!(map.bucket_35y, SERIES_35Y);
+
+        assert!(SeriesMap::from_json(r#"{"overal": "X"}"#).is_err());
+        assert!(SeriesMap::from_json(r#"{"ratings": {"AAAA": "X"}}"#).is_err());
+        assert!(SeriesMap::from_json(r#"{"bucket_13y": "bad id"}"#).is_err());
+    }
+
+    #[test]
+    fn trailing_mean_averages_the_last_n_observations() {
+        let d = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        // Unsorted, as FRED returns newest first; the 10th is after `end`.
+        let series = vec![(d(10), 999.0), (d(8), 130.0), (d(6), 110.0), (d(7), 120.0), (d(3), 100.0)];
+
+        assert_eq!(trailing_mean("S", &series, d(8), 1).unwrap(), 130.0);
+        assert_eq!(trailing_mean("S", &series, d(8), 3).unwrap(), 120.0);
+        let err = trailing_mean("BAMLC0A4CBBB", &series, d(8), 5).unwrap_err();
+        assert_eq!(err.exit_code(), 4);
+        assert_eq!(err.to_string(), "--smooth-days 5: series BAMLC0A4CBBB has only 4 observation(s) on or before 2025-01-08.");
+    }
+
+    #[test]
+    fn forward_fill_carries_lagging_series_to_the_overall_date() {
+        let d = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        let mut maps: HashMap<&str, HashMap<NaiveDate, f64>> = HashMap::new();
+        maps.insert("OVERALL", [(d(8), 100.0), (d(10), 101.0)].into_iter().collect());
+        maps.insert("BBB", [(d(7), 150.0), (d(8), 152.0)].into_iter().collect());
+        maps.insert("AA", [(d(6), 60.0), (d(10), 61.0)].into_iter().collect());
+        maps.insert("NEW", [(d(11), 5.0)].into_iter().collect());
+
+        let overall_latest = *maps["OVERALL"].keys().max().unwrap();
+        let filled = forward_fill(&mut maps, overall_latest);
+
+        assert_eq!(filled, vec![("BBB".to_string(), d(8))]);
+        assert_eq!(maps["BBB"][&d(10)], 152.0);
+        assert_eq!(maps["AA"][&d(10)], 61.0);
+        // Nothing on or before the date: left missing.
+        assert!(!maps["NEW"].contains_key(&d(10)));
+    }
+
+    #[test]
+    fn rating_maps_serialize_in_rating_order() {
+        #[derive(serde::Serialize)]
+        struct Table {
+            #[serde(serialize_with = "crate::domain::serialize_rating_map")]
+            levels: HashMap<RatingBand, f64>,
+        }
+
+        let snapshot = crate::data::sample::canned_snapshot();
+        let mut levels = snapshot.ratings_bp.clone();
+        levels.remove(&RatingBand::BB);
+        let order: Vec<RatingBand> = RatingBand::ordered(&levels).map(|(b, _)| b).collect();
+        assert_eq!(order, [RatingBand::AAA, RatingBand::AA, RatingBand::A, RatingBand::BBB, RatingBand::B, RatingBand::CCC]);
+
+        // Rebuilt maps get fresh hash seeds; the JSON must not change.
+        let json = |levels: HashMap<RatingBand, f64>| serde_json::to_string(&Table { levels }).unwrap();
+        let first = json(levels.clone());
+        assert!(first.starts_with(r#"{"levels":{"AAA":100.0,"AA":100.0,"A":100.0,"BBB""#), "{first}");
+        for _ in 0..5 {
+            assert_eq!(json(levels.iter().map(|(k, v)| (*k, *v)).collect()), first);
+        }
+    }
+
+    #[test]
+    fn snapshot_diff_reports_moves_and_na_for_missing_inputs() {
+        let from = crate::data::sample::canned_snapshot();
+        let mut to = from.clone();
+        to.date = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        to.overall_bp = 93.5;
+        to.buckets.y_710y = 89.0;
+        to.ratings_bp.insert(RatingBand::BBB, 108.0);
+        to.volatility.ratings_vol.insert(RatingBand::BBB, 0.015);
+        to.ratings_bp.remove(&RatingBand::CCC);
+        to.volatility.fallback_series.push(SERIES_13Y.to_string());
+
+        let diff = diff_snapshots(&from, &to);
+        assert_eq!(diff.rows.len(), 5 + RatingBand::ALL.len());
+        let row = |input: &str| diff.rows.iter().find(|r| r.input == input).unwrap();
+        assert_eq!(diff.rows[0].input, "overall");
+        assert_eq!(row("overall").level_delta, Some(3.5));
+        assert_eq!(row("bucket 7-10y").level_delta, Some(-2.0));
+        assert_eq!(row("BBB").level_delta, Some(8.0));
+        assert!((row("BBB").vol_delta.unwrap() - 0.005).abs() < 1e-12);
+        // Missing on one side: the value and the delta are n/a, not an error.
+        let ccc = row("CCC");
+        assert_eq!((ccc.level_from, ccc.level_to, ccc.level_delta), (Some(100.0), None, None));
+        assert_eq!((ccc.vol_to, ccc.vol_delta), (None, None));
+        assert_eq!((row("bucket 1-3y").vol_to, row("bucket 1-3y").level_delta), (None, Some(0.0)));
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["to_date"], "2025-01-03");
+        assert!(json["rows"][11]["level_delta"].is_null());
+        let text = crate::report::format_snapshot_diff(&diff);
+        assert!(text.lines().any(|l| l.starts_with("CCC") && l.contains("n/a")), "{text}");
+    }
+
+    #[test]
+    fn min_vol_obs_falls_back_or_fails_on_short_histories() {
+        let ids = SeriesMap::default();
+        let d = |day: u32| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        let history = |n: u32| -> Vec<(NaiveDate, f64)> { (1..=n).map(|i| (d(i), 100.0 + (i % 3) as f64)).collect() };
+        let mut data: HashMap<&str, Vec<(NaiveDate, f64)>> = HashMap::new();
+        data.insert(ids.overall.as_str(), history(20));
+        for id in ids.buckets() {
+            data.insert(id, history(20));
+        }
+        for band in RatingBand::ALL {
+            data.insert(ids.rating(band), history(if band == RatingBand::CCC { 5 } else { 20 }));
+        }
+
+        // Default: any history with a return or two is used as-is.
+        let vol = compute_volatility(&data, &ids, 0, ShortVolHistory::Warn).unwrap();
+        assert!(vol.fallback_series.is_empty());
+        assert_ne!(vol.ratings_vol[&RatingBand::CCC], FALLBACK_VOL);
+
+        // CCC has 4 usable returns: below 10 it falls back (and is flagged) ...
+        let vol = compute_volatility(&data, &ids, 10, ShortVolHistory::Warn).unwrap();
+        assert_eq!(vol.fallback_series, vec![ids.rating(RatingBand::CCC).to_string()]);
+        assert_eq!(vol.ratings_vol[&RatingBand::CCC], FALLBACK_VOL);
+
+        // ... or fails the fetch when asked to be strict.
+        let err = compute_volatility(&data, &ids, 10, ShortVolHistory::Error).unwrap_err();
+        assert_eq!(err.exit_code(), 4);
+        assert!(err.to_string().contains("--min-vol-obs 10): BAMLH0A3HYC (4)"), "{err}");
+
+        // A band that was never fetched (skipped) is not a strictness failure.
+        data.remove(ids.rating(RatingBand::CCC));
+        let vol = compute_volatility(&data, &ids, 10, ShortVolHistory::Error).unwrap();
+        assert_eq!(vol.fallback_series.len(), 1);
+    }
 }
@@ -1,18 +1,18 @@
 //! Synthetic bond sample generation from FRED OAS baselines.
 
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
 
-use chrono::Duration;
+use chrono::{Duration, NaiveDate};
 use rand::prelude::*;
 use rand::rngs::StdRng;
 use rand_distr::Normal;
 
-use crate::data::fred::{BucketSeries, BucketVolatility, FredSnapshot};
+use crate::data::fred::{BucketSeries, BucketVolatility, FredSnapshot, FredVolatility, SeriesMap};
 use crate::domain::{
-    BondExtras, BondMeta, BondPoint, DatasetStats, FitConfig, RatingBand, RunSpec, YKind,
+    BondExtras, BondMeta, BondPoint, DatasetStats, FitConfig, RatingBand, RatingBlend, RunSpec, WeightMode, YKind,
 };
 use crate::error::AppError;
+use crate::math::Fnv1a;
 
 /// Power-law exponent for short-end extrapolation.
 /// spread(t) = spread(2y) * (t / 2)^alpha for t < 2y.
@@ -27,6 +27,12 @@ pub struct SampleData {
     pub baseline: Vec<f64>,
     pub spec: RunSpec,
     pub stats: DatasetStats,
+    /// RNG seed the sample was drawn with (derived from snapshot + config).
+    pub seed: u64,
+    /// Bonds whose `sigma_ln` was raised to `--vol-floor`.
+    pub vol_floored: usize,
+    /// Bonds whose `sigma_ln` was lowered to `--vol-cap`.
+    pub vol_capped: usize,
 }
 
 pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<SampleData, AppError> {
@@ -49,25 +55,51 @@ pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<Sa
     {
         return Err(AppError::new(2, "Invalid jump magnitude settings."));
     }
+    let vol_floor = config.vol_floor.unwrap_or(0.0);
+    let vol_cap = config.vol_cap.unwrap_or(f64::INFINITY);
+    if !(vol_floor.is_finite() && vol_floor >= 0.0 && vol_cap > 0.0 && vol_floor <= vol_cap) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid --vol-floor/--vol-cap ({vol_floor}, {vol_cap}): need 0 <= floor <= cap and cap > 0."),
+        ));
+    }
+
+    let tenor_grid = match config.tenor_grid_step {
+        Some(step) => Some(tenor_grid(config.tenor_min, config.tenor_max, step)?),
+        None => None,
+    };
 
-    let mut rng = StdRng::seed_from_u64(sample_seed(snapshot, config));
+    let seed = config.rng_seed.unwrap_or_else(|| sample_seed(snapshot, config));
+    let mut rng = StdRng::seed_from_u64(seed);
+    // With a noise seed, noise and jumps come from their own stream so the
+    // tenor draws (and grid placement) don't depend on it.
+    let mut noise_rng = config.noise_seed.map(StdRng::seed_from_u64);
     let normal = Normal::new(0.0, 1.0)
         .map_err(|e| AppError::new(4, format!("Noise distribution error: {e}")))?;
 
-    // Get the rating-specific historical volatility (log-return std dev).
-    let rating_vol = snapshot
-        .volatility
-        .ratings_vol
-        .get(&config.rating)
-        .copied()
-        .unwrap_or(0.01);
+    // Rating-specific historical volatility (log-return std dev), weighted
+    // across the blend's bands.
+    let blend = config.sample_blend();
+    let label = blend.label();
+    let rating_vol: f64 = blend
+        .parts
+        .iter()
+        .map(|&(band, w)| w * snapshot.volatility.ratings_vol.get(&band).copied().unwrap_or(0.01))
+        .sum();
 
     let mut points = Vec::with_capacity(config.sample_count);
     let mut baseline = Vec::with_capacity(config.sample_count);
+    let (mut vol_floored, mut vol_capped) = (0, 0);
 
     for i in 0..config.sample_count {
-        let tenor = rng.gen_range(config.tenor_min..=config.tenor_max);
-        let curve_level = baseline_curve(snapshot, config.rating, tenor)?;
+        // The uniform draw is made even on a fixed grid so the noise stream (and
+        // therefore every level shock) matches the uniform-tenor sample.
+        let drawn = rng.gen_range(config.tenor_min..=config.tenor_max);
+        let tenor = match &tenor_grid {
+            Some(grid) => grid[i % grid.len()],
+            None => drawn,
+        };
+        let curve_level = blended_baseline_curve(snapshot, &blend, tenor)?;
         baseline.push(curve_level);
 
         // Get tenor-specific bucket volatility (interpolated).
@@ -83,13 +115,25 @@ pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<Sa
         // Floor at 0.25 to avoid near-zero vol for very short tenors.
         let tenor_scale = tenor.sqrt().max(0.25);
 
-        // Effective daily log-volatility for this bond.
-        let sigma_ln = combined_vol * tenor_scale;
+        // Effective daily log-volatility for this bond, clamped to
+        // [--vol-floor, --vol-cap]. Everything below (noise, jump, and mean
+        // correction) uses the clamped value, so the baseline stays unbiased.
+        let raw_sigma = combined_vol * tenor_scale;
+        let sigma_ln = raw_sigma.clamp(vol_floor, vol_cap);
+        if raw_sigma < vol_floor {
+            vol_floored += 1;
+        } else if raw_sigma > vol_cap {
+            vol_capped += 1;
+        }
 
         // Apply jump-diffusion model.
-        let z = normal.sample(&mut rng);
+        let noise = match noise_rng.as_mut() {
+            Some(noise) => noise,
+            None => &mut rng,
+        };
+        let z = normal.sample(noise);
         let jump = sample_jump(
-            &mut rng,
+            noise,
             config.jump_prob_wide,
             config.jump_prob_tight,
             config.jump_k_wide,
@@ -112,12 +156,16 @@ pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<Sa
             .checked_add_signed(Duration::days((tenor * 365.25).round() as i64))
             .unwrap_or(snapshot.date);
 
-        let id = format!("{}-{:03}", config.rating.display_name(), i + 1);
+        let id = format!("{label}-{:03}", i + 1);
         let meta = BondMeta {
             issuer: None,
-            rating: Some(config.rating.display_name().to_string()),
+            rating: Some(label.clone()),
+        };
+        let extras = BondExtras {
+            oas: Some(y_obs),
+            sigma_ln: Some(sigma_ln),
+            dv01: None,
         };
-        let extras = BondExtras { oas: Some(y_obs) };
 
         points.push(BondPoint {
             id,
@@ -131,6 +179,10 @@ pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<Sa
         });
     }
 
+    if config.weight_mode == WeightMode::InverseVar {
+        apply_inverse_var_weights(&mut points)?;
+    }
+
     let stats = compute_stats(&points).ok_or_else(|| AppError::new(4, "Failed to compute sample stats."))?;
     let spec = RunSpec {
         asof_date: snapshot.date,
@@ -142,9 +194,90 @@ pub fn generate_sample(snapshot: &FredSnapshot, config: &FitConfig) -> Result<Sa
         baseline,
         spec,
         stats,
+        seed,
+        vol_floored,
+        vol_capped,
+    })
+}
+
+/// Wrap real bonds (`--sample-from-points`) as the run's sample instead of
+/// drawing synthetic ones.
+///
+/// The snapshot only supplies the as-of date and, unless `--no-prior`, the
+/// FRED baseline at each bond's tenor. The seed is a stable (FNV-1a) hash of
+/// the bonds, so the run hash still identifies the input.
+pub fn sample_from_points(snapshot: &FredSnapshot, config: &FitConfig, points: Vec<BondPoint>) -> Result<SampleData, AppError> {
+    let baseline = if config.no_prior {
+        Vec::new()
+    } else {
+        let blend = config.sample_blend();
+        points
+            .iter()
+            .map(|p| blended_baseline_curve(snapshot, &blend, p.tenor))
+            .collect::<Result<Vec<f64>, AppError>>()?
+    };
+
+    let mut hasher = Fnv1a::new();
+    for p in &points {
+        hasher.write_str(&p.id);
+        hasher.write_f64(p.tenor);
+        hasher.write_f64(p.y_obs);
+        hasher.write_f64(p.weight);
+    }
+
+    let stats = compute_stats(&points).ok_or_else(|| AppError::new(2, "Bond CSV has no points."))?;
+    Ok(SampleData {
+        points,
+        baseline,
+        spec: RunSpec {
+            asof_date: snapshot.date,
+            y_kind: YKind::Oas,
+        },
+        stats,
+        seed: hasher.finish(),
+        vol_floored: 0,
+        vol_capped: 0,
     })
 }
 
+/// Fixed sample tenors `tenor_min, tenor_min + step, ...` up to `tenor_max`.
+fn tenor_grid(tenor_min: f64, tenor_max: f64, step: f64) -> Result<Vec<f64>, AppError> {
+    if !(step.is_finite() && step > 0.0 && step <= tenor_max - tenor_min) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid --tenor-grid-sample step {step}: must be > 0 and at most the tenor range."),
+        ));
+    }
+    // Small slack so a step that divides the range exactly still reaches tenor_max.
+    let count = ((tenor_max - tenor_min) / step + 1e-9).floor() as usize + 1;
+    Ok((0..count).map(|k| tenor_min + step * k as f64).collect())
+}
+
+/// Set each point's weight to `1/sigma_ln^2`, normalized so the mean weight is 1.
+///
+/// Normalizing keeps weighted SSE/RMSE on the same scale as uniform weighting;
+/// only the relative trust between points changes.
+fn apply_inverse_var_weights(points: &mut [BondPoint]) -> Result<(), AppError> {
+    let mut raw = Vec::with_capacity(points.len());
+    for p in points.iter() {
+        let sigma = p.extras.sigma_ln.unwrap_or(f64::NAN);
+        let w = 1.0 / (sigma * sigma);
+        if !(w.is_finite() && w > 0.0) {
+            return Err(AppError::new(
+                4,
+                format!("Invalid noise level {sigma} for inverse-variance weight of bond {}.", p.id),
+            ));
+        }
+        raw.push(w);
+    }
+
+    let mean = raw.iter().sum::<f64>() / raw.len().max(1) as f64;
+    for (p, w) in points.iter_mut().zip(raw) {
+        p.weight = w / mean;
+    }
+    Ok(())
+}
+
 /// Interpolate bucket volatility at a given tenor using the FRED bucket knots.
 fn interpolate_bucket_vol(tenor: f64, buckets: &BucketVolatility) -> f64 {
     // Bucket midpoints: 1-3y -> 2y, 3-5y -> 4y, 5-7y -> 6y, 7-10y -> 8.5y
@@ -194,32 +327,36 @@ fn linear_interp(a: (f64, f64), b: (f64, f64), x: f64) -> f64 {
     y0 + u * (y1 - y0)
 }
 
+/// Deterministic RNG seed from the snapshot and sampling config (used unless
+/// `config.rng_seed` pins the seed explicitly).
 fn sample_seed(snapshot: &FredSnapshot, config: &FitConfig) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    snapshot.date.hash(&mut hasher);
-    snapshot.overall_bp.to_bits().hash(&mut hasher);
-    for band in RatingBand::ALL {
-        if let Some(v) = snapshot.ratings_bp.get(&band) {
-            v.to_bits().hash(&mut hasher);
-        }
+    let mut hasher = Fnv1a::new();
+    hasher.write_str(&snapshot.date.to_string());
+    hasher.write_f64(snapshot.overall_bp);
+    for (_, v) in RatingBand::ordered(&snapshot.ratings_bp) {
+        hasher.write_f64(*v);
     }
-    snapshot.buckets.y_13y.to_bits().hash(&mut hasher);
-    snapshot.buckets.y_35y.to_bits().hash(&mut hasher);
-    snapshot.buckets.y_57y.to_bits().hash(&mut hasher);
-    snapshot.buckets.y_710y.to_bits().hash(&mut hasher);
+    hasher.write_f64(snapshot.buckets.y_13y);
+    hasher.write_f64(snapshot.buckets.y_35y);
+    hasher.write_f64(snapshot.buckets.y_57y);
+    hasher.write_f64(snapshot.buckets.y_710y);
 
     // Include volatility in seed for reproducibility.
-    snapshot.volatility.overall_vol.to_bits().hash(&mut hasher);
-
-    config.rating.hash(&mut hasher);
-    config.sample_count.hash(&mut hasher);
-    config.sample_seed.hash(&mut hasher);
-    (config.tenor_min.to_bits()).hash(&mut hasher);
-    (config.tenor_max.to_bits()).hash(&mut hasher);
-    config.jump_prob_wide.to_bits().hash(&mut hasher);
-    config.jump_prob_tight.to_bits().hash(&mut hasher);
-    config.jump_k_wide.to_bits().hash(&mut hasher);
-    config.jump_k_tight.to_bits().hash(&mut hasher);
+    hasher.write_f64(snapshot.volatility.overall_vol);
+
+    hasher.write_str(config.rating.display_name());
+    // Only a genuine mix changes the seed, so `BBB:1` draws the plain BBB sample.
+    if let Some(blend) = config.rating_blend.as_ref().filter(|b| b.parts.len() > 1) {
+        hasher.write_str(&blend.to_string());
+    }
+    hasher.write(&(config.sample_count as u64).to_le_bytes());
+    hasher.write(&config.sample_seed.to_le_bytes());
+    hasher.write_f64(config.tenor_min);
+    hasher.write_f64(config.tenor_max);
+    hasher.write_f64(config.jump_prob_wide);
+    hasher.write_f64(config.jump_prob_tight);
+    hasher.write_f64(config.jump_k_wide);
+    hasher.write_f64(config.jump_k_tight);
     hasher.finish()
 }
 
@@ -287,39 +424,163 @@ fn sample_jump(
     }
 }
 
+/// Fixed synthetic FRED snapshot (2025-01-02, every rating at 100bp, 1% vols):
+/// used by `rv selftest` and the sample/pipeline tests, so it needs no network.
+pub fn canned_snapshot() -> FredSnapshot {
+    let mut ratings_bp = HashMap::new();
+    let mut ratings_vol = HashMap::new();
+    for band in RatingBand::ALL {
+        ratings_bp.insert(band, 100.0);
+        ratings_vol.insert(band, 0.01);
+    }
+    FredSnapshot {
+        date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+        overall_bp: 90.0,
+        buckets: BucketSeries {
+            y_13y: 52.0,
+            y_35y: 71.0,
+            y_57y: 82.0,
+            y_710y: 91.0,
+        },
+        ratings_bp,
+        volatility: FredVolatility {
+            ratings_vol,
+            buckets_vol: BucketVolatility {
+                y_13y: 0.01,
+                y_35y: 0.01,
+                y_57y: 0.01,
+                y_710y: 0.01,
+            },
+            overall_vol: 0.01,
+            n_obs: 100,
+            fallback_series: Vec::new(),
+        },
+        interpolated_ratings: Vec::new(),
+        forward_filled: Vec::new(),
+        series: SeriesMap::default(),
+    }
+}
+
+/// The generating baseline (`blended_baseline_curve`) sampled at `n` evenly
+/// spaced tenors over `[t_min, t_max]` and scaled from bp by `scale`, for plot
+/// overlays. Tenors where the baseline is undefined are left out.
+pub fn baseline_grid(
+    snapshot: &FredSnapshot,
+    blend: &RatingBlend,
+    t_min: f64,
+    t_max: f64,
+    n: usize,
+    scale: f64,
+) -> Vec<(f64, f64)> {
+    let n = n.max(2);
+    (0..n)
+        .filter_map(|i| {
+            let t = t_min + (t_max - t_min) * i as f64 / (n - 1) as f64;
+            blended_baseline_curve(snapshot, blend, t).ok().map(|y| (t, y * scale))
+        })
+        .collect()
+}
+
+/// Bucket volatility term structure (`interpolate_bucket_vol`, daily log-vol
+/// in percent) sampled at `n` evenly spaced tenors over `[t_min, t_max]`, for
+/// the `--plot-vol` overlay.
+pub fn vol_grid(snapshot: &FredSnapshot, t_min: f64, t_max: f64, n: usize) -> Vec<(f64, f64)> {
+    let n = n.max(2);
+    (0..n)
+        .map(|i| {
+            let t = t_min + (t_max - t_min) * i as f64 / (n - 1) as f64;
+            (t, 100.0 * interpolate_bucket_vol(t, &snapshot.volatility.buckets_vol))
+        })
+        .collect()
+}
+
 pub fn baseline_curve(
     snapshot: &FredSnapshot,
     rating: RatingBand,
     tenor: f64,
 ) -> Result<f64, AppError> {
-    let rating_level = snapshot
-        .ratings_bp
-        .get(&rating)
-        .copied()
-        .ok_or_else(|| AppError::new(4, "Missing rating baseline in snapshot."))?;
+    let name = rating.display_name();
+    if !tenor.is_finite() {
+        return Err(AppError::new(4, format!("Invalid tenor {tenor} for rating {name} baseline.")));
+    }
+    let rating_level = rating_level(snapshot, rating)?;
+    scale_to_tenor(snapshot, rating_level, name, tenor)
+}
+
+/// Baseline for a rating blend: the weighted mix of the bands' `ratings_bp`
+/// levels, shaped by the bucket curve exactly as in `baseline_curve` (which a
+/// single-band blend reproduces bit for bit).
+pub fn blended_baseline_curve(snapshot: &FredSnapshot, blend: &RatingBlend, tenor: f64) -> Result<f64, AppError> {
+    let name = blend.label();
+    if !tenor.is_finite() {
+        return Err(AppError::new(4, format!("Invalid tenor {tenor} for rating {name} baseline.")));
+    }
+    let mut level = 0.0;
+    for &(band, w) in &blend.parts {
+        level += w * rating_level(snapshot, band)?;
+    }
+    scale_to_tenor(snapshot, level, &name, tenor)
+}
+
+/// Validated `ratings_bp` level of `rating`.
+fn rating_level(snapshot: &FredSnapshot, rating: RatingBand) -> Result<f64, AppError> {
+    let name = rating.display_name();
+    let rating_level = snapshot.ratings_bp.get(&rating).copied().ok_or_else(|| {
+        AppError::new(
+            4,
+            format!("Missing rating baseline for {name} (series {}) in snapshot.", snapshot.series.rating(rating)),
+        )
+    })?;
 
     if !(rating_level.is_finite() && rating_level > 0.0) {
-        return Err(AppError::new(4, "Invalid rating baseline from snapshot."));
+        return Err(AppError::new(
+            4,
+            format!(
+                "Invalid rating baseline {rating_level} for rating {name} (series {}).",
+                snapshot.series.rating(rating)
+            ),
+        ));
     }
+    Ok(rating_level)
+}
 
+/// Shape a rating level by the bucket curve: `level * bucket(t) / overall`.
+fn scale_to_tenor(snapshot: &FredSnapshot, rating_level: f64, name: &str, tenor: f64) -> Result<f64, AppError> {
     let bucket_level = bucket_curve(tenor, &snapshot.buckets);
     if !(bucket_level.is_finite() && bucket_level > 0.0) {
-        return Err(AppError::new(4, "Invalid bucket baseline from snapshot."));
+        return Err(AppError::new(
+            4,
+            format!("Invalid bucket baseline {bucket_level} at tenor {tenor:.2} for rating {name}."),
+        ));
     }
 
     if !(snapshot.overall_bp.is_finite() && snapshot.overall_bp > 0.0) {
-        return Err(AppError::new(4, "Invalid overall baseline from snapshot."));
+        return Err(AppError::new(
+            4,
+            format!(
+                "Invalid overall baseline {} (series {}) at tenor {tenor:.2} for rating {name}.",
+                snapshot.overall_bp,
+                snapshot.series.overall
+            ),
+        ));
     }
 
     let curve_level = rating_level * (bucket_level / snapshot.overall_bp);
     if !(curve_level.is_finite() && curve_level > 0.0) {
-        return Err(AppError::new(4, "Invalid computed baseline curve."));
+        return Err(AppError::new(
+            4,
+            format!(
+                "Invalid computed baseline {curve_level} at tenor {tenor:.2} for rating {name} \
+                 (rating={rating_level}, bucket={bucket_level}, overall={}).",
+                snapshot.overall_bp
+            ),
+        ));
     }
 
     Ok(curve_level)
 }
 
-fn compute_stats(points: &[BondPoint]) -> Option<DatasetStats> {
+pub(crate) fn compute_stats(points: &[BondPoint]) -> Option<DatasetStats> {
     let mut tenor_min = f64::INFINITY;
     let mut tenor_max = f64::NEG_INFINITY;
     let mut y_min = f64::INFINITY;
@@ -346,9 +607,60 @@ fn compute_stats(points: &[BondPoint]) -> Option<DatasetStats> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
+    /// Flat synthetic snapshot shared by sample/pipeline tests.
+    pub(crate) fn test_snapshot() -> FredSnapshot {
+        canned_snapshot()
+    }
+
+    #[test]
+    fn baseline_curve_errors_name_rating_tenor_and_value() {
+        let mut snapshot = test_snapshot();
+        snapshot.buckets.y_35y = f64::NAN;
+        let err = baseline_curve(&snapshot, RatingBand::BBB, 3.2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid bucket baseline NaN at tenor 3.20 for rating BBB."
+        );
+
+        let mut snapshot = test_snapshot();
+        snapshot.ratings_bp.insert(RatingBand::CCC, 0.0);
+        let err = baseline_curve(&snapshot, RatingBand::CCC, 5.0).unwrap_err();
+        assert!(err.to_string().contains("0 for rating CCC (series BAMLH0A3HYC)"), "{err}");
+    }
+
+    #[test]
+    fn inverse_var_weights_favor_low_noise_and_average_one() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut points: Vec<BondPoint> = [0.01, 0.02, 0.04]
+            .iter()
+            .enumerate()
+            .map(|(i, &sigma)| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 1.0 + i as f64,
+                y_obs: 100.0,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras {
+                    oas: Some(100.0),
+                    sigma_ln: Some(sigma),
+                    dv01: None,
+                },
+            })
+            .collect();
+
+        apply_inverse_var_weights(&mut points).unwrap();
+        let w: Vec<f64> = points.iter().map(|p| p.weight).collect();
+        assert!((w.iter().sum::<f64>() / 3.0 - 1.0).abs() < 1e-12);
+        // Halving sigma quadruples the weight.
+        assert!((w[0] / w[1] - 4.0).abs() < 1e-9);
+        assert!((w[1] / w[2] - 4.0).abs() < 1e-9);
+    }
+
     #[test]
     fn bucket_curve_power_law_short_end() {
         // Test that short-end extrapolation uses power-law (sqrt) scaling.
@@ -420,4 +732,142 @@ mod tests {
             "At 5y: expected 76.5, got {at_5y:.2}"
         );
     }
+
+    #[test]
+    fn explicit_rng_seed_reproduces_the_sample() {
+        let snapshot = test_snapshot();
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.sample_count = 30;
+        config.tenor_min = 0.5;
+        config.tenor_max = 20.0;
+
+        let hashed = generate_sample(&snapshot, &config).unwrap();
+        assert_eq!(hashed.seed, sample_seed(&snapshot, &config));
+
+        // Feeding a seed back (as printed by --random-seed) reproduces the sample,
+        // regardless of --seed.
+        config.rng_seed = Some(0xDEAD_BEEF);
+        let first = generate_sample(&snapshot, &config).unwrap();
+        config.sample_seed += 1;
+        let second = generate_sample(&snapshot, &config).unwrap();
+        assert_eq!(first.seed, 0xDEAD_BEEF);
+        let ys = |s: &SampleData| s.points.iter().map(|p| (p.tenor, p.y_obs)).collect::<Vec<_>>();
+        assert_eq!(ys(&first), ys(&second));
+        assert_ne!(ys(&first), ys(&hashed));
+    }
+
+    #[test]
+    fn tenor_grid_sample_cycles_and_keeps_level_shocks() {
+        let snapshot = test_snapshot();
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.sample_count = 12;
+        config.tenor_min = 1.0;
+        config.tenor_max = 5.0;
+        config.rng_seed = Some(7);
+        let uniform = generate_sample(&snapshot, &config).unwrap();
+
+        config.tenor_grid_step = Some(1.0);
+        let grid = generate_sample(&snapshot, &config).unwrap();
+        let tenors: Vec<f64> = grid.points.iter().map(|p| p.tenor).collect();
+        assert_eq!(&tenors[..7], &[1.0, 2.0, 3.0, 4.0, 5.0, 1.0, 2.0]);
+        assert_eq!(grid.baseline.len(), grid.points.len());
+
+        // Recover the standardized shock (z + jump) per bond: it must not move.
+        let shock = |s: &SampleData, i: usize| {
+            let sigma = s.points[i].extras.sigma_ln.unwrap();
+            let mc = jump_mean_correction(
+                sigma,
+                config.jump_prob_wide,
+                config.jump_prob_tight,
+                config.jump_k_wide,
+                config.jump_k_tight,
+            );
+            ((s.points[i].y_obs / s.baseline[i]).ln() + mc) / sigma
+        };
+        for i in 0..config.sample_count {
+            assert!((shock(&grid, i) - shock(&uniform, i)).abs() < 1e-9, "bond {i}");
+        }
+
+        config.tenor_grid_step = Some(10.0);
+        assert_eq!(generate_sample(&snapshot, &config).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn vol_bounds_clamp_sigma_and_keep_the_same_shocks() {
+        let snapshot = test_snapshot();
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.sample_count = 40;
+        config.tenor_min = 0.5;
+        config.tenor_max = 30.0;
+        config.rng_seed = Some(11);
+        let free = generate_sample(&snapshot, &config).unwrap();
+        assert_eq!((free.vol_floored, free.vol_capped), (0, 0));
+
+        // 1% vols scaled by sqrt(tenor) span roughly 0.007-0.055.
+        config.vol_floor = Some(0.01);
+        config.vol_cap = Some(0.03);
+        let bounded = generate_sample(&snapshot, &config).unwrap();
+        let sigmas: Vec<f64> = bounded.points.iter().map(|p| p.extras.sigma_ln.unwrap()).collect();
+        assert!(sigmas.iter().all(|s| (0.01..=0.03).contains(s)), "{sigmas:?}");
+        let floored = free.points.iter().filter(|p| p.extras.sigma_ln.unwrap() < 0.01).count();
+        let capped = free.points.iter().filter(|p| p.extras.sigma_ln.unwrap() > 0.03).count();
+        assert!(capped > 0 && floored > 0);
+        assert_eq!((bounded.vol_floored, bounded.vol_capped), (floored, capped));
+
+        // The standardized shock is unchanged, and the mean correction uses the
+        // clamped sigma.
+        let shock = |s: &SampleData, i: usize| {
+            let sigma = s.points[i].extras.sigma_ln.unwrap();
+            let mc = jump_mean_correction(
+                sigma,
+                config.jump_prob_wide,
+                config.jump_prob_tight,
+                config.jump_k_wide,
+                config.jump_k_tight,
+            );
+            ((s.points[i].y_obs / s.baseline[i]).ln() + mc) / sigma
+        };
+        for i in 0..config.sample_count {
+            assert!((shock(&bounded, i) - shock(&free, i)).abs() < 1e-9, "bond {i}");
+        }
+
+        config.vol_floor = Some(0.05);
+        assert_eq!(generate_sample(&snapshot, &config).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn rating_blend_mixes_levels_and_single_band_matches_plain_rating() {
+        let mut snapshot = test_snapshot();
+        snapshot.ratings_bp.insert(RatingBand::A, 80.0);
+        snapshot.ratings_bp.insert(RatingBand::BBB, 120.0);
+        snapshot.volatility.ratings_vol.insert(RatingBand::A, 0.02);
+
+        for bad in ["A:0.5,BBB:0.6", "A:-0.5,BBB:1.5", "A:0.5,A:0.5", "XYZ:1", "A", "A:nan"] {
+            assert!(bad.parse::<RatingBlend>().is_err(), "{bad}");
+        }
+        let blend: RatingBlend = "a:0.25, BBB:0.75".parse().unwrap();
+        assert_eq!(blend.dominant(), RatingBand::BBB);
+        assert_eq!(blend.label(), "A/BBB");
+        let mixed = blended_baseline_curve(&snapshot, &blend, 5.0).unwrap();
+        let a = baseline_curve(&snapshot, RatingBand::A, 5.0).unwrap();
+        let bbb = baseline_curve(&snapshot, RatingBand::BBB, 5.0).unwrap();
+        assert!((mixed - (0.25 * a + 0.75 * bbb)).abs() < 1e-9);
+
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.sample_count = 20;
+        config.rating = RatingBand::A;
+        let plain = generate_sample(&snapshot, &config).unwrap();
+        config.rating_blend = Some("A:1".parse().unwrap());
+        let single = generate_sample(&snapshot, &config).unwrap();
+        assert_eq!(single.seed, plain.seed);
+        let rows = |s: &SampleData| {
+            s.points.iter().map(|p| (p.id.clone(), p.tenor.to_bits(), p.y_obs.to_bits())).collect::<Vec<_>>()
+        };
+        assert_eq!(rows(&single), rows(&plain));
+
+        config.rating_blend = Some(blend);
+        let blended = generate_sample(&snapshot, &config).unwrap();
+        assert!(blended.points[0].id.starts_with("A/BBB-"));
+        assert_ne!(blended.seed, plain.seed);
+    }
 }
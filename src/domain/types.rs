@@ -6,14 +6,17 @@
 //! - exported to JSON/CSV
 //! - reloaded later for plotting or comparisons
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use chrono::NaiveDate;
 use clap::ValueEnum;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 /// ICE BofA OAS rating bands available from FRED.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+///
+/// Ordering follows declaration order (highest to lowest quality).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum RatingBand {
     AAA,
@@ -64,6 +67,20 @@ impl RatingBand {
             RatingBand::CCC => "CCC",
         }
     }
+
+    /// Entries of a rating-keyed map in `ALL` order, skipping absent bands.
+    ///
+    /// `HashMap` iteration order changes from run to run, so anything that
+    /// prints or serializes rating-keyed data goes through this instead.
+    pub fn ordered<V>(map: &HashMap<RatingBand, V>) -> impl Iterator<Item = (RatingBand, &V)> {
+        RatingBand::ALL.into_iter().filter_map(move |band| map.get(&band).map(|v| (band, v)))
+    }
+}
+
+/// `#[serde(serialize_with)]` helper: a rating-keyed map as an object with keys
+/// in `RatingBand::ALL` order, so JSON exports are byte-stable.
+pub fn serialize_rating_map<V: Serialize, S: Serializer>(map: &HashMap<RatingBand, V>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(RatingBand::ordered(map))
 }
 
 /// Concrete y-kind for fitting (simplified for FRED mode).
@@ -81,31 +98,447 @@ impl YKind {
     }
 }
 
+/// Unit of the y-values a run produces and reports.
+///
+/// The synthetic FRED sample is generated in basis points; `Decimal` divides every
+/// y-value by 10,000 before fitting. The fit is unit-invariant (taus and model
+/// choice are unchanged, betas scale by the same factor).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum YUnit {
+    /// Basis points (e.g. 125.0).
+    #[default]
+    Bp,
+    /// Decimal spread (e.g. 0.0125).
+    Decimal,
+}
+
+impl YUnit {
+    /// Label used in headers, axis titles, and exports.
+    pub fn label(self) -> &'static str {
+        match self {
+            YUnit::Bp => "bp",
+            YUnit::Decimal => "decimal",
+        }
+    }
+
+    /// Size of one basis point in this unit.
+    pub fn per_bp(self) -> f64 {
+        match self {
+            YUnit::Bp => 1.0,
+            YUnit::Decimal => 1e-4,
+        }
+    }
+
+    /// Decimal places used when printing y-values in this unit.
+    pub fn decimals(self) -> usize {
+        match self {
+            YUnit::Bp => 2,
+            YUnit::Decimal => 6,
+        }
+    }
+}
+
+/// What to do when a rating band has no FRED value on the snapshot date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum MissingRating {
+    /// Fail the fetch.
+    #[default]
+    Error,
+    /// Synthesize the level from neighbouring bands (flagged in warnings).
+    Interp,
+    /// Leave the band out (batch runs skip it).
+    Skip,
+}
+
+/// How the FRED snapshot date is chosen across series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum DatePolicy {
+    /// Latest date observed in every fetched series.
+    #[default]
+    Common,
+    /// Latest date of the overall index; lagging series are forward-filled
+    /// from their most recent prior observation (flagged in warnings).
+    LatestOverall,
+}
+
+/// What to do with a series below `--min-vol-obs` usable returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum ShortVolHistory {
+    /// Use `FALLBACK_VOL` for the series (flagged in warnings).
+    #[default]
+    Warn,
+    /// Fail the fetch.
+    Error,
+}
+
+/// What to do with observations whose fit weight is non-finite or `<= 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum BadWeight {
+    /// Drop those bonds from the fit (counted in the accounting and warned about).
+    #[default]
+    Drop,
+    /// Fail the run, reporting how many weights were invalid.
+    Error,
+}
+
+/// Ordering key for the cheap/rich tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum RankBy {
+    /// Raw residual in output units.
+    #[default]
+    Residual,
+    /// Residual in units of the fit's residual standard error.
+    Se,
+    /// Composite signal (residual, z-score, DV01; `--signal-weights`).
+    Signal,
+}
+
+/// Scale the curve is fitted on (`--y-transform`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum YTransform {
+    /// Fit `y` directly.
+    #[default]
+    None,
+    /// Fit `ln(y)`; requires every observation to be positive.
+    Log,
+}
+
+impl YTransform {
+    pub fn is_none(&self) -> bool {
+        *self == YTransform::None
+    }
+
+    /// Map an observation onto the fitting scale.
+    pub fn apply(self, y: f64) -> f64 {
+        match self {
+            YTransform::None => y,
+            YTransform::Log => y.ln(),
+        }
+    }
+
+    /// Map a fitted value back to the observation scale.
+    pub fn invert(self, v: f64) -> f64 {
+        match self {
+            YTransform::None => v,
+            YTransform::Log => v.exp(),
+        }
+    }
+}
+
+/// Target language for `--export-formula`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+pub enum FormulaLang {
+    /// A `rv_curve(t)` function with `f1`/`f2` helpers.
+    Python,
+    /// A single `=LET(...)` cell formula reading the tenor from `A2`.
+    Excel,
+}
+
+/// How `CurveFile::interp` treats tenors outside the stored grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GridExtrapolation {
+    /// Hold the first/last grid value flat.
+    #[default]
+    Clamp,
+    /// Extend the first/last grid segment linearly.
+    Linear,
+}
+
+/// A tenor pair for a slope ("box") metric, e.g. `2-10` for the 2s10s slope.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TenorPair {
+    /// The pair exactly as requested (used as the output label).
+    pub label: String,
+    pub t1: f64,
+    pub t2: f64,
+}
+
+impl std::str::FromStr for TenorPair {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let label = s.trim();
+        let (a, b) = label
+            .split_once('-')
+            .ok_or_else(|| format!("'{label}' is not a tenor pair like 2-10"))?;
+        let parse = |v: &str| match v.trim().parse::<f64>() {
+            Ok(t) if t.is_finite() && t > 0.0 => Ok(t),
+            _ => Err(format!("'{label}': '{v}' is not a positive tenor in years")),
+        };
+        let (t1, t2) = (parse(a)?, parse(b)?);
+        if t1 == t2 {
+            return Err(format!("'{label}': tenors must differ"));
+        }
+        Ok(Self { label: label.to_string(), t1, t2 })
+    }
+}
+
+/// Weighted mix of rating bands for a blended sample baseline, e.g.
+/// `A:0.5,BBB:0.5`.
+///
+/// Weights must be non-negative and sum to 1 (within `1e-3`); they are
+/// renormalized to sum exactly to 1, so a single-band blend has weight 1.0.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RatingBlend {
+    pub parts: Vec<(RatingBand, f64)>,
+}
+
+impl RatingBlend {
+    /// Blend that is exactly `rating`.
+    pub fn single(rating: RatingBand) -> Self {
+        Self { parts: vec![(rating, 1.0)] }
+    }
+
+    /// Heaviest band (first listed on ties).
+    pub fn dominant(&self) -> RatingBand {
+        let mut best = self.parts[0];
+        for &part in &self.parts[1..] {
+            if part.1 > best.1 {
+                best = part;
+            }
+        }
+        best.0
+    }
+
+    /// Label for generated bond IDs and metadata: `BBB`, or `A/BBB` for a blend.
+    pub fn label(&self) -> String {
+        let names: Vec<&str> = self.parts.iter().map(|(band, _)| band.display_name()).collect();
+        names.join("/")
+    }
+}
+
+impl std::fmt::Display for RatingBlend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.parts.iter().map(|(band, w)| format!("{}:{w}", band.display_name())).collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+impl std::str::FromStr for RatingBlend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<(RatingBand, f64)> = Vec::new();
+        for item in s.split(',').map(str::trim) {
+            let (band, weight) = item
+                .split_once(':')
+                .ok_or_else(|| format!("'{item}' is not a band:weight pair like BBB:0.5"))?;
+            let band = RatingBand::from_str(band.trim(), true).map_err(|_| format!("'{}' is not a rating band", band.trim()))?;
+            let weight = match weight.trim().parse::<f64>() {
+                Ok(w) if w.is_finite() && w >= 0.0 => w,
+                _ => return Err(format!("'{item}': weight must be a non-negative number")),
+            };
+            if parts.iter().any(|(b, _)| *b == band) {
+                return Err(format!("rating {} listed twice", band.display_name()));
+            }
+            parts.push((band, weight));
+        }
+        let total: f64 = parts.iter().map(|(_, w)| w).sum();
+        if (total - 1.0).abs() > 1e-3 {
+            return Err(format!("weights sum to {total}, expected 1"));
+        }
+        parts.retain(|(_, w)| *w > 0.0);
+        parts.iter_mut().for_each(|(_, w)| *w /= total);
+        Ok(Self { parts })
+    }
+}
+
+/// Strictly increasing tenor bin edges (years), e.g. `0,2,5,10,30`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BinEdges(pub Vec<f64>);
+
+impl Default for BinEdges {
+    fn default() -> Self {
+        Self(vec![0.0, 2.0, 5.0, 10.0, 20.0, 30.0])
+    }
+}
+
+impl std::str::FromStr for BinEdges {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let edges = s
+            .split(',')
+            .map(|v| match v.trim().parse::<f64>() {
+                Ok(t) if t.is_finite() && t >= 0.0 => Ok(t),
+                _ => Err(format!("'{}' is not a non-negative tenor", v.trim())),
+            })
+            .collect::<Result<Vec<f64>, String>>()?;
+        if edges.len() < 2 {
+            return Err("need at least two edges".to_string());
+        }
+        if edges.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("edges must be strictly increasing".to_string());
+        }
+        Ok(Self(edges))
+    }
+}
+
+/// Weights of the residual, z-score and DV01 terms in the per-bond `signal`
+/// (`--signal-weights R,Z,D`; see `report::assign_signals`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SignalWeights {
+    pub residual: f64,
+    pub z: f64,
+    pub dv01: f64,
+}
+
+impl Default for SignalWeights {
+    fn default() -> Self {
+        Self { residual: 1.0, z: 1.0, dv01: 1.0 }
+    }
+}
+
+impl std::str::FromStr for SignalWeights {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let weights = s
+            .split(',')
+            .map(|v| match v.trim().parse::<f64>() {
+                Ok(w) if w.is_finite() && w >= 0.0 => Ok(w),
+                _ => Err(format!("'{}' is not a non-negative weight", v.trim())),
+            })
+            .collect::<Result<Vec<f64>, String>>()?;
+        let [residual, z, dv01] = weights[..] else {
+            return Err(format!("expected three weights R,Z,D (got {})", weights.len()));
+        };
+        if residual + z + dv01 == 0.0 {
+            return Err("at least one weight must be positive".to_string());
+        }
+        Ok(Self { residual, z, dv01 })
+    }
+}
+
+/// Number formatting for printed tables and the run summary.
+///
+/// Exports (CSV/JSON) are always machine-plain regardless of this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum NumberLocale {
+    /// `1234.56` (no grouping, `.` decimal).
+    #[default]
+    Plain,
+    /// `1,234.56`
+    Us,
+    /// `1.234,56`
+    Eu,
+}
+
+/// Shape of the fit's standard output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, ValueEnum)]
+pub enum OutputFormat {
+    /// Summary, rankings, and plot for a terminal.
+    #[default]
+    Text,
+    /// One JSON object per bond residual, cheapest first, flushed line by line.
+    Ndjson,
+}
+
 /// Which model(s) to fit.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum ModelSpec {
     Auto,
     Ns,
+    /// NSS with one shared decay (see `ModelKind::NssShared`).
+    #[serde(rename = "nss-shared")]
+    NssShared,
     Nss,
     Nssc,
     All,
 }
 
+/// Parameterization of the NS-family decay parameters.
+///
+/// Both produce the same loadings (and therefore identical fitted curves):
+/// - `Standard`: decay *times* `τ` (years), loadings in `x = t/τ`
+/// - `DieboldLi`: decay *rates* `λ = 1/τ` (per year), loadings in `x = λt`
+///
+/// Fitting always runs on `τ`; the basis controls how the decay parameters are
+/// reported and recorded in curve JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Basis {
+    #[default]
+    Standard,
+    DieboldLi,
+}
+
+impl Basis {
+    /// Label for the reported decay parameters.
+    pub fn param_label(self) -> &'static str {
+        match self {
+            Basis::Standard => "taus",
+            Basis::DieboldLi => "lambdas",
+        }
+    }
+
+    /// Convert fitted `τ` values into this basis's decay parameters.
+    pub fn decay_params(self, taus: &[f64]) -> Vec<f64> {
+        match self {
+            Basis::Standard => taus.to_vec(),
+            Basis::DieboldLi => taus.iter().map(|t| 1.0 / t).collect(),
+        }
+    }
+}
+
+/// How synthetic bonds are weighted in the fit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum WeightMode {
+    /// Every bond gets weight 1.0.
+    #[default]
+    Uniform,
+    /// Inverse-variance weights `1/sigma_ln^2` from the injected noise level,
+    /// normalized to mean 1.
+    InverseVar,
+}
+
+/// Tie-break rule among tau candidates with exactly equal SSE.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum TauTiebreak {
+    /// Lowest grid index (grids are generated in ascending tau order).
+    #[default]
+    LowIndex,
+    /// Lexicographically smallest tau tuple.
+    Smallest,
+    /// Lexicographically largest tau tuple (smoothest curve).
+    Largest,
+}
+
 /// Concrete fitted model kind.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ModelKind {
     Ns,
+    /// NSS with a single decay: the second hump's decay is tied to the first at
+    /// `models::SHARED_TAU_RATIO` (4 betas, 1 tau).
+    #[serde(rename = "nss-shared")]
+    NssShared,
     Nss,
     Nssc,
 }
 
 impl ModelKind {
+    /// Machine-readable name, as serialized (`"nss-shared"`); used in CSV,
+    /// `--compact`, and formula output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ModelKind::Ns => "ns",
+            ModelKind::NssShared => "nss-shared",
+            ModelKind::Nss => "nss",
+            ModelKind::Nssc => "nssc",
+        }
+    }
+
     /// Human-readable label for terminal output.
     pub fn display_name(self) -> &'static str {
         match self {
             ModelKind::Ns => "NS",
+            ModelKind::NssShared => "NSS (shared τ)",
             ModelKind::Nss => "NSS",
             ModelKind::Nssc => "NSS+ (3-hump)",
         }
@@ -115,6 +548,7 @@ impl ModelKind {
     pub fn beta_len(self) -> usize {
         match self {
             ModelKind::Ns => 3,
+            ModelKind::NssShared => 4,
             ModelKind::Nss => 4,
             ModelKind::Nssc => 5,
         }
@@ -124,6 +558,7 @@ impl ModelKind {
     pub fn tau_len(self) -> usize {
         match self {
             ModelKind::Ns => 1,
+            ModelKind::NssShared => 1,
             ModelKind::Nss => 2,
             ModelKind::Nssc => 3,
         }
@@ -158,6 +593,18 @@ pub struct BondPoint {
     pub extras: BondExtras,
 }
 
+impl BondPoint {
+    /// Tenor as shown in reports and exports: snapped to the nearest multiple
+    /// of `step` (`--round-tenors`), or unchanged without one. Fitting always
+    /// uses the raw `tenor`.
+    pub fn reported_tenor(&self, step: Option<f64>) -> f64 {
+        match step {
+            Some(step) => (self.tenor / step).round() * step,
+            None => self.tenor,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct BondMeta {
     pub issuer: Option<String>,
@@ -167,6 +614,10 @@ pub struct BondMeta {
 #[derive(Debug, Clone, Default)]
 pub struct BondExtras {
     pub oas: Option<f64>,
+    /// Log-noise std dev injected when this bond was generated (synthetic only).
+    pub sigma_ln: Option<f64>,
+    /// Spread DV01 per unit notional, when the input provides it.
+    pub dv01: Option<f64>,
 }
 
 /// A per-bond fitted result (used for ranking and exports).
@@ -175,6 +626,52 @@ pub struct BondResidual {
     pub point: BondPoint,
     pub y_fit: f64,
     pub residual: f64,
+    /// Rank-based richness percentile within this fit's residuals: 100 = richest
+    /// (most negative residual), 0 = cheapest; tied residuals share a value.
+    pub richness_pct: f64,
+    /// Weighted residual (`sqrt(weight) * residual`, on the fitting scale) in
+    /// multiples of the fit's residual standard error; `None` when the fit has
+    /// no degrees of freedom left or a zero SSE.
+    pub residual_se_units: Option<f64>,
+    /// Composite cheap (+) / rich (-) score in `[-1, 1]` (`assign_signals`);
+    /// 0 until assigned.
+    pub signal: f64,
+}
+
+#[cfg(test)]
+impl BondResidual {
+    /// Test fixture: bond `id` at `tenor`, `residual` above a fit of 100, with
+    /// unit weight, no metadata, and neutral richness/z/signal. Tests set any
+    /// other field on the returned value.
+    pub fn fixture(id: &str, tenor: f64, residual: f64) -> Self {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        BondResidual {
+            point: BondPoint {
+                id: id.to_string(),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor,
+                y_obs: 100.0 + residual,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            },
+            y_fit: 100.0,
+            residual,
+            richness_pct: 50.0,
+            residual_se_units: None,
+            signal: 0.0,
+        }
+    }
+
+    /// One `fixture` per value: ids `B0, B1, ...` at tenors `0.5, 1.5, ...`.
+    pub fn fixtures(residuals: &[f64]) -> Vec<Self> {
+        residuals
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| Self::fixture(&format!("B{i}"), 0.5 + i as f64, r))
+            .collect()
+    }
 }
 
 /// Fit quality diagnostics.
@@ -193,6 +690,23 @@ pub struct CurveModel {
     pub display_name: String,
     pub betas: Vec<f64>,
     pub taus: Vec<f64>,
+    /// Scale the betas were fitted on; `CurveModel::eval` maps back to y.
+    /// Omitted from curve JSON when `none`.
+    #[serde(default, skip_serializing_if = "YTransform::is_none")]
+    pub y_transform: YTransform,
+    /// Long segment of a `--splice-at` fit; these parameters are then the
+    /// short segment. Omitted from curve JSON when unspliced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splice: Option<Box<Splice>>,
+}
+
+/// Where and how a spliced curve hands over from its short to its long
+/// segment: a linear crossfade over `window` years centred on `at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Splice {
+    pub at: f64,
+    pub window: f64,
+    pub long: CurveModel,
 }
 
 /// Fit output for a single model.
@@ -202,6 +716,18 @@ pub struct FitResult {
     pub quality: FitQuality,
 }
 
+impl FitResult {
+    /// Residual standard error `sqrt(SSE / (n - k))`, with `SSE` the weighted
+    /// SSE and `k` the fitted parameter count (betas + taus, both segments of a
+    /// spliced curve). `None` when
+    /// `n <= k`.
+    pub fn residual_se(&self) -> Option<f64> {
+        let k = self.model.param_count();
+        let dof = self.quality.n.checked_sub(k).filter(|&d| d > 0)?;
+        Some((self.quality.sse / dof as f64).sqrt())
+    }
+}
+
 /// High-level run specification.
 #[derive(Debug, Clone)]
 pub struct RunSpec {
@@ -222,36 +748,192 @@ pub struct DatasetStats {
 /// A full run's configuration as understood by the pipeline.
 ///
 /// This is derived from CLI flags (plus defaults).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FitConfig {
-    /// Rating band for sample generation.
+    /// Rating band for sample generation (the heaviest band when blending).
     pub rating: RatingBand,
+    /// Blend of bands whose levels and vols replace `rating`'s in the sample.
+    pub rating_blend: Option<RatingBlend>,
 
     /// Number of synthetic bonds to generate.
     pub sample_count: usize,
 
+    /// Requested as-of date (FRED `observation_end`); `None` means latest.
+    pub asof: Option<NaiveDate>,
+    /// Policy for rating bands missing from the FRED snapshot.
+    pub missing_rating: MissingRating,
+    /// How the snapshot date is resolved across series.
+    pub date_policy: DatePolicy,
+    /// Minimum usable daily returns per series for its volatility estimate
+    /// (`None` = any history will do).
+    pub min_vol_obs: Option<usize>,
+    /// Fallback or fail for series below `min_vol_obs`.
+    pub short_vol_history: ShortVolHistory,
+    /// Error instead of resolving a non-business-day or unobserved `asof`.
+    pub strict_dates: bool,
+    /// JSON file overriding the FRED series IDs (`data::fred::SeriesMap`).
+    pub series_map: Option<PathBuf>,
+    /// File holding the FRED API key (takes precedence over the environment).
+    pub api_key_file: Option<PathBuf>,
+    /// Trailing observation window averaged into each FRED level (1 = none).
+    pub smooth_days: usize,
+    /// Fit these real bonds (CSV) instead of a synthetic sample.
+    pub points_file: Option<PathBuf>,
+    /// With `points_file`, skip the FRED baseline (no baseline values or overlay).
+    pub no_prior: bool,
+
     /// Optional user-provided seed for reproducibility (combined with FRED data).
     pub sample_seed: u64,
+    /// Exact RNG seed for the sample, overriding the snapshot/config hash
+    /// (`--deterministic-seed`, or drawn from OS entropy by `--random-seed`).
+    pub rng_seed: Option<u64>,
+    /// Separate seed for the sample's noise and jumps; tenors keep following
+    /// the main seed. Set per seed by `--seed-sweep` so each bond ID keeps its
+    /// tenor across the sweep.
+    pub noise_seed: Option<u64>,
+
+    /// Base weighting of generated bonds.
+    pub weight_mode: WeightMode,
 
     /// Model selection spec.
     pub model_spec: ModelSpec,
 
+    /// Decay parameterization used when reporting fitted parameters.
+    pub basis: Basis,
+
     pub tau_min: f64,
     pub tau_max: f64,
     pub tau_steps_ns: usize,
     pub tau_steps_nss: usize,
     pub tau_steps_nssc: usize,
+    /// Tie-break among equal-SSE tau candidates.
+    pub tau_tiebreak: TauTiebreak,
+    /// Largest |beta| a tau candidate may solve to (`None` = unbounded).
+    pub beta_abs_max: Option<f64>,
+    /// Bounds on a candidate's front-end level `y(0)`, `β0 + β1` mapped back to
+    /// the observation scale (output unit; `None` = open on that side).
+    pub y0_min: Option<f64>,
+    pub y0_max: Option<f64>,
+    /// Scale to fit on; reporting is always on the original y scale.
+    pub y_transform: YTransform,
+    /// BIC points within which a simpler model beats the minimum (0 = pure minimum BIC).
+    pub bic_margin: f64,
+    /// Knot tenor for a two-segment spliced fit (`--splice-at`).
+    pub splice_at: Option<f64>,
+    /// Crossfade width (years) around `splice_at`.
+    pub splice_window: f64,
+    /// Show tau-search progress on stderr (TTY only).
+    pub progress: bool,
+    /// Extra observations required beyond the parameter count (`n >= k + buffer`).
+    pub min_n_buffer: usize,
+    /// Observed tenor span, as a fraction of `[tenor_min, tenor_max]`, below
+    /// which models beyond NS are skipped.
+    pub min_coverage: f64,
 
     pub tenor_min: f64,
     pub tenor_max: f64,
+    /// Fit only bonds with tenors in `[fit_min, fit_max]` (each defaulting to
+    /// the tenor bounds); residuals, ranking and plots still cover every bond.
+    pub fit_min: Option<f64>,
+    pub fit_max: Option<f64>,
+    /// Place sample tenors on a fixed grid with this spacing instead of drawing
+    /// them uniformly (cycling when `sample_count` exceeds the grid).
+    pub tenor_grid_step: Option<f64>,
+    /// Cap the exported curve grid at `observed tenor max + years` (fit unchanged).
+    pub max_extrap_years: Option<f64>,
+    /// Snap reported/exported tenors to this step (years); cosmetic only.
+    pub round_tenors: Option<f64>,
+
+    /// Optional per-tenor weight multipliers applied before fitting.
+    pub weight_file: Option<PathBuf>,
+    /// Policy for bonds left with an invalid weight after weighting.
+    pub on_bad_weight: BadWeight,
+    /// Largest share of the total weight any one issuer may hold.
+    pub issuer_cap: Option<f64>,
 
     pub top_n: usize,
+    /// Cheap/rich list lengths overriding `top_n` per side (`--top-cheap`/`--top-rich`).
+    pub top_cheap: Option<usize>,
+    pub top_rich: Option<usize>,
+    /// Key for the cheap/rich tables (`--rank-by`).
+    pub rank_by: RankBy,
+    /// Term weights for the per-bond `signal`.
+    pub signal_weights: SignalWeights,
+    /// Unit for produced/reported y-values.
+    pub output_unit: YUnit,
+    /// Number formatting for printed tables/summary (never exports).
+    pub locale: NumberLocale,
+    /// Replace all terminal output with a single `key=value` summary line.
+    pub compact: bool,
+    /// Terminal report or streamed per-bond NDJSON.
+    pub output_format: OutputFormat,
+    /// Print per-phase wall-clock timings after the summary.
+    pub timing: bool,
     pub plot: bool,
     pub plot_width: usize,
     pub plot_height: usize,
+    /// Overlay the generating baseline curve on the ASCII/TUI charts.
+    pub plot_baseline: bool,
+    /// Overlay bucket volatility on a right-hand axis in the ASCII/TUI charts.
+    pub plot_vol: bool,
+    /// Append `CurveModel::fingerprint` to the ASCII plot header.
+    pub plot_hash: bool,
 
     pub export_results: Option<PathBuf>,
+    /// Append to `export_results` instead of overwriting it.
+    pub export_append: bool,
     pub export_curve: Option<PathBuf>,
+    /// CSV of every evaluated tau candidate for the selected model.
+    pub export_landscape: Option<PathBuf>,
+    /// Time-series CSV to append one row (date + selected model params) to.
+    pub export_timeseries: Option<PathBuf>,
+    /// Print the side-by-side model metrics table.
+    pub compare_models: bool,
+    /// Write the model metrics table (CSV, or JSON for a `.json` path).
+    pub export_models: Option<PathBuf>,
+    /// Write the fitted sample as a `--sample-from-points` CSV here.
+    pub export_sample: Option<PathBuf>,
+    /// Write the one-row residual summary (`ResidualStats`) CSV here.
+    pub export_stats: Option<PathBuf>,
+    /// Write the run summary (`RunSummary`) as JSON here.
+    pub export_diagnostics: Option<PathBuf>,
+    /// Include the FRED inputs in the diagnostics JSON.
+    pub embed_inputs: bool,
+    /// Key tenors to print the fitted level at (`--pillars`; empty = off).
+    pub pillars: Vec<f64>,
+    /// Also write the pillar table as JSON here.
+    pub pillars_json: Option<PathBuf>,
+    /// Emit the selected curve as a formula in this language.
+    pub export_formula: Option<FormulaLang>,
+    /// Destination for `export_formula` (default: `rv-formula-<rating>-<date>.<ext>`).
+    pub formula_out: Option<PathBuf>,
+
+    /// Previous curve JSON for the residual-shift report.
+    pub baseline_curve: Option<PathBuf>,
+    /// Order the residual-shift report by shift instead of today's residual.
+    pub rank_by_shift: bool,
+
+    /// Exit with code 3 when the run produced any warnings.
+    pub fail_on_warnings: bool,
+
+    /// Compute leave-one-out influence diagnostics.
+    pub influence: bool,
+
+    /// Fit every rating band as a parallel batch (summary table only).
+    pub all_ratings: bool,
+    /// Thread budget for batch runs (`None` = Rayon default, one per core).
+    pub threads: Option<usize>,
+    /// Refit over this many derived noise seeds and report cheap/rich stability.
+    pub seed_sweep: Option<usize>,
+
+    /// Print the step-by-step model selection trace.
+    pub explain_selection: bool,
+    /// Print residual serial-correlation diagnostics.
+    pub diagnostics: bool,
+    /// Slope metrics `y(t2) - y(t1)` to report in the summary.
+    pub slopes: Vec<TenorPair>,
+    /// Tenor bins for the worst-fit region diagnostic.
+    pub resid_bins: BinEdges,
 
     /// Jump probability for wide outliers (rich bonds).
     pub jump_prob_wide: f64,
@@ -261,18 +943,105 @@ pub struct FitConfig {
     pub jump_k_wide: f64,
     /// Jump magnitude multiplier for tight outliers.
     pub jump_k_tight: f64,
+    /// Lower bound on each bond's injected log-vol (`None` = unbounded).
+    pub vol_floor: Option<f64>,
+    /// Upper bound on each bond's injected log-vol (`None` = unbounded).
+    pub vol_cap: Option<f64>,
+}
+
+impl FitConfig {
+    /// Lengths of the cheap and rich lists: `--top-cheap`/`--top-rich`, each
+    /// falling back to `--top`.
+    pub fn top_counts(&self) -> (usize, usize) {
+        (self.top_cheap.unwrap_or(self.top_n), self.top_rich.unwrap_or(self.top_n))
+    }
+
+    /// The `--y0-min`/`--y0-max` range, open where unset, when either is set.
+    pub fn y0_range(&self) -> Option<(f64, f64)> {
+        (self.y0_min.is_some() || self.y0_max.is_some())
+            .then(|| (self.y0_min.unwrap_or(f64::NEG_INFINITY), self.y0_max.unwrap_or(f64::INFINITY)))
+    }
+
+    /// The `--fit-min`/`--fit-max` window, when either is set.
+    pub fn fit_window(&self) -> Option<(f64, f64)> {
+        (self.fit_min.is_some() || self.fit_max.is_some())
+            .then(|| (self.fit_min.unwrap_or(self.tenor_min), self.fit_max.unwrap_or(self.tenor_max)))
+    }
+
+    /// Bands the sample is drawn from: `rating_blend`, or `rating` alone.
+    pub fn sample_blend(&self) -> RatingBlend {
+        self.rating_blend.clone().unwrap_or_else(|| RatingBlend::single(self.rating))
+    }
 }
 
-/// A saved curve file (JSON).
+/// Current `CurveFile::schema_version`; bump when the curve JSON layout changes.
+pub const CURVE_SCHEMA_VERSION: u32 = 1;
+
+/// A saved curve file (JSON). `rv schema curve` prints its JSON Schema.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurveFile {
     pub tool: String,
+    /// Layout version (`CURVE_SCHEMA_VERSION`); files written before it
+    /// existed read as version 1.
+    #[serde(default = "default_curve_schema_version")]
+    pub schema_version: u32,
     pub asof_date: NaiveDate,
+    /// As-of date requested by the user, when it was given (`asof_date` is the
+    /// FRED observation date actually used).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_asof: Option<NaiveDate>,
     pub y: YKind,
+    /// Unit of `grid.y` and the model betas.
+    #[serde(default)]
+    pub unit: YUnit,
     pub rating: RatingBand,
+    /// Decay parameterization used for reporting (`model.taus` are always `τ`).
+    #[serde(default)]
+    pub basis: Basis,
+    /// Diebold-Li decay rates `λ = 1/τ` (only present for `basis = diebold-li`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lambdas: Option<Vec<f64>>,
     pub model: CurveModel,
     pub fit_quality: FitQuality,
     pub grid: CurveGrid,
+    /// Shape of `grid.y` (see `models::shape`); absent in older files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+}
+
+fn default_curve_schema_version() -> u32 {
+    1
+}
+
+#[cfg(test)]
+impl CurveFile {
+    /// Test fixture: a BBB OAS curve in bp as of 2025-01-02, holding an NS
+    /// model with `betas`/`taus` and the grid `tenor_years`/`y` (not checked
+    /// against the model). Tests set any other field on the returned value.
+    pub fn fixture(betas: &[f64], taus: &[f64], tenor_years: &[f64], y: &[f64]) -> Self {
+        CurveFile {
+            tool: "rv".to_string(),
+            schema_version: CURVE_SCHEMA_VERSION,
+            asof_date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+            requested_asof: None,
+            y: YKind::Oas,
+            unit: YUnit::Bp,
+            rating: RatingBand::BBB,
+            basis: Basis::Standard,
+            lambdas: None,
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: betas.to_vec(),
+                taus: taus.to_vec(),
+                y_transform: YTransform::None,
+                splice: None,
+            },
+            fit_quality: FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 0 },
+            grid: CurveGrid { tenor_years: tenor_years.to_vec(), y: y.to_vec() },
+            shape: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,3 +1,16 @@
+/// Exit codes returned through `AppError::exit_code`, with their meanings.
+///
+/// Printed by the hidden `rv exit-codes` command. Code 2 is shared with clap,
+/// which exits with 2 on argument errors.
+pub const EXIT_CODES: [(u8, &str); 6] = [
+    (0, "success"),
+    (1, "internal error (e.g. the batch thread pool could not start, or JSON output failed to serialize) or a failed `rv selftest`"),
+    (2, "usage or input error: invalid arguments or config, unreadable/invalid input files, failed exports, `rv validate` problems"),
+    (3, "insufficient data to fit any model, or warnings raised under --fail-on-warnings"),
+    (4, "data-source or runtime failure: FRED request/response errors, missing or invalid series values, non-finite model output, terminal I/O"),
+    (5, "`rv check`: the saved curve deviates from today's FRED baseline by more than --max-deviation"),
+];
+
 #[derive(Clone)]
 pub struct AppError {
     exit_code: u8,
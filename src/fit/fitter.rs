@@ -12,12 +12,15 @@
 //!
 //! and return the best (lowest SSE) candidate.
 
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use nalgebra::{DMatrix, DVector};
 use rayon::prelude::*;
 
-use crate::domain::{BondPoint, ModelKind};
+use crate::domain::{BondPoint, FitConfig, ModelKind, TauTiebreak, YTransform};
 use crate::error::AppError;
-use crate::math::solve_least_squares;
+use crate::math::{solve_least_squares_with, RELAXED_SVD_TOLERANCE, SVD_TOLERANCES};
 use crate::models::{fill_design_row, predict};
 
 /// Best fit for a single model kind.
@@ -28,6 +31,92 @@ pub struct ModelFit {
     pub taus: Vec<f64>,
     pub sse: f64,
     pub rmse: f64,
+    /// Every valid candidate in grid order (only when `FitOptions::collect_landscape`).
+    pub landscape: Option<Vec<TauCandidate>>,
+    /// Fallback relaxations the search needed (empty on the normal path).
+    pub warnings: Vec<String>,
+}
+
+/// One evaluated point of the tau search (for landscape diagnostics).
+#[derive(Debug, Clone)]
+pub struct TauCandidate {
+    pub taus: Vec<f64>,
+    pub sse: f64,
+    /// True for the candidate the search selected.
+    pub best: bool,
+}
+
+/// Search options for a single-model fit.
+#[derive(Debug, Clone, Default)]
+pub struct FitOptions {
+    /// Tie-break among candidates with exactly equal SSE.
+    pub tiebreak: TauTiebreak,
+    /// Draw a progress line on stderr while the grid is searched.
+    pub progress: bool,
+    /// Keep every evaluated candidate's `(taus, sse)` in `ModelFit::landscape`.
+    pub collect_landscape: bool,
+    /// Treat candidates with any `|beta|` above this as invalid.
+    pub beta_abs_max: Option<f64>,
+    /// Treat candidates whose front-end level `y(0)` (observation scale) falls
+    /// outside this range as invalid.
+    pub y0_range: Option<(f64, f64)>,
+    /// Scale the points are fitted on, to report `y(0)` on the observation scale.
+    pub y_transform: YTransform,
+}
+
+impl FitOptions {
+    /// Options for `config`; progress is only drawn when stderr is a terminal.
+    pub fn from_config(config: &FitConfig) -> Self {
+        Self {
+            tiebreak: config.tau_tiebreak,
+            progress: config.progress && std::io::stderr().is_terminal(),
+            collect_landscape: config.export_landscape.is_some(),
+            beta_abs_max: config.beta_abs_max,
+            y0_range: config.y0_range(),
+            y_transform: config.y_transform,
+        }
+    }
+}
+
+/// Number of progress redraws over a full grid search.
+const PROGRESS_REDRAWS: usize = 100;
+
+/// Shared candidate counter for the `--progress` line.
+///
+/// Workers only do an atomic increment; the line is redrawn at most
+/// `PROGRESS_REDRAWS` times. Results never depend on it.
+struct Progress {
+    label: &'static str,
+    total: usize,
+    step: usize,
+    done: AtomicUsize,
+}
+
+impl Progress {
+    fn new(label: &'static str, total: usize) -> Self {
+        Self {
+            label,
+            total,
+            step: (total / PROGRESS_REDRAWS).max(1),
+            done: AtomicUsize::new(0),
+        }
+    }
+
+    fn tick(&self) {
+        let k = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if k.is_multiple_of(self.step) || k == self.total {
+            eprint!(
+                "\r{} tau search: {:>3}% ({k}/{})",
+                self.label,
+                k * 100 / self.total,
+                self.total
+            );
+        }
+    }
+
+    fn finish(&self) {
+        eprintln!();
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -39,16 +128,22 @@ struct Candidate {
 }
 
 /// Fit a single model kind over a tau grid.
+///
+/// Stages: input validation, the standard SVD tolerance ladder, then one
+/// relaxed-tolerance retry (with a warning). There is no monotone-constrained
+/// stage and so no monotone -> unconstrained fallback: the search is
+/// unconstrained from the start.
 pub fn fit_model(
     model: ModelKind,
     points: &[BondPoint],
     tau_grid: &[Vec<f64>],
+    options: &FitOptions,
 ) -> Result<ModelFit, AppError> {
     if points.is_empty() {
         return Err(AppError::new(3, "No data points to fit."));
     }
     if tau_grid.is_empty() {
-        return Err(AppError::new(4, "Tau grid is empty."));
+        return Err(AppError::new(2, "Tau grid is empty (check --tau-min/--tau-max/--tau-steps-*)."));
     }
 
     // Extract raw arrays.
@@ -56,15 +151,172 @@ pub fn fit_model(
     let y: Vec<f64> = points.iter().map(|p| p.y_obs).collect();
     let w: Vec<f64> = points.iter().map(|p| p.weight).collect();
 
-    let p = model.beta_len();
+    // Stage 0: bad inputs fail every candidate alike, so reject them up front.
+    if tenors.iter().any(|t| !t.is_finite() || *t <= 0.0)
+        || y.iter().any(|v| !v.is_finite())
+        || w.iter().any(|v| !v.is_finite() || *v <= 0.0)
+    {
+        return Err(AppError::new(
+            4,
+            format!(
+                "No valid fit candidates for model {} (failed at input validation: non-finite or non-positive tenor, non-finite y, or non-positive weight).",
+                model.display_name()
+            ),
+        ));
+    }
+
+    // Stage 1: the standard SVD tolerance ladder.
+    let mut warnings = Vec::new();
+    let mut candidates = search_candidates(model, tau_grid, &tenors, &y, &w, &SVD_TOLERANCES, options.progress);
+
+    // Stage 2: every tuple was ill-conditioned; retry once with a looser cutoff.
+    if candidates.is_empty() {
+        candidates = search_candidates(model, tau_grid, &tenors, &y, &w, &[RELAXED_SVD_TOLERANCE], false);
+        if candidates.is_empty() {
+            return Err(AppError::new(
+                4,
+                format!(
+                    "No valid fit candidates for model {} (failed at relaxed SVD: all {} tau tuples ill-conditioned at tolerance {:e} and after relaxing to {:e}).",
+                    model.display_name(),
+                    tau_grid.len(),
+                    SVD_TOLERANCES[SVD_TOLERANCES.len() - 1],
+                    RELAXED_SVD_TOLERANCE
+                ),
+            ));
+        }
+        warnings.push(format!(
+            "{}: no tau tuple solved at SVD tolerance {:e}; relaxed to {:e} ({}/{} tuples accepted).",
+            model.display_name(),
+            SVD_TOLERANCES[SVD_TOLERANCES.len() - 1],
+            RELAXED_SVD_TOLERANCE,
+            candidates.len(),
+            tau_grid.len()
+        ));
+    }
     let n = tenors.len();
 
-    // Evaluate each tau tuple independently (parallel).
-    let candidates: Vec<Candidate> = tau_grid
+    // Stage 3: the optional bounds, each applied to the survivors of the
+    // last. Candidates are filtered after the search so a bound that moves
+    // the selection can be reported.
+    if let Some(max) = options.beta_abs_max {
+        let largest = |c: &Candidate| c.betas.iter().fold(0.0_f64, |m, b| m.max(b.abs()));
+        let bound = CandidateBound {
+            flag: format!("--beta-abs-max {max}"),
+            rule: format!("keeps every |beta| <= --beta-abs-max {max}"),
+            keep: &|c| largest(c) <= max,
+            describe: &|c| format!("max |beta| {:.4}", largest(c)),
+        };
+        candidates = apply_bound(model, candidates, &bound, options.tiebreak, &mut warnings);
+    }
+    if let Some((lo, hi)) = options.y0_range {
+        let y0 = |c: &Candidate| options.y_transform.invert(front_end_level(&c.betas));
+        let bound = CandidateBound {
+            flag: format!("--y0-min/--y0-max [{lo}, {hi}]"),
+            rule: format!("keeps y(0) within --y0-min/--y0-max [{lo}, {hi}]"),
+            keep: &|c| (lo..=hi).contains(&y0(c)),
+            describe: &|c| format!("y(0) {:.4}", y0(c)),
+        };
+        candidates = apply_bound(model, candidates, &bound, options.tiebreak, &mut warnings);
+    }
+
+    let best = pick_best(&candidates, options.tiebreak);
+
+    let landscape = options.collect_landscape.then(|| {
+        let mut all: Vec<&Candidate> = candidates.iter().collect();
+        all.sort_by_key(|c| c.idx);
+        all.into_iter()
+            .map(|c| TauCandidate {
+                taus: c.taus.clone(),
+                sse: c.sse,
+                best: c.idx == best.idx,
+            })
+            .collect()
+    });
+
+    let rmse = (best.sse / n as f64).sqrt();
+    Ok(ModelFit {
+        model,
+        betas: best.betas.clone(),
+        taus: best.taus.clone(),
+        sse: best.sse,
+        rmse,
+        landscape,
+        warnings,
+    })
+}
+
+/// A post-search rule on candidates (`--beta-abs-max`, `--y0-min/--y0-max`).
+struct CandidateBound<'a> {
+    /// The flag as the user gave it, for the rejection warning.
+    flag: String,
+    /// What a passing candidate does, for the nothing-qualifies warning.
+    rule: String,
+    keep: &'a dyn Fn(&Candidate) -> bool,
+    /// The offending quantity of a candidate.
+    describe: &'a dyn Fn(&Candidate) -> String,
+}
+
+/// Keep the candidates `bound` accepts, warning when that moves the
+/// selection; when none qualifies, keep them all and warn instead.
+fn apply_bound(
+    model: ModelKind,
+    candidates: Vec<Candidate>,
+    bound: &CandidateBound<'_>,
+    tiebreak: TauTiebreak,
+    warnings: &mut Vec<String>,
+) -> Vec<Candidate> {
+    let unbounded = pick_best(&candidates, tiebreak).clone();
+    let total = candidates.len();
+    let bounded: Vec<Candidate> = candidates.iter().filter(|c| (bound.keep)(c)).cloned().collect();
+    if bounded.is_empty() {
+        warnings.push(format!(
+            "{}: no tau tuple {}; using the unbounded fit ({}).",
+            model.display_name(),
+            bound.rule,
+            (bound.describe)(&unbounded)
+        ));
+        return candidates;
+    }
+    if pick_best(&bounded, tiebreak).idx != unbounded.idx {
+        warnings.push(format!(
+            "{}: {} rejected {} of {total} tau tuple(s), including the lowest-SSE one (taus {:?}, {}).",
+            model.display_name(),
+            bound.flag,
+            total - bounded.len(),
+            unbounded.taus,
+            (bound.describe)(&unbounded)
+        ));
+    }
+    bounded
+}
+
+/// Fitted level as `t -> 0` on the fitting scale: every loading but the
+/// level and slope terms vanishes there, leaving `β0 + β1`.
+fn front_end_level(betas: &[f64]) -> f64 {
+    betas[0] + betas[1]
+}
+
+/// Evaluate every tau tuple (in parallel) under one SVD tolerance ladder,
+/// keeping the tuples that solve.
+fn search_candidates(
+    model: ModelKind,
+    tau_grid: &[Vec<f64>],
+    tenors: &[f64],
+    y: &[f64],
+    w: &[f64],
+    tolerances: &[f64],
+    show_progress: bool,
+) -> Vec<Candidate> {
+    let progress = show_progress.then(|| Progress::new(model.display_name(), tau_grid.len()));
+
+    let candidates = tau_grid
         .par_iter()
         .enumerate()
         .filter_map(|(idx, taus)| {
-            evaluate_candidate(model, taus, &tenors, &y, &w, n, p)
+            if let Some(progress) = &progress {
+                progress.tick();
+            }
+            evaluate_candidate(model, taus, tenors, y, w, tolerances)
                 .map(|(betas, sse)| Candidate {
                     idx,
                     taus: taus.clone(),
@@ -74,29 +326,42 @@ pub fn fit_model(
         })
         .collect();
 
-    if candidates.is_empty() {
-        return Err(AppError::new(
-            4,
-            format!("No valid fit candidates for model {}.", model.display_name()),
-        ));
+    if let Some(progress) = &progress {
+        progress.finish();
     }
+    candidates
+}
+
+/// Deterministic selection: pick the minimum SSE; break exact ties per `tiebreak`
+/// (falling back to the original grid index, so the result never depends on
+/// evaluation order).
+fn pick_best(candidates: &[Candidate], tiebreak: TauTiebreak) -> &Candidate {
+    let tie_wins = |c: &Candidate, best: &Candidate| -> bool {
+        let by_taus = match tiebreak {
+            TauTiebreak::LowIndex => std::cmp::Ordering::Equal,
+            TauTiebreak::Smallest => cmp_taus(&c.taus, &best.taus),
+            TauTiebreak::Largest => cmp_taus(&best.taus, &c.taus),
+        };
+        by_taus.then(c.idx.cmp(&best.idx)) == std::cmp::Ordering::Less
+    };
 
-    // Deterministic selection: pick the minimum SSE; break ties by original grid index.
     let mut best = &candidates[0];
     for c in &candidates[1..] {
-        if c.sse < best.sse || (c.sse == best.sse && c.idx < best.idx) {
+        if c.sse < best.sse || (c.sse == best.sse && tie_wins(c, best)) {
             best = c;
         }
     }
+    best
+}
 
-    let rmse = (best.sse / n as f64).sqrt();
-    Ok(ModelFit {
-        model,
-        betas: best.betas.clone(),
-        taus: best.taus.clone(),
-        sse: best.sse,
-        rmse,
-    })
+fn cmp_taus(a: &[f64], b: &[f64]) -> std::cmp::Ordering {
+    for (x, y) in a.iter().zip(b) {
+        match x.total_cmp(y) {
+            std::cmp::Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    a.len().cmp(&b.len())
 }
 
 fn evaluate_candidate(
@@ -105,19 +370,10 @@ fn evaluate_candidate(
     tenors: &[f64],
     y: &[f64],
     w: &[f64],
-    n: usize,
-    p: usize,
+    tolerances: &[f64],
 ) -> Option<(Vec<f64>, f64)> {
-    // Validate inputs - skip candidates with invalid data.
-    if tenors.iter().any(|t| !t.is_finite() || *t <= 0.0) {
-        return None;
-    }
-    if y.iter().any(|v| !v.is_finite()) {
-        return None;
-    }
-    if w.iter().any(|v| !v.is_finite() || *v <= 0.0) {
-        return None;
-    }
+    let n = tenors.len();
+    let p = model.beta_len();
 
     // Build weighted design matrix X_w and weighted observation vector y_w.
     let mut xw = DMatrix::<f64>::zeros(n, p);
@@ -134,7 +390,7 @@ fn evaluate_candidate(
         yw[i] = y[i] * sw;
     }
 
-    let beta = solve_least_squares(&xw, &yw)?;
+    let beta = solve_least_squares_with(&xw, &yw, tolerances)?;
     let betas: Vec<f64> = beta.iter().copied().collect();
 
     // Compute weighted SSE using the unweighted model prediction.
@@ -182,11 +438,26 @@ mod tests {
             .collect();
 
         let grid = vec![vec![2.0]];
-        let fit = fit_model(ModelKind::Ns, &points, &grid).unwrap();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
         assert!(fit.sse.is_finite());
         assert!(fit.rmse.is_finite());
     }
 
+    #[test]
+    fn fit_model_needs_no_fallback_and_names_the_failing_stage() {
+        let (points, grid) = known_tau_points();
+        // The happy path needs no fallback.
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
+        assert!(fit.warnings.is_empty(), "{:?}", fit.warnings);
+
+        // A bad weight fails every candidate alike; the error names the stage.
+        let mut bad = points.clone();
+        bad[2].weight = 0.0;
+        let err = fit_model(ModelKind::Ns, &bad, &grid, &FitOptions::default()).unwrap_err();
+        assert_eq!(err.exit_code(), 4);
+        assert!(err.to_string().contains("failed at input validation"), "{err}");
+    }
+
     #[test]
     fn fit_model_selects_correct_tau_from_grid() {
         // Synthetic NS data with a known tau; ensure the grid search picks it.
@@ -211,7 +482,7 @@ mod tests {
             .collect();
 
         let grid = vec![vec![1.0], vec![2.0], vec![4.0]];
-        let fit = fit_model(ModelKind::Ns, &points, &grid).unwrap();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
 
         assert_eq!(fit.taus.len(), 1);
         assert!((fit.taus[0] - 2.0).abs() < 1e-12);
@@ -219,4 +490,113 @@ mod tests {
             assert!((a - b).abs() < 1e-9);
         }
     }
+
+    /// The `fit_model_selects_correct_tau_from_grid` data: NS with tau 2, and a
+    /// grid that contains it.
+    fn known_tau_points() -> (Vec<BondPoint>, Vec<Vec<f64>>) {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let true_betas = [120.0, -30.0, 40.0];
+        let points = (0..20)
+            .map(|i| {
+                let t = 0.5 + i as f64 * 0.5;
+                BondPoint {
+                    id: format!("B{i}"),
+                    asof_date: asof,
+                    maturity_date: asof,
+                    tenor: t,
+                    y_obs: predict(ModelKind::Ns, t, &true_betas, &[2.0]),
+                    weight: 1.0,
+                    meta: BondMeta::default(),
+                    extras: BondExtras::default(),
+                }
+            })
+            .collect();
+        (points, vec![vec![1.0], vec![2.0], vec![4.0]])
+    }
+
+    #[test]
+    fn progress_does_not_change_the_fit() {
+        let (points, grid) = known_tau_points();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
+        let with_progress = FitOptions { progress: true, ..FitOptions::default() };
+        let instrumented = fit_model(ModelKind::Ns, &points, &grid, &with_progress).unwrap();
+        assert_eq!(instrumented.taus, fit.taus);
+        assert_eq!(instrumented.betas, fit.betas);
+        assert_eq!(instrumented.sse, fit.sse);
+    }
+
+    #[test]
+    fn landscape_lists_every_candidate_and_marks_the_winner() {
+        let (points, grid) = known_tau_points();
+        assert!(fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap().landscape.is_none());
+
+        let collecting = FitOptions { collect_landscape: true, ..FitOptions::default() };
+        let landscape = fit_model(ModelKind::Ns, &points, &grid, &collecting).unwrap().landscape.unwrap();
+        let taus: Vec<f64> = landscape.iter().map(|c| c.taus[0]).collect();
+        assert_eq!(taus, vec![1.0, 2.0, 4.0]);
+        let winners: Vec<bool> = landscape.iter().map(|c| c.best).collect();
+        assert_eq!(winners, vec![false, true, false]);
+    }
+
+    #[test]
+    fn beta_bound_moves_the_search_or_falls_back_with_a_warning() {
+        let (points, grid) = known_tau_points();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
+
+        // A beta bound that excludes the exact tau moves the search, with a warning.
+        let max_beta = |tau: f64| {
+            let single = fit_model(ModelKind::Ns, &points, &[vec![tau]], &FitOptions::default()).unwrap();
+            single.betas.iter().fold(0.0_f64, |m, b| m.max(b.abs()))
+        };
+        let bound = max_beta(2.0) * (1.0 - 1e-9);
+        assert!(max_beta(1.0) <= bound || max_beta(4.0) <= bound);
+        let bounded = FitOptions { beta_abs_max: Some(bound), ..FitOptions::default() };
+        let moved = fit_model(ModelKind::Ns, &points, &grid, &bounded).unwrap();
+        assert_ne!(moved.taus, fit.taus);
+        assert!(moved.betas.iter().all(|b| b.abs() <= bound));
+        assert!(moved.warnings[0].contains("including the lowest-SSE one (taus [2.0]"), "{:?}", moved.warnings);
+
+        // Nothing qualifies: keep the unbounded fit and say so.
+        let strict = FitOptions { beta_abs_max: Some(1e-3), ..FitOptions::default() };
+        let fallback = fit_model(ModelKind::Ns, &points, &grid, &strict).unwrap();
+        assert_eq!(fallback.taus, fit.taus);
+        assert!(fallback.warnings[0].contains("using the unbounded fit"), "{:?}", fallback.warnings);
+    }
+
+    #[test]
+    fn y0_bound_rejects_like_the_beta_bound_and_composes_with_it() {
+        let (points, grid) = known_tau_points();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
+
+        let y0 = |tau: f64| front_end_level(&fit_model(ModelKind::Ns, &points, &[vec![tau]], &FitOptions::default()).unwrap().betas);
+        let (lo, hi) = (y0(2.0).min(y0(4.0)), y0(2.0).max(y0(4.0)));
+        let excluding_exact = if y0(2.0) < y0(4.0) { (hi - 1e-9, f64::INFINITY) } else { (f64::NEG_INFINITY, lo + 1e-9) };
+        let level = FitOptions { y0_range: Some(excluding_exact), ..FitOptions::default() };
+        let moved = fit_model(ModelKind::Ns, &points, &grid, &level).unwrap();
+        assert_ne!(moved.taus, fit.taus);
+        assert!((excluding_exact.0..=excluding_exact.1).contains(&front_end_level(&moved.betas)));
+        assert!(moved.warnings[0].contains("--y0-min/--y0-max ["), "{:?}", moved.warnings);
+
+        let both = FitOptions { beta_abs_max: Some(1e-3), y0_range: Some((-1e-3, 1e-3)), ..FitOptions::default() };
+        let fallback = fit_model(ModelKind::Ns, &points, &grid, &both).unwrap();
+        assert_eq!(fallback.taus, fit.taus);
+        assert_eq!(fallback.warnings.len(), 2, "{:?}", fallback.warnings);
+        assert!(fallback.warnings[1].contains("no tau tuple keeps y(0) within"), "{:?}", fallback.warnings);
+    }
+
+    #[test]
+    fn pick_best_tiebreak_rules() {
+        let cand = |idx: usize, tau: f64, sse: f64| Candidate {
+            idx,
+            taus: vec![tau],
+            betas: vec![],
+            sse,
+        };
+        // Evaluation order is not grid order (as with par_iter collection).
+        let candidates = vec![cand(2, 4.0, 1.0), cand(0, 1.0, 1.0), cand(1, 2.0, 0.5), cand(3, 8.0, 0.5)];
+
+        assert_eq!(pick_best(&candidates, TauTiebreak::LowIndex).idx, 1);
+        assert_eq!(pick_best(&candidates, TauTiebreak::Smallest).taus, vec![2.0]);
+        assert_eq!(pick_best(&candidates, TauTiebreak::Largest).taus, vec![8.0]);
+    }
 }
@@ -0,0 +1,130 @@
+//! Leave-one-out (jackknife) influence diagnostics.
+//!
+//! For each bond we refit the *selected* model kind on the sample with that bond
+//! removed, using the same tau grid and search options as the main fit, and
+//! measure how much the fitted value at the bond's own tenor moves (DFFIT-style):
+//!
+//! ```text
+//! dffit_i = y_fit(t_i) - y_fit_(-i)(t_i)
+//! ```
+//!
+//! Refits run on the model's `y_transform` scale; `dffit` is on the y scale.
+//!
+//! This costs one full grid search per bond, so it is opt-in and the per-bond
+//! refits run in parallel.
+
+use rayon::prelude::*;
+
+use crate::domain::{BondPoint, FitConfig, FitResult};
+use crate::error::AppError;
+use crate::fit::fitter::{fit_model, FitOptions};
+use crate::fit::selection::{tau_grid_for, transform_points};
+use crate::models::predict;
+
+/// Influence of a single bond on the fitted curve.
+#[derive(Debug, Clone)]
+pub struct BondInfluence {
+    pub id: String,
+    pub tenor: f64,
+    /// Full-sample fit minus leave-one-out fit at the bond's tenor.
+    pub dffit: f64,
+}
+
+/// Compute leave-one-out influence for every bond, sorted by `|dffit|` descending.
+pub fn jackknife_influence(
+    points: &[BondPoint],
+    best: &FitResult,
+    config: &FitConfig,
+) -> Result<Vec<BondInfluence>, AppError> {
+    let kind = best.model.name;
+    let scale = best.model.y_transform;
+    let tau_grid = tau_grid_for(kind, config)?;
+    let fit_points = transform_points(points, scale)?;
+    let fit_points = fit_points.as_deref().unwrap_or(points);
+    // One refit per bond: a progress line per refit would just be noise.
+    let options = FitOptions {
+        progress: false,
+        collect_landscape: false,
+        ..FitOptions::from_config(config)
+    };
+
+    let mut out = (0..points.len())
+        .into_par_iter()
+        .map(|i| {
+            let subset: Vec<BondPoint> = fit_points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, p)| p.clone())
+                .collect();
+            let loo = fit_model(kind, &subset, &tau_grid, &options)?;
+
+            let t = points[i].tenor;
+            let full = best.model.eval(t);
+            let without = scale.invert(predict(kind, t, &loo.betas, &loo.taus));
+            Ok(BondInfluence {
+                id: points[i].id.clone(),
+                tenor: t,
+                dffit: full - without,
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    out.sort_by(|a, b| b.dffit.abs().total_cmp(&a.dffit.abs()));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BondExtras, BondMeta, CurveModel, FitQuality, ModelKind, ModelSpec};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn outlier_is_most_influential() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let betas = [100.0, -20.0, 30.0];
+        let taus = [2.0];
+        let points: Vec<BondPoint> = (0..60)
+            .map(|i| {
+                let t = 0.5 + i as f64 * 0.25;
+                let bump = if i == 20 { 80.0 } else { 0.0 };
+                BondPoint {
+                    id: format!("B{i}"),
+                    asof_date: asof,
+                    maturity_date: asof,
+                    tenor: t,
+                    y_obs: predict(ModelKind::Ns, t, &betas, &taus) + bump,
+                    weight: 1.0,
+                    meta: BondMeta::default(),
+                    extras: BondExtras::default(),
+                }
+            })
+            .collect();
+
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.model_spec = ModelSpec::Ns;
+        // Near-fixed tau so the comparison isolates the outlier's pull on the betas.
+        config.tau_min = 1.99;
+        config.tau_max = 2.0;
+        config.tau_steps_ns = 2;
+        let grid = tau_grid_for(ModelKind::Ns, &config).unwrap();
+        let fit = fit_model(ModelKind::Ns, &points, &grid, &FitOptions::default()).unwrap();
+        let best = FitResult {
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: fit.betas,
+                taus: fit.taus,
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: fit.sse, rmse: fit.rmse, bic: 0.0, n: points.len() },
+        };
+
+        let influence = jackknife_influence(&points, &best, &config).unwrap();
+        assert_eq!(influence.len(), points.len());
+        assert_eq!(influence[0].id, "B20");
+        assert!(influence[0].dffit > 0.0);
+    }
+}
@@ -5,12 +5,17 @@
 //! - generate tau grids for NS / NSS / NSSC
 //! - evaluate each candidate tau tuple (parallel)
 //! - select best model using BIC + guardrails
+//! - optionally splice separate short/long fits at a knot tenor
+//! - optional leave-one-out influence diagnostics
 
 pub mod fitter;
+pub mod influence;
 pub mod selection;
+pub mod splice;
 pub mod tau_grid;
 
 pub use fitter::*;
+pub use influence::*;
 pub use selection::*;
 pub use tau_grid::*;
 
@@ -1,23 +1,26 @@
-//! Model selection (NS vs NSS vs NSSC) using BIC with guardrails.
+//! Model selection (NS vs NSS-shared vs NSS vs NSSC) using BIC with guardrails.
 //!
 //! The tool fits each enabled model and computes:
 //! - SSE / RMSE
 //! - BIC = n * ln(SSE/n) + k * ln(n)
 //!
 //! Selection rules (per spec):
-//! 1. Exclude underdetermined models: require `n >= k + 5`
+//! 1. Exclude underdetermined models: require `n >= k + 5`, and models beyond
+//!    NS when the observed tenors span less than `--min-coverage` of the
+//!    configured `[tenor_min, tenor_max]` range
 //! 2. Choose the model with minimum BIC
-//! 3. If delta_BIC < 2 between the best and a simpler model, pick the simpler model
+//! 3. If delta_BIC <= margin (`--bic-margin`, default 2) between the best and a
+//!    simpler model, pick the simpler model; a margin of 0 is pure minimum BIC
 
-use crate::domain::{BondPoint, CurveModel, FitConfig, FitResult, FitQuality, ModelKind, ModelSpec};
+use crate::domain::{BondPoint, CurveModel, FitConfig, FitResult, FitQuality, ModelKind, ModelSpec, YTransform};
 use crate::error::AppError;
-use crate::fit::fitter::{fit_model, ModelFit};
-use crate::fit::tau_grid::{tau_grid_ns, tau_grid_nss, tau_grid_nssc};
+use crate::fit::fitter::{fit_model, FitOptions, ModelFit, TauCandidate};
+use crate::fit::tau_grid::{tau_grid_ns, tau_grid_nss, tau_grid_nss_shared, tau_grid_nssc};
 use crate::io::ingest::InputSpec;
-use crate::models::predict;
 
-/// Minimum number of extra observations beyond parameter count.
-const MIN_N_BUFFER: usize = 5;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
 
 /// Output of fitting + selection.
 #[derive(Debug, Clone)]
@@ -27,40 +30,106 @@ pub struct FitSelection {
     pub fits: Vec<FitResult>,
     /// Any models that were skipped and why (for diagnostics).
     pub skipped: Vec<(ModelKind, String)>,
+    /// Step-by-step account of how `best` was chosen (see `--explain-selection`).
+    pub explanation: Vec<String>,
+    /// Tau-search landscape of the selected model (only with `--export-landscape`).
+    pub landscape: Option<Vec<TauCandidate>>,
+    /// Fitter fallbacks (e.g. a relaxed SVD tolerance) across all attempted models.
+    pub warnings: Vec<String>,
+    /// Tau tuples searched per fitted model (grid size after the ordering
+    /// constraints, not `steps^d`).
+    pub candidates: Vec<(ModelKind, usize)>,
+    /// Wall time of each fitted model's tau search (`fit_model`, parallel
+    /// grid included).
+    pub durations: Vec<(ModelKind, Duration)>,
 }
 
 pub fn fit_and_select(points: &[BondPoint], _input_spec: &InputSpec, config: &FitConfig) -> Result<FitSelection, AppError> {
+    if !(config.bic_margin.is_finite() && config.bic_margin >= 0.0) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid --bic-margin {}: must be a finite number >= 0.", config.bic_margin),
+        ));
+    }
+    if let Some(max) = config.beta_abs_max.filter(|m| m.is_nan() || *m <= 0.0) {
+        return Err(AppError::new(2, format!("Invalid --beta-abs-max {max}: must be > 0.")));
+    }
+    if let Some((lo, hi)) = config.y0_range().filter(|(lo, hi)| lo.is_nan() || hi.is_nan() || lo >= hi) {
+        return Err(AppError::new(2, format!("Invalid --y0-min/--y0-max [{lo}, {hi}]: the minimum must be below the maximum.")));
+    }
     let n = points.len();
+    if n == 0 {
+        return Err(AppError::new(
+            3,
+            format!(
+                "No bonds to fit: 0 points remain after filtering (tenor bounds [{:.2}, {:.2}]y).",
+                config.tenor_min, config.tenor_max
+            ),
+        ));
+    }
+
+    // Fit on the transformed scale; `CurveModel::eval` maps predictions back.
+    let transformed = transform_points(points, config.y_transform)?;
+    let points = transformed.as_deref().unwrap_or(points);
 
     // Determine which model kinds to attempt.
     let model_kinds: Vec<ModelKind> = match config.model_spec {
         ModelSpec::Ns => vec![ModelKind::Ns],
+        ModelSpec::NssShared => vec![ModelKind::NssShared],
         ModelSpec::Nss => vec![ModelKind::Nss],
         ModelSpec::Nssc => vec![ModelKind::Nssc],
-        ModelSpec::All | ModelSpec::Auto => vec![ModelKind::Ns, ModelKind::Nss, ModelKind::Nssc],
+        ModelSpec::All | ModelSpec::Auto => {
+            vec![ModelKind::Ns, ModelKind::NssShared, ModelKind::Nss, ModelKind::Nssc]
+        }
     };
 
+    let options = FitOptions::from_config(config);
     let mut fits = Vec::new();
     let mut skipped = Vec::new();
+    let mut explanation = Vec::new();
+    let mut landscapes = Vec::new();
+    let mut warnings = Vec::new();
+    let mut candidates = Vec::new();
+    let mut durations = Vec::new();
 
+    if !(0.0..=1.0).contains(&config.min_coverage) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid --min-coverage {}: must be between 0 and 1.", config.min_coverage),
+        ));
+    }
+    let coverage = tenor_coverage(points, config.tenor_min, config.tenor_max);
+
+    let buffer = config.min_n_buffer;
     for kind in model_kinds {
         let k = kind.param_count();
-        if n < k + MIN_N_BUFFER {
-            skipped.push((
-                kind,
-                format!("Underdetermined: n={n} < k+{MIN_N_BUFFER}={}", k + MIN_N_BUFFER),
-            ));
+        if n < k + buffer {
+            let reason = format!("Underdetermined: n={n} < k+{buffer}={}", k + buffer);
+            explanation.push(format!("Guardrail: {} skipped ({reason}).", kind.display_name()));
+            skipped.push((kind, reason));
+            continue;
+        }
+        if kind != ModelKind::Ns && coverage < config.min_coverage {
+            let reason = format!(
+                "Low tenor coverage: observed tenors span {:.1}% of [{:.2}, {:.2}]y < --min-coverage {:.1}%",
+                coverage * 100.0,
+                config.tenor_min,
+                config.tenor_max,
+                config.min_coverage * 100.0
+            );
+            explanation.push(format!("Guardrail: {} skipped ({reason}).", kind.display_name()));
+            skipped.push((kind, reason));
             continue;
         }
 
-        let tau_grid = match kind {
-            ModelKind::Ns => tau_grid_ns(config.tau_min, config.tau_max, config.tau_steps_ns)?,
-            ModelKind::Nss => tau_grid_nss(config.tau_min, config.tau_max, config.tau_steps_nss)?,
-            ModelKind::Nssc => tau_grid_nssc(config.tau_min, config.tau_max, config.tau_steps_nssc)?,
-        };
-
-        let fit = fit_model(kind, points, &tau_grid)?;
-        fits.push(to_fit_result(fit, n, k));
+        let tau_grid = tau_grid_for(kind, config)?;
+        candidates.push((kind, tau_grid.len()));
+        let started = Instant::now();
+        let mut fit = fit_model(kind, points, &tau_grid, &options)?;
+        durations.push((kind, started.elapsed()));
+        landscapes.push((kind, fit.landscape.take()));
+        warnings.append(&mut fit.warnings);
+        fits.push(to_fit_result(fit, n, k, config.y_transform));
     }
 
     if fits.is_empty() {
@@ -71,20 +140,88 @@ pub fn fit_and_select(points: &[BondPoint], _input_spec: &InputSpec, config: &Fi
     }
 
     // If the user requested a single model, it's already the best.
-    let best = if matches!(config.model_spec, ModelSpec::Ns | ModelSpec::Nss | ModelSpec::Nssc) {
+    let best = if matches!(config.model_spec, ModelSpec::Ns | ModelSpec::NssShared | ModelSpec::Nss | ModelSpec::Nssc) {
+        explanation.push(format!(
+            "Single model requested (--model {}); no BIC selection applied.",
+            fits[0].model.display_name
+        ));
         fits[0].clone()
     } else {
-        select_by_bic(&fits)
+        let (best, steps) = select_by_bic(&fits, config.bic_margin);
+        explanation.extend(steps);
+        best
     };
 
+    let landscape = landscapes
+        .into_iter()
+        .find(|(kind, _)| *kind == best.model.name)
+        .and_then(|(_, landscape)| landscape);
+
     Ok(FitSelection {
         best,
         fits,
         skipped,
+        explanation,
+        landscape,
+        warnings,
+        candidates,
+        durations,
     })
 }
 
-fn to_fit_result(fit: ModelFit, n: usize, k: usize) -> FitResult {
+/// Span of the observed tenors as a fraction of `[tenor_min, tenor_max]`,
+/// capped at 1. A degenerate configured range counts as fully covered.
+pub fn tenor_coverage(points: &[BondPoint], tenor_min: f64, tenor_max: f64) -> f64 {
+    let range = tenor_max - tenor_min;
+    if range.is_nan() || range <= 0.0 || points.is_empty() {
+        return 1.0;
+    }
+    let (lo, hi) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.tenor), hi.max(p.tenor)));
+    ((hi - lo) / range).clamp(0.0, 1.0)
+}
+
+/// Tau grid searched for `kind` under `config`.
+pub fn tau_grid_for(kind: ModelKind, config: &FitConfig) -> Result<Vec<Vec<f64>>, AppError> {
+    match kind {
+        ModelKind::Ns => tau_grid_ns(config.tau_min, config.tau_max, config.tau_steps_ns),
+        ModelKind::NssShared => tau_grid_nss_shared(config.tau_min, config.tau_max, config.tau_steps_ns),
+        ModelKind::Nss => tau_grid_nss(config.tau_min, config.tau_max, config.tau_steps_nss),
+        ModelKind::Nssc => tau_grid_nssc(config.tau_min, config.tau_max, config.tau_steps_nssc),
+    }
+}
+
+/// Points with `y_obs` mapped onto the fitting scale, or `None` when the
+/// transform is the identity (the caller keeps using the original slice).
+///
+/// `log` needs every observation to be positive; otherwise the fit fails
+/// rather than silently flooring spreads.
+pub(crate) fn transform_points(points: &[BondPoint], transform: YTransform) -> Result<Option<Vec<BondPoint>>, AppError> {
+    if transform.is_none() {
+        return Ok(None);
+    }
+    let bad: Vec<&BondPoint> = points.iter().filter(|p| !(p.y_obs.is_finite() && p.y_obs > 0.0)).collect();
+    if let Some(first) = bad.first() {
+        return Err(AppError::new(
+            4,
+            format!(
+                "--y-transform log needs positive observations: {} bond(s) are <= 0 or non-finite (first: {} y={}).",
+                bad.len(),
+                first.id,
+                first.y_obs
+            ),
+        ));
+    }
+    Ok(Some(
+        points
+            .iter()
+            .map(|p| BondPoint { y_obs: transform.apply(p.y_obs), ..p.clone() })
+            .collect(),
+    ))
+}
+
+fn to_fit_result(fit: ModelFit, n: usize, k: usize, y_transform: YTransform) -> FitResult {
     let bic = bic(n, fit.sse, k);
 
     FitResult {
@@ -93,6 +230,8 @@ fn to_fit_result(fit: ModelFit, n: usize, k: usize) -> FitResult {
             display_name: fit.model.display_name().to_string(),
             betas: fit.betas,
             taus: fit.taus,
+            y_transform,
+            splice: None,
         },
         quality: FitQuality {
             sse: fit.sse,
@@ -103,13 +242,32 @@ fn to_fit_result(fit: ModelFit, n: usize, k: usize) -> FitResult {
     }
 }
 
-fn bic(n: usize, sse: f64, k: usize) -> f64 {
+pub(crate) fn bic(n: usize, sse: f64, k: usize) -> f64 {
     let n_f = n as f64;
     let sse_per = (sse / n_f).max(1e-12);
     n_f * sse_per.ln() + (k as f64) * n_f.ln()
 }
 
-fn select_by_bic(fits: &[FitResult]) -> FitResult {
+/// Apply the BIC selection rule, returning the chosen fit and a step-by-step trace.
+///
+/// A simpler model is preferred when its BIC is within `margin` points of the
+/// minimum; with `margin = 0` only exact ties go to the simpler model, which is
+/// plain minimum-BIC selection since the minimum scan keeps the first (simplest).
+///
+/// The trace is produced inline by the same code that makes the decision, so it
+/// cannot drift from the actual rule.
+fn select_by_bic(fits: &[FitResult], margin: f64) -> (FitResult, Vec<String>) {
+    let mut steps = Vec::new();
+    for f in fits {
+        steps.push(format!(
+            "Criterion: {} BIC={:.3} (k={}, n={}).",
+            f.model.display_name,
+            f.quality.bic,
+            f.model.name.param_count(),
+            f.quality.n
+        ));
+    }
+
     // Find minimum BIC.
     let mut best = &fits[0];
     for f in &fits[1..] {
@@ -119,57 +277,245 @@ fn select_by_bic(fits: &[FitResult]) -> FitResult {
     }
 
     let best_bic = best.quality.bic;
+    steps.push(format!("Minimum BIC: {} ({best_bic:.3}).", best.model.display_name));
 
-    // Prefer simplicity if within 2 BIC points.
-    let order = [ModelKind::Ns, ModelKind::Nss, ModelKind::Nssc];
+    // Prefer simplicity if within the margin, scanning from the simplest model.
+    let order = [ModelKind::Ns, ModelKind::NssShared, ModelKind::Nss, ModelKind::Nssc];
     for kind in order {
         if let Some(f) = fits.iter().find(|f| f.model.name == kind) {
-            if f.quality.bic <= best_bic + 2.0 {
-                return f.clone();
+            let delta = f.quality.bic - best_bic;
+            if f.quality.bic <= best_bic + margin {
+                steps.push(format!(
+                    "Threshold: {} ΔBIC={delta:.3} <= {margin} (within).",
+                    f.model.display_name
+                ));
+                let rule = if f.model.name == best.model.name {
+                    "minimum BIC (no simpler model within threshold)".to_string()
+                } else {
+                    format!(
+                        "simplicity ({} preferred over {} within {margin} BIC points)",
+                        f.model.display_name, best.model.display_name
+                    )
+                };
+                steps.push(format!("Rule fired: {rule}; selected {}.", f.model.display_name));
+                return (f.clone(), steps);
             }
+            steps.push(format!(
+                "Threshold: {} ΔBIC={delta:.3} > {margin} (not within).",
+                f.model.display_name
+            ));
         }
     }
 
-    best.clone()
+    steps.push(format!("Rule fired: minimum BIC; selected {}.", best.model.display_name));
+    (best.clone(), steps)
+}
+
+/// Schwarz (BIC) weights: relative support for each attempted model.
+///
+/// `w_i = exp(-0.5 * ΔBIC_i) / Σ_j exp(-0.5 * ΔBIC_j)`, where `ΔBIC_i` is measured
+/// from the minimum BIC. Only the fits passed in (i.e. models that survived the
+/// guardrails) take part in the normalization, so the weights sum to 1.
+pub fn bic_weights(fits: &[FitResult]) -> Vec<f64> {
+    let min_bic = fits
+        .iter()
+        .map(|f| f.quality.bic)
+        .fold(f64::INFINITY, f64::min);
+    if !min_bic.is_finite() {
+        return vec![0.0; fits.len()];
+    }
+
+    let raw: Vec<f64> = fits
+        .iter()
+        .map(|f| (-0.5 * (f.quality.bic - min_bic)).exp())
+        .collect();
+    let total: f64 = raw.iter().sum();
+    raw.into_iter().map(|w| w / total).collect()
+}
+
+/// One row of the `--compare-models` table.
+///
+/// Metrics are `None` for models skipped by the guardrails (`skip_reason` says
+/// why). MAE and R² use the same weights as SSE; AIC = n * ln(SSE/n) + 2k.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelMetrics {
+    pub model: ModelKind,
+    pub n: usize,
+    pub params: usize,
+    pub sse: Option<f64>,
+    pub rmse: Option<f64>,
+    pub mae: Option<f64>,
+    pub bic: Option<f64>,
+    pub aic: Option<f64>,
+    pub r2: Option<f64>,
+    pub selected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+}
+
+/// Side-by-side metrics for every attempted and skipped model, in `ModelKind` order.
+pub fn compare_models(points: &[BondPoint], selection: &FitSelection) -> Vec<ModelMetrics> {
+    let n = points.len();
+    let w_sum: f64 = points.iter().map(|p| p.weight).sum();
+    // SSE is on the fitting scale, so R² compares it with SST on that scale.
+    let scale = selection.best.model.y_transform;
+    let y_mean = points.iter().map(|p| p.weight * scale.apply(p.y_obs)).sum::<f64>() / w_sum;
+    let sst: f64 = points.iter().map(|p| p.weight * (scale.apply(p.y_obs) - y_mean).powi(2)).sum();
+
+    let mut rows: Vec<ModelMetrics> = selection
+        .fits
+        .iter()
+        .map(|fit| {
+            let kind = fit.model.name;
+            let abs_err: f64 = points
+                .iter()
+                .map(|p| p.weight * (p.y_obs - fit.model.eval(p.tenor)).abs())
+                .sum();
+            let n_f = n as f64;
+            let aic = n_f * (fit.quality.sse / n_f).max(1e-12).ln() + 2.0 * kind.param_count() as f64;
+            ModelMetrics {
+                model: kind,
+                n,
+                params: kind.param_count(),
+                sse: Some(fit.quality.sse),
+                rmse: Some(fit.quality.rmse),
+                mae: Some(abs_err / w_sum),
+                bic: Some(fit.quality.bic),
+                aic: Some(aic),
+                r2: (sst > 0.0).then(|| 1.0 - fit.quality.sse / sst),
+                selected: kind == selection.best.model.name,
+                skip_reason: None,
+            }
+        })
+        .collect();
+    rows.extend(selection.skipped.iter().map(|(kind, reason)| ModelMetrics {
+        model: *kind,
+        n,
+        params: kind.param_count(),
+        sse: None,
+        rmse: None,
+        mae: None,
+        bic: None,
+        aic: None,
+        r2: None,
+        selected: false,
+        skip_reason: Some(reason.clone()),
+    }));
+    rows.sort_by_key(|r| r.model.param_count());
+    rows
 }
 
 /// Compute fitted values on an x-grid from a `FitResult`.
 pub fn fitted_grid(fit: &CurveModel, tenors: &[f64]) -> Vec<f64> {
     tenors
         .iter()
-        .map(|&t| predict(fit.name, t, &fit.betas, &fit.taus))
+        .map(|&t| fit.eval(t))
         .collect()
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::domain::{BondExtras, BondMeta, RatingBand, YKind};
     use chrono::NaiveDate;
 
-    fn make_test_config() -> FitConfig {
+    /// Small-grid config shared by fitting tests across the crate.
+    pub(crate) fn make_test_config() -> FitConfig {
         FitConfig {
             rating: RatingBand::BBB,
             sample_count: 100,
+            asof: None,
+            missing_rating: crate::domain::MissingRating::Error,
+            date_policy: crate::domain::DatePolicy::Common,
+            min_vol_obs: None,
+            short_vol_history: crate::domain::ShortVolHistory::Warn,
+            strict_dates: false,
+            series_map: None,
+            api_key_file: None,
+            smooth_days: 1,
+            points_file: None,
+            no_prior: false,
             sample_seed: 42,
+            rng_seed: None,
+            noise_seed: None,
+            weight_mode: crate::domain::WeightMode::Uniform,
+            rating_blend: None,
             model_spec: ModelSpec::Auto,
+            basis: crate::domain::Basis::Standard,
             tau_min: 0.05,
             tau_max: 30.0,
             tau_steps_ns: 5,
             tau_steps_nss: 5,
             tau_steps_nssc: 5,
+            tau_tiebreak: crate::domain::TauTiebreak::LowIndex,
+            beta_abs_max: None,
+            y0_min: None,
+            y0_max: None,
+            y_transform: crate::domain::YTransform::None,
+            bic_margin: 2.0,
+            splice_at: None,
+            splice_window: 1.0,
+            min_n_buffer: 5,
+            min_coverage: 0.1,
+            progress: false,
             tenor_min: 0.0,
+            tenor_grid_step: None,
+            max_extrap_years: None,
+            round_tenors: None,
             tenor_max: 100.0,
+            fit_min: None,
+            fit_max: None,
+            weight_file: None,
+            on_bad_weight: crate::domain::BadWeight::Drop,
+            issuer_cap: None,
             top_n: 10,
+            top_cheap: None,
+            top_rich: None,
+            rank_by: crate::domain::RankBy::Residual,
+            signal_weights: crate::domain::SignalWeights::default(),
+            output_unit: crate::domain::YUnit::Bp,
+            locale: crate::domain::NumberLocale::Plain,
+            compact: false,
+            output_format: crate::domain::OutputFormat::Text,
+            timing: false,
             plot: false,
             plot_width: 80,
             plot_height: 20,
+            plot_baseline: false,
+            plot_vol: false,
+            plot_hash: false,
             export_results: None,
+            export_append: false,
             export_curve: None,
+            export_timeseries: None,
+            export_landscape: None,
+            compare_models: false,
+            export_models: None,
+            export_sample: None,
+            export_stats: None,
+            export_diagnostics: None,
+            embed_inputs: false,
+            pillars: Vec::new(),
+            pillars_json: None,
+            export_formula: None,
+            formula_out: None,
+            baseline_curve: None,
+            rank_by_shift: false,
+            fail_on_warnings: false,
+            influence: false,
+            all_ratings: false,
+            threads: None,
+            seed_sweep: None,
+            explain_selection: false,
+            diagnostics: false,
+            slopes: Vec::new(),
+            resid_bins: crate::domain::BinEdges::default(),
             jump_prob_wide: 0.05,
             jump_prob_tight: 0.05,
             jump_k_wide: 2.5,
             jump_k_tight: 2.5,
+            vol_floor: None,
+            vol_cap: None,
         }
     }
 
@@ -183,6 +529,8 @@ mod tests {
                     display_name: "NS".to_string(),
                     betas: vec![],
                     taus: vec![],
+                    y_transform: crate::domain::YTransform::None,
+                    splice: None,
                 },
                 quality: FitQuality {
                     sse: 100.0,
@@ -197,6 +545,8 @@ mod tests {
                     display_name: "NSS".to_string(),
                     betas: vec![],
                     taus: vec![],
+                    y_transform: crate::domain::YTransform::None,
+                    splice: None,
                 },
                 quality: FitQuality {
                     sse: 99.0,
@@ -207,10 +557,63 @@ mod tests {
             },
         ];
 
-        let chosen = select_by_bic(&fits);
+        let (chosen, _) = select_by_bic(&fits, 2.0);
         assert_eq!(chosen.model.name, ModelKind::Ns);
     }
 
+    /// An NS and an NSS fit with the given BICs (SSEs and params are unused
+    /// by the selection rules).
+    fn ns_and_nss(ns_bic: f64, nss_bic: f64) -> Vec<FitResult> {
+        [(ModelKind::Ns, "NS", ns_bic), (ModelKind::Nss, "NSS", nss_bic)]
+            .into_iter()
+            .map(|(name, display_name, bic)| FitResult {
+                model: CurveModel {
+                    name,
+                    display_name: display_name.to_string(),
+                    betas: vec![],
+                    taus: vec![],
+                    y_transform: crate::domain::YTransform::None,
+                    splice: None,
+                },
+                quality: FitQuality { sse: 100.0, rmse: 0.0, bic, n: 200 },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bic_margin_sets_how_far_the_simplicity_rule_reaches() {
+        // NSS has the lower BIC but NS is within the default margin.
+        let fits = ns_and_nss(10.0, 8.5);
+        assert_eq!(select_by_bic(&fits, 2.0).0.model.name, ModelKind::Ns);
+        // Margin 0 is pure minimum BIC; a wider margin reaches further.
+        assert_eq!(select_by_bic(&fits, 0.0).0.model.name, ModelKind::Nss);
+        let fits = ns_and_nss(10.0, 5.0);
+        assert_eq!(select_by_bic(&fits, 2.0).0.model.name, ModelKind::Nss);
+        assert_eq!(select_by_bic(&fits, 5.0).0.model.name, ModelKind::Ns);
+    }
+
+    #[test]
+    fn bic_selection_explains_the_rule_that_fired() {
+        let (_, steps) = select_by_bic(&ns_and_nss(10.0, 11.5), 2.0);
+        assert!(steps.last().unwrap().starts_with("Rule fired: minimum BIC"), "{steps:?}");
+
+        let (_, steps) = select_by_bic(&ns_and_nss(10.0, 8.5), 2.0);
+        assert!(steps.contains(&"Minimum BIC: NSS (8.500).".to_string()), "{steps:?}");
+        assert!(steps.last().unwrap().starts_with("Rule fired: simplicity"), "{steps:?}");
+
+        let (_, steps) = select_by_bic(&ns_and_nss(10.0, 8.5), 0.0);
+        assert!(steps.last().unwrap().starts_with("Rule fired: minimum BIC"), "{steps:?}");
+    }
+
+    #[test]
+    fn bic_weights_follow_the_bic_gap() {
+        // ΔBIC = 1.5 -> weights 1 : exp(-0.75).
+        let weights = bic_weights(&ns_and_nss(10.0, 11.5));
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        let expected_ns = 1.0 / (1.0 + (-0.75_f64).exp());
+        assert!((weights[0] - expected_ns).abs() < 1e-12);
+    }
+
     #[test]
     fn fit_and_select_skips_underdetermined() {
         let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
@@ -230,6 +633,7 @@ mod tests {
         let input_spec = InputSpec {
             asof_date: asof,
             y_kind: YKind::Oas,
+            y_unit: crate::domain::YUnit::Bp,
         };
 
         let config = make_test_config();
@@ -238,6 +642,104 @@ mod tests {
         assert_eq!(err.exit_code(), 3);
     }
 
+    /// Five flat 100bp bonds at 1..5y: enough for NS (k=4) only with a buffer of 1.
+    fn five_flat_points() -> (Vec<BondPoint>, InputSpec) {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let points = (0..5)
+            .map(|i| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 1.0 + i as f64,
+                y_obs: 100.0,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            })
+            .collect();
+        (points, InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: crate::domain::YUnit::Bp })
+    }
+
+    #[test]
+    fn min_n_buffer_sets_the_underdetermined_threshold() {
+        let (points, input_spec) = five_flat_points();
+        let mut config = make_test_config();
+        // With a buffer of 1, NS (k=4) fits on 5 points; the others are skipped
+        // with the configured buffer in the reason.
+        config.min_n_buffer = 1;
+        let selection = fit_and_select(&points, &input_spec, &config).unwrap();
+        assert_eq!(selection.best.model.name, ModelKind::Ns);
+        assert!(selection.skipped.iter().all(|(_, r)| r.contains("k+1=")), "{:?}", selection.skipped);
+    }
+
+    #[test]
+    fn compare_models_keeps_skipped_models_with_their_reason() {
+        let (points, input_spec) = five_flat_points();
+        let mut config = make_test_config();
+        config.min_n_buffer = 1;
+        let selection = fit_and_select(&points, &input_spec, &config).unwrap();
+
+        let rows = compare_models(&points, &selection);
+        let kinds: Vec<ModelKind> = rows.iter().map(|r| r.model).collect();
+        assert_eq!(kinds, [ModelKind::Ns, ModelKind::NssShared, ModelKind::Nss, ModelKind::Nssc]);
+        assert!(rows[0].selected && rows[0].skip_reason.is_none());
+        assert!(rows[0].mae.unwrap() < 1e-6);
+        // Flat data has no variance to explain.
+        assert_eq!(rows[0].r2, None);
+        assert!(rows[1..].iter().all(|r| r.sse.is_none() && r.skip_reason.is_some()));
+    }
+
+    #[test]
+    fn coverage_guard_keeps_only_ns_for_clustered_tenors() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        // 40 bonds in 5-10y of a 0-40y range: 12.5% coverage.
+        let points: Vec<BondPoint> = (0..40)
+            .map(|i| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 5.0 + 5.0 * i as f64 / 39.0,
+                y_obs: 100.0 + i as f64,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            })
+            .collect();
+        let input_spec = InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: crate::domain::YUnit::Bp };
+        assert!((tenor_coverage(&points, 0.0, 40.0) - 0.125).abs() < 1e-12);
+
+        // The default threshold leaves this alone.
+        let mut config = make_test_config();
+        config.tenor_max = 40.0;
+        let selection = fit_and_select(&points, &input_spec, &config).unwrap();
+        assert_eq!(selection.fits.len(), 4);
+
+        config.min_coverage = 0.2;
+        let selection = fit_and_select(&points, &input_spec, &config).unwrap();
+        assert_eq!(selection.best.model.name, ModelKind::Ns);
+        assert_eq!(selection.skipped.len(), 3);
+        assert!(
+            selection.skipped.iter().all(|(_, r)| r.contains("span 12.5% of [0.00, 40.00]y < --min-coverage 20.0%")),
+            "{:?}",
+            selection.skipped
+        );
+
+        config.min_coverage = 1.5;
+        assert_eq!(fit_and_select(&points, &input_spec, &config).unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn fit_and_select_reports_empty_point_set() {
+        let input_spec = InputSpec {
+            asof_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            y_kind: YKind::Oas,
+            y_unit: crate::domain::YUnit::Bp,
+        };
+        let err = fit_and_select(&[], &input_spec, &make_test_config()).unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+        assert!(err.to_string().contains("0 points remain"), "{err}");
+    }
+
     #[test]
     fn auto_selects_ns_on_ns_data_even_if_more_complex_fit_is_exact() {
         let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
@@ -263,6 +765,7 @@ mod tests {
         let input_spec = InputSpec {
             asof_date: asof,
             y_kind: YKind::Oas,
+            y_unit: crate::domain::YUnit::Bp,
         };
 
         let mut config = make_test_config();
@@ -301,6 +804,7 @@ mod tests {
         let input_spec = InputSpec {
             asof_date: asof,
             y_kind: YKind::Oas,
+            y_unit: crate::domain::YUnit::Bp,
         };
 
         let mut config = make_test_config();
@@ -313,4 +817,45 @@ mod tests {
         let selection = fit_and_select(&points, &input_spec, &config).unwrap();
         assert_eq!(selection.best.model.name, ModelKind::Nss);
     }
+
+    #[test]
+    fn log_transform_fits_ln_y_and_reports_on_the_original_scale() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (true_betas, true_taus) = ([4.5, -0.6, 0.4], [2.0]);
+        let mut points: Vec<BondPoint> = (0..30)
+            .map(|i| {
+                let t = 0.5 + i as f64;
+                BondPoint {
+                    id: format!("B{i}"),
+                    asof_date: asof,
+                    maturity_date: asof,
+                    tenor: t,
+                    y_obs: crate::models::predict(ModelKind::Ns, t, &true_betas, &true_taus).exp(),
+                    weight: 1.0,
+                    meta: BondMeta::default(),
+                    extras: BondExtras::default(),
+                }
+            })
+            .collect();
+        let input_spec = InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: crate::domain::YUnit::Bp };
+        let mut config = make_test_config();
+        config.model_spec = ModelSpec::Ns;
+        config.tau_min = 1.0;
+        config.tau_max = 4.0;
+        config.tau_steps_ns = 3;
+        config.y_transform = YTransform::Log;
+
+        let selection = fit_and_select(&points, &input_spec, &config).unwrap();
+        let model = &selection.best.model;
+        assert_eq!(model.y_transform, YTransform::Log);
+        assert!((model.betas[0] - true_betas[0]).abs() < 1e-6, "{:?}", model.betas);
+        for p in &points {
+            assert!((model.eval(p.tenor) - p.y_obs).abs() < 1e-6 * p.y_obs);
+        }
+
+        points[3].y_obs = 0.0;
+        let err = fit_and_select(&points, &input_spec, &config).unwrap_err();
+        assert_eq!(err.exit_code(), 4);
+        assert!(err.to_string().contains("first: B3"), "{err}");
+    }
 }
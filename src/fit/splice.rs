@@ -0,0 +1,207 @@
+//! Two-segment fits spliced at a knot tenor (`--splice-at`).
+//!
+//! The selected model kind is refitted separately to the bonds below and at or
+//! above the knot, and the two curves are joined by a linear crossfade over
+//! `--splice-window` years centred on the knot (`CurveModel::predict`). Both
+//! segments are continuous and the crossfade weight is continuous in `t`, so
+//! the spliced curve is continuous. The window must be positive: a zero-width
+//! crossfade would step at the knot.
+
+use crate::domain::{BondPoint, CurveModel, FitConfig, FitQuality, FitResult, ModelKind, ModelSpec, Splice};
+use crate::error::AppError;
+use crate::fit::selection::{bic, fit_and_select, transform_points, FitSelection};
+use crate::io::ingest::InputSpec;
+
+/// Replace `selection.best` with a curve spliced at `at`, or keep the single
+/// fit (with a warning) when either segment is too small for the model or its
+/// fit fails.
+pub fn splice_selection(
+    points: &[BondPoint],
+    input_spec: &InputSpec,
+    config: &FitConfig,
+    at: f64,
+    mut selection: FitSelection,
+) -> Result<FitSelection, AppError> {
+    if !(at.is_finite() && at > 0.0) {
+        return Err(AppError::new(2, format!("Invalid --splice-at {at}: must be a positive tenor in years.")));
+    }
+    if !(config.splice_window.is_finite() && config.splice_window > 0.0) {
+        return Err(AppError::new(
+            2,
+            format!("Invalid --splice-window {}: must be a finite number > 0.", config.splice_window),
+        ));
+    }
+
+    let kind = selection.best.model.name;
+    let (short, long): (Vec<BondPoint>, Vec<BondPoint>) = points.iter().cloned().partition(|p| p.tenor < at);
+    let need = kind.param_count() + config.min_n_buffer;
+    for (label, segment) in [("short", &short), ("long", &long)] {
+        if segment.len() < need {
+            selection.warnings.push(format!(
+                "--splice-at {at}: {label} segment is underdetermined (n={} < k+{}={need}); using a single {} fit.",
+                segment.len(),
+                config.min_n_buffer,
+                kind.display_name()
+            ));
+            return Ok(selection);
+        }
+    }
+
+    // Each segment fits only the selected kind, with its share of the tenor
+    // range for the coverage guard.
+    let segment_config = |tenor_min: f64, tenor_max: f64| FitConfig {
+        model_spec: model_spec_for(kind),
+        tenor_min,
+        tenor_max,
+        ..config.clone()
+    };
+    let fits = fit_and_select(&short, input_spec, &segment_config(config.tenor_min, at)).and_then(|short_fit| {
+        fit_and_select(&long, input_spec, &segment_config(at, config.tenor_max)).map(|long_fit| (short_fit, long_fit))
+    });
+    let (short_fit, long_fit) = match fits {
+        Ok(fits) => fits,
+        Err(e) => {
+            selection.warnings.push(format!(
+                "--splice-at {at}: segment fit failed ({e}); using a single {} fit.",
+                kind.display_name()
+            ));
+            return Ok(selection);
+        }
+    };
+    selection.warnings.extend(short_fit.warnings);
+    selection.warnings.extend(long_fit.warnings);
+
+    let model = CurveModel {
+        display_name: format!("{} spliced at {at}y", kind.display_name()),
+        splice: Some(Box::new(Splice {
+            at,
+            window: config.splice_window,
+            long: long_fit.best.model,
+        })),
+        ..short_fit.best.model
+    };
+
+    // Quality over all bonds, on the fitting scale like the single fits.
+    let transformed = transform_points(points, config.y_transform)?;
+    let fit_points = transformed.as_deref().unwrap_or(points);
+    let sse: f64 = fit_points
+        .iter()
+        .map(|p| p.weight * (p.y_obs - model.predict(p.tenor)).powi(2))
+        .sum();
+    let n = points.len();
+    let quality = FitQuality {
+        sse,
+        rmse: (sse / n as f64).sqrt(),
+        bic: bic(n, sse, model.param_count()),
+        n,
+    };
+
+    selection.explanation.push(format!(
+        "Spliced at {at}y: {} refitted to {} bond(s) below and {} at or above, crossfaded over {}y.",
+        kind.display_name(),
+        short.len(),
+        long.len(),
+        config.splice_window
+    ));
+    selection.best = FitResult { model, quality };
+    // The tau landscape belongs to the single fit.
+    selection.landscape = None;
+    Ok(selection)
+}
+
+/// The `--model` value that fits exactly `kind`.
+fn model_spec_for(kind: ModelKind) -> ModelSpec {
+    match kind {
+        ModelKind::Ns => ModelSpec::Ns,
+        ModelKind::NssShared => ModelSpec::NssShared,
+        ModelKind::Nss => ModelSpec::Nss,
+        ModelKind::Nssc => ModelSpec::Nssc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BondExtras, BondMeta, YKind, YUnit};
+    use chrono::NaiveDate;
+
+    /// Flat 80bp to 5y, then rising 10bp/y: a kink NS can't follow.
+    fn level(t: f64) -> f64 {
+        if t < 5.0 { 80.0 } else { 80.0 + 10.0 * (t - 5.0) }
+    }
+
+    fn kinked_points() -> (Vec<BondPoint>, InputSpec) {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let points = (0..80)
+            .map(|i| {
+                let t = 0.5 + i as f64 * 0.25;
+                BondPoint {
+                    id: format!("B{i}"),
+                    asof_date: asof,
+                    maturity_date: asof,
+                    tenor: t,
+                    y_obs: level(t),
+                    weight: 1.0,
+                    meta: BondMeta::default(),
+                    extras: BondExtras::default(),
+                }
+            })
+            .collect();
+        (points, InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: YUnit::Bp })
+    }
+
+    #[test]
+    fn splice_fits_each_side_of_a_kink_and_stays_continuous() {
+        let (points, spec) = kinked_points();
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.model_spec = ModelSpec::Ns;
+        config.splice_window = 1.0;
+
+        let single = fit_and_select(&points, &spec, &config).unwrap();
+        let spliced = splice_selection(&points, &spec, &config, 5.0, single.clone()).unwrap();
+        let model = &spliced.best.model;
+        assert!(model.splice.is_some(), "{:?}", spliced.warnings);
+        assert_eq!(model.param_count(), 2 * ModelKind::Ns.param_count());
+        assert!(spliced.best.quality.sse < single.best.quality.sse / 10.0);
+
+        // Continuous through the crossfade window, and on each segment away from it.
+        let step = 1e-4;
+        for i in 0..200 {
+            let t = 4.0 + i as f64 * 0.01;
+            assert!((model.eval(t + step) - model.eval(t)).abs() < 0.05, "jump at t={t}");
+        }
+        assert!((model.eval(2.0) - 80.0).abs() < 1.0);
+        assert!((model.eval(15.0) - level(15.0)).abs() < 1.0);
+
+        // Too few bonds on one side: keep the single fit and say so.
+        let few = splice_selection(&points, &spec, &config, 1.0, single.clone()).unwrap();
+        assert!(few.best.model.splice.is_none());
+        assert!(few.warnings.iter().any(|w| w.contains("short segment is underdetermined")), "{:?}", few.warnings);
+
+        // Spliced curves survive the curve-JSON round trip.
+        let json = serde_json::to_string(model).unwrap();
+        let back: CurveModel = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.eval(5.2), model.eval(5.2));
+    }
+
+    #[test]
+    fn splice_rejects_a_zero_window_and_falls_back_when_a_segment_fails() {
+        let (points, spec) = kinked_points();
+        let mut config = crate::fit::selection::tests::make_test_config();
+        config.model_spec = ModelSpec::Nss;
+        let single = fit_and_select(&points, &spec, &config).unwrap();
+
+        config.splice_window = 0.0;
+        let err = splice_selection(&points, &spec, &config, 5.0, single.clone()).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+
+        // Enough bonds on both sides, but the long side spans 16% of [5, 100]y,
+        // so its NSS fit hits the coverage guardrail.
+        config.splice_window = 1.0;
+        config.min_coverage = 0.5;
+        let kept = splice_selection(&points, &spec, &config, 5.0, single.clone()).unwrap();
+        assert!(kept.best.model.splice.is_none());
+        assert_eq!(kept.best.quality.sse, single.best.quality.sse);
+        assert!(kept.warnings.iter().any(|w| w.contains("segment fit failed")), "{:?}", kept.warnings);
+    }
+}
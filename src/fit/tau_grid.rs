@@ -1,6 +1,6 @@
 //! Tau grid generation.
 //!
-//! We fit NS/NSS/NSSC using a deterministic grid search over τ values.
+//! We fit NS/NSS-shared/NSS/NSSC using a deterministic grid search over τ values.
 //!
 //! Why grid search?
 //! - It avoids local minima issues common in nonlinear optimization.
@@ -8,6 +8,7 @@
 //! - With small parameter counts, a modest grid is fast enough for daily RV screens.
 
 use crate::error::AppError;
+use crate::models::SHARED_TAU_RATIO;
 
 /// Generate `steps` log-spaced points between `min` and `max` (inclusive).
 pub fn log_space(min: f64, max: f64, steps: usize) -> Result<Vec<f64>, AppError> {
@@ -38,6 +39,34 @@ pub fn tau_grid_ns(min: f64, max: f64, steps: usize) -> Result<Vec<Vec<f64>>, Ap
     Ok(values.into_iter().map(|t| vec![t]).collect())
 }
 
+/// NSS-shared tau grid: `[τ]`, capped at `max / SHARED_TAU_RATIO` so the tied
+/// second decay stays within the searched range.
+pub fn tau_grid_nss_shared(min: f64, max: f64, steps: usize) -> Result<Vec<Vec<f64>>, AppError> {
+    // A valid range can still be too narrow for the tied ratio.
+    if min.is_finite() && max.is_finite() && min > 0.0 && max > min && max / SHARED_TAU_RATIO <= min {
+        return Err(AppError::new(
+            2,
+            format!(
+                "NSS-shared tau grid is empty: with tau2 = {SHARED_TAU_RATIO} * tau1, tau range [{min}, {max}] leaves no tau1 \
+                 (needs tau_max > {SHARED_TAU_RATIO} * tau_min = {}). Widen the range or use another model.",
+                SHARED_TAU_RATIO * min
+            ),
+        ));
+    }
+    tau_grid_ns(min, max / SHARED_TAU_RATIO, steps)
+}
+
+/// Error for an ordered grid with no valid tuples.
+fn empty_grid_error(model: &str, order: &str, min: f64, max: f64, steps: usize, need: usize) -> AppError {
+    AppError::new(
+        2,
+        format!(
+            "{model} tau grid is empty: {steps} steps over [{min}, {max}] leave no {order} tuples. \
+             Use at least {need} steps."
+        ),
+    )
+}
+
 /// NSS tau grid: `[τ1, τ2]` with constraint `τ1 < τ2`.
 pub fn tau_grid_nss(min: f64, max: f64, steps: usize) -> Result<Vec<Vec<f64>>, AppError> {
     let values = log_space(min, max, steps)?;
@@ -47,6 +76,9 @@ pub fn tau_grid_nss(min: f64, max: f64, steps: usize) -> Result<Vec<Vec<f64>>, A
             out.push(vec![values[i], values[j]]);
         }
     }
+    if out.is_empty() {
+        return Err(empty_grid_error("NSS", "tau1 < tau2", min, max, steps, 2));
+    }
     Ok(out)
 }
 
@@ -61,6 +93,9 @@ pub fn tau_grid_nssc(min: f64, max: f64, steps: usize) -> Result<Vec<Vec<f64>>,
             }
         }
     }
+    if out.is_empty() {
+        return Err(empty_grid_error("NSSC", "tau1 < tau2 < tau3", min, max, steps, 3));
+    }
     Ok(out)
 }
 
@@ -82,5 +117,17 @@ mod tests {
             assert!(taus[0] < taus[1] && taus[1] < taus[2]);
         }
     }
-}
 
+    #[test]
+    fn empty_grids_name_the_offending_values() {
+        let err = tau_grid_nssc(0.1, 10.0, 2).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("NSSC tau grid is empty: 2 steps over [0.1, 10]"), "{err}");
+        assert_eq!(tau_grid_nssc(0.1, 10.0, 3).unwrap().len(), 1);
+
+        let err = tau_grid_nss_shared(1.0, 2.5, 10).unwrap_err();
+        assert!(err.to_string().contains("tau range [1, 2.5] leaves no tau1"), "{err}");
+        // NS never comes back empty once the steps are valid.
+        assert_eq!(tau_grid_ns(1.0, 2.5, 2).unwrap().len(), 2);
+    }
+}
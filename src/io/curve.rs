@@ -10,32 +10,47 @@
 use std::fs::File;
 use std::path::Path;
 
-use crate::domain::{CurveFile, CurveGrid, FitConfig, FitResult};
+use serde::Serialize;
+
+use chrono::NaiveDate;
+
+use crate::report::RunOutput;
+use crate::domain::{Basis, CurveFile, CurveGrid, FitConfig, CURVE_SCHEMA_VERSION, FitResult, GridExtrapolation, RatingBand, YUnit};
 use crate::error::AppError;
+use crate::io::export::write_atomic;
 use crate::io::ingest::IngestedData;
-use crate::models::predict;
+use crate::models::classify_shape;
 
-/// Write a curve JSON file.
+/// Write a curve JSON file (atomically, via `write_atomic`).
 pub fn write_curve_json(path: &Path, best: &FitResult, ingest: &IngestedData, config: &FitConfig) -> Result<(), AppError> {
-    let file = File::create(path)
-        .map_err(|e| AppError::new(2, format!("Failed to create curve JSON '{}': {e}", path.display())))?;
-
-    let (tenors, y) = build_grid(best, ingest.stats.tenor_min, ingest.stats.tenor_max, 101);
+    let cap = match config.max_extrap_years {
+        Some(years) if !(years.is_finite() && years >= 0.0) => {
+            return Err(AppError::new(2, format!("Invalid --max-extrap-years {years}: must be >= 0.")));
+        }
+        Some(years) => Some(ingest.stats.tenor_max + years),
+        None => None,
+    };
+    let (tenors, y) = build_grid(best, ingest.stats.tenor_min, ingest.stats.tenor_max, cap, 101);
 
     let curve = CurveFile {
         tool: "rv".to_string(),
+        schema_version: CURVE_SCHEMA_VERSION,
         asof_date: ingest.input_spec.asof_date,
+        requested_asof: config.asof,
         y: ingest.input_spec.y_kind,
+        unit: ingest.input_spec.y_unit,
         rating: config.rating,
+        basis: config.basis,
+        lambdas: (config.basis == Basis::DieboldLi).then(|| config.basis.decay_params(&best.model.taus)),
         model: best.model.clone(),
         fit_quality: best.quality.clone(),
+        shape: Some(classify_shape(&y).as_str().to_string()),
         grid: CurveGrid { tenor_years: tenors, y },
     };
 
-    serde_json::to_writer_pretty(file, &curve)
+    let json = serde_json::to_vec_pretty(&curve)
         .map_err(|e| AppError::new(2, format!("Failed to write curve JSON: {e}")))?;
-
-    Ok(())
+    write_atomic(path, &json, "curve JSON")
 }
 
 /// Read a curve JSON file.
@@ -47,7 +62,188 @@ pub fn read_curve_json(path: &Path) -> Result<CurveFile, AppError> {
     Ok(curve)
 }
 
-fn build_grid(best: &FitResult, tenor_min: f64, tenor_max: f64, n: usize) -> (Vec<f64>, Vec<f64>) {
+/// Default `--max-curve-age-days` for `rv plot` / `rv eval`.
+pub const DEFAULT_MAX_CURVE_AGE_DAYS: i64 = 7;
+
+/// Warning for a curve whose FRED observation date is more than
+/// `max_age_days` before `today`, or `None` when it is fresh enough.
+pub fn curve_age_warning(curve: &CurveFile, today: NaiveDate, max_age_days: i64) -> Option<String> {
+    let age = (today - curve.asof_date).num_days();
+    (age > max_age_days).then(|| {
+        format!(
+            "Curve as-of {} is {age} days old (--max-curve-age-days {max_age_days}); pass --no-age-check for historical analysis.",
+            curve.asof_date
+        )
+    })
+}
+
+/// Consistency problems in a loaded curve file (empty when it is valid).
+///
+/// Checks that the grid is well-formed and, when the file records a shape, that
+/// it matches the shape re-derived from the grid.
+pub fn validate_curve(curve: &CurveFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    let grid = &curve.grid;
+    if grid.tenor_years.len() != grid.y.len() {
+        problems.push(format!(
+            "grid length mismatch: {} tenors vs {} y-values",
+            grid.tenor_years.len(),
+            grid.y.len()
+        ));
+    }
+    if grid.tenor_years.windows(2).any(|w| w[1].partial_cmp(&w[0]) != Some(std::cmp::Ordering::Greater)) {
+        problems.push("grid tenors are not strictly increasing".to_string());
+    }
+    if grid.y.iter().any(|v| !v.is_finite()) {
+        problems.push("grid contains non-finite y-values".to_string());
+    }
+    if let Some(stored) = &curve.shape {
+        let computed = classify_shape(&grid.y).as_str();
+        if stored != computed {
+            problems.push(format!("stored shape '{stored}' does not match grid shape '{computed}'"));
+        }
+    }
+    problems
+}
+
+impl CurveFile {
+    /// Linearly interpolate the stored grid at `tenor`, without the model.
+    ///
+    /// This lets consumers that do not implement NS evaluate a curve JSON from its
+    /// grid alone. Tenors on a grid knot return the stored value exactly; tenors
+    /// outside the grid follow `policy`. Errors on an empty or degenerate grid
+    /// (fewer than two knots, mismatched lengths, unsorted or non-finite values).
+    pub fn interp(&self, tenor: f64, policy: GridExtrapolation) -> Result<f64, AppError> {
+        let (xs, ys) = (&self.grid.tenor_years, &self.grid.y);
+        if xs.len() < 2 || xs.len() != ys.len() {
+            return Err(AppError::new(
+                2,
+                format!("Curve grid is empty or degenerate ({} tenors, {} y-values).", xs.len(), ys.len()),
+            ));
+        }
+        if xs.iter().chain(ys).any(|v| !v.is_finite())
+            || xs.windows(2).any(|w| w[1].partial_cmp(&w[0]) != Some(std::cmp::Ordering::Greater))
+        {
+            return Err(AppError::new(
+                2,
+                "Curve grid must have finite values and strictly increasing tenors.".to_string(),
+            ));
+        }
+        if !tenor.is_finite() {
+            return Err(AppError::new(2, format!("Invalid tenor {tenor}.")));
+        }
+
+        let last = xs.len() - 1;
+        let seg = match xs.binary_search_by(|x| x.total_cmp(&tenor)) {
+            Ok(i) => return Ok(ys[i]),
+            Err(0) if policy == GridExtrapolation::Clamp => return Ok(ys[0]),
+            Err(i) if i > last && policy == GridExtrapolation::Clamp => return Ok(ys[last]),
+            Err(i) => i.clamp(1, last),
+        };
+        let (x0, x1, y0, y1) = (xs[seg - 1], xs[seg], ys[seg - 1], ys[seg]);
+        Ok(y0 + (y1 - y0) * (tenor - x0) / (x1 - x0))
+    }
+}
+
+/// Tenor range `[min, max]` covered by a curve file's fitted grid.
+///
+/// Returns `None` for an empty grid.
+pub fn grid_range(curve: &CurveFile) -> Option<(f64, f64)> {
+    let grid = &curve.grid.tenor_years;
+    let lo = grid.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = grid.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (lo <= hi).then_some((lo, hi))
+}
+
+/// A saved curve evaluated at one tenor.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurveValue {
+    pub tenor: f64,
+    pub y: f64,
+    /// True when `tenor` lies outside the fitted range (a saved curve's grid,
+    /// or a live fit's observed tenors): the value is an extrapolation of the
+    /// model, not supported by the fitted data.
+    pub out_of_range: bool,
+}
+
+/// The selected curve at a set of key tenors (`--pillars`), with the labels a
+/// term-sheet table needs.
+#[derive(Debug, Clone, Serialize)]
+pub struct PillarTable {
+    pub asof_date: NaiveDate,
+    pub rating: RatingBand,
+    pub model: String,
+    pub unit: YUnit,
+    /// Observed tenor range of the fit; pillars outside it are flagged.
+    pub fitted_range: (f64, f64),
+    pub pillars: Vec<CurveValue>,
+}
+
+/// Evaluate the run's selected curve at `config.pillars`.
+pub fn pillar_table(run: &RunOutput, config: &FitConfig) -> Result<PillarTable, AppError> {
+    let (lo, hi) = (run.ingest.stats.tenor_min, run.ingest.stats.tenor_max);
+    let model = &run.selection.best.model;
+    let pillars = config
+        .pillars
+        .iter()
+        .map(|&tenor| {
+            if !(tenor.is_finite() && tenor > 0.0) {
+                return Err(AppError::new(2, format!("Invalid pillar {tenor}: must be a positive number of years.")));
+            }
+            Ok(CurveValue { tenor, y: model.eval(tenor), out_of_range: tenor < lo || tenor > hi })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+    Ok(PillarTable {
+        asof_date: run.ingest.input_spec.asof_date,
+        rating: config.rating,
+        model: model.display_name.clone(),
+        unit: run.ingest.input_spec.y_unit,
+        fitted_range: (lo, hi),
+        pillars,
+    })
+}
+
+/// Write a pillar table as pretty JSON (atomically).
+pub fn write_pillars_json(path: &Path, table: &PillarTable) -> Result<(), AppError> {
+    let json = serde_json::to_vec_pretty(table)
+        .map_err(|e| AppError::new(2, format!("Failed to write pillars JSON: {e}")))?;
+    write_atomic(path, &json, "pillars JSON")
+}
+
+/// Evaluate a saved curve at the given tenors: through its model, or by
+/// interpolating the stored grid when `from_grid` gives an extrapolation policy.
+pub fn eval_curve(
+    curve: &CurveFile,
+    tenors: &[f64],
+    from_grid: Option<GridExtrapolation>,
+) -> Result<Vec<CurveValue>, AppError> {
+    let range = grid_range(curve);
+    let model = &curve.model;
+    tenors
+        .iter()
+        .map(|&tenor| {
+            if !(tenor.is_finite() && tenor > 0.0) {
+                return Err(AppError::new(2, format!("Invalid tenor {tenor}: must be a positive number of years.")));
+            }
+            let y = match from_grid {
+                Some(policy) => curve.interp(tenor, policy)?,
+                None => model.eval(tenor),
+            };
+            if !y.is_finite() {
+                return Err(AppError::new(4, format!("Non-finite curve value at tenor {tenor}.")));
+            }
+            let out_of_range = range.is_none_or(|(lo, hi)| tenor < lo || tenor > hi);
+            Ok(CurveValue { tenor, y, out_of_range })
+        })
+        .collect()
+}
+
+/// Evenly spaced `(tenor, y)` grid over `[tenor_min, tenor_max]`.
+///
+/// Degenerate or empty ranges (e.g. every bond at one tenor) fall back to
+/// 0.25-30y; `cap` then bounds the upper end, so the grid never extrapolates
+/// further than the caller allows.
+fn build_grid(best: &FitResult, tenor_min: f64, tenor_max: f64, cap: Option<f64>, n: usize) -> (Vec<f64>, Vec<f64>) {
     let n = n.max(2);
     let mut t0 = tenor_min;
     let mut t1 = tenor_max;
@@ -57,7 +253,10 @@ fn build_grid(best: &FitResult, tenor_min: f64, tenor_max: f64, n: usize) -> (Ve
     }
     if (t1 - t0).abs() < 1e-9 {
         t0 = (t0 - 0.5).max(0.01);
-        t1 = t1 + 0.5;
+        t1 += 0.5;
+    }
+    if let Some(cap) = cap.filter(|c| c.is_finite() && *c > t0) {
+        t1 = t1.min(cap);
     }
 
     let mut tenors = Vec::with_capacity(n);
@@ -67,8 +266,119 @@ fn build_grid(best: &FitResult, tenor_min: f64, tenor_max: f64, n: usize) -> (Ve
         let u = i as f64 / (n as f64 - 1.0);
         let t = t0 + u * (t1 - t0);
         tenors.push(t);
-        y.push(predict(best.model.name, t, &best.model.betas, &best.model.taus));
+        y.push(best.model.eval(t));
     }
 
     (tenors, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CurveModel, FitQuality, ModelKind};
+    use crate::models::predict;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn eval_flags_tenors_outside_grid() {
+        let curve = CurveFile::fixture(&[100.0, -20.0, 30.0], &[2.0], &[1.0, 5.0, 10.0], &[0.0; 3]);
+        let model = &curve.model;
+
+        let values = eval_curve(&curve, &[0.5, 1.0, 7.0, 10.0, 30.0], None).unwrap();
+        let flags: Vec<bool> = values.iter().map(|v| v.out_of_range).collect();
+        assert_eq!(flags, vec![true, false, false, false, true]);
+        assert_eq!(values[2].y, predict(ModelKind::Ns, 7.0, &model.betas, &model.taus));
+        assert!(eval_curve(&curve, &[-1.0], None).is_err());
+    }
+
+    #[test]
+    fn curve_age_warning_counts_days_from_the_curve_asof() {
+        // Age check against the FRED observation date; the fixture is as of 2025-01-02.
+        let curve = CurveFile::fixture(&[100.0, -20.0, 30.0], &[2.0], &[1.0, 5.0, 10.0], &[0.0; 3]);
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        assert_eq!(curve_age_warning(&curve, day(9), 7), None);
+        let warning = curve_age_warning(&curve, day(10), 7).unwrap();
+        assert!(warning.starts_with("Curve as-of 2025-01-02 is 8 days old"), "{warning}");
+    }
+
+    #[test]
+    fn validate_cross_checks_stored_shape() {
+        let mut curve = CurveFile::fixture(&[100.0, 0.0, 0.0], &[1.0], &[1.0, 2.0, 3.0], &[90.0, 110.0, 100.0]);
+        // Older files without a shape are still valid.
+        assert!(validate_curve(&curve).is_empty());
+
+        curve.shape = Some("humped".to_string());
+        assert!(validate_curve(&curve).is_empty());
+
+        curve.shape = Some("increasing".to_string());
+        let problems = validate_curve(&curve);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("'increasing' does not match grid shape 'humped'"));
+    }
+
+    #[test]
+    fn grid_interp_hits_knots_and_follows_policy() {
+        let mut curve = CurveFile::fixture(&[100.0, 0.0, 0.0], &[1.0], &[1.0, 3.0, 7.0], &[100.1, 120.3, 110.7]);
+
+        assert_eq!(curve.interp(3.0, GridExtrapolation::Clamp).unwrap(), 120.3);
+        assert_eq!(curve.interp(7.0, GridExtrapolation::Linear).unwrap(), 110.7);
+        assert!((curve.interp(2.0, GridExtrapolation::Clamp).unwrap() - 110.2).abs() < 1e-9);
+        assert_eq!(curve.interp(0.5, GridExtrapolation::Clamp).unwrap(), 100.1);
+        assert_eq!(curve.interp(9.0, GridExtrapolation::Clamp).unwrap(), 110.7);
+        assert!((curve.interp(0.0, GridExtrapolation::Linear).unwrap() - 90.0).abs() < 1e-9);
+        assert!((curve.interp(11.0, GridExtrapolation::Linear).unwrap() - 101.1).abs() < 1e-9);
+
+        curve.grid = CurveGrid { tenor_years: vec![1.0], y: vec![100.0] };
+        assert!(curve.interp(1.0, GridExtrapolation::Clamp).is_err());
+        curve.grid = CurveGrid { tenor_years: vec![], y: vec![] };
+        assert!(curve.interp(1.0, GridExtrapolation::Clamp).is_err());
+    }
+
+    #[test]
+    fn build_grid_cap_bounds_the_upper_end_only_when_tighter() {
+        let fit = FitResult {
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, -20.0, 30.0],
+                taus: vec![2.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 0 },
+        };
+        // Large cap: same grid as no cap.
+        assert_eq!(build_grid(&fit, 1.0, 10.0, Some(1e6), 11), build_grid(&fit, 1.0, 10.0, None, 11));
+
+        // A single observed tenor falls back to the 0.25-30y grid; a zero cap
+        // stops the upper end at the observed tenor.
+        let (tenors, _) = build_grid(&fit, 5.0, 5.0, None, 3);
+        assert_eq!(tenors, vec![0.25, 15.125, 30.0]);
+        let (tenors, y) = build_grid(&fit, 5.0, 5.0, Some(5.0), 3);
+        assert_eq!(tenors, vec![0.25, 2.625, 5.0]);
+        assert_eq!(y[2], predict(ModelKind::Ns, 5.0, &fit.model.betas, &fit.model.taus));
+    }
+
+    #[test]
+    fn pillars_default_to_standard_tenors_and_flag_extrapolation() {
+        use clap::Parser;
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "--pillars"]).unwrap();
+        assert_eq!(args.pillars.as_deref(), Some(&[1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 20.0, 30.0][..]));
+        let mut config = crate::app::fit_config_from_args(&args);
+        let run = crate::app::pipeline::run_fit_with_snapshot(&config, crate::data::sample::canned_snapshot()).unwrap();
+
+        let table = pillar_table(&run, &config).unwrap();
+        let best = &run.selection.best.model;
+        assert!(table.pillars.iter().all(|p| p.y == best.eval(p.tenor)));
+        let (lo, hi) = table.fitted_range;
+        assert!(table.pillars.iter().all(|p| p.out_of_range == (p.tenor < lo || p.tenor > hi)));
+        assert!(table.pillars.last().unwrap().out_of_range, "30y lies beyond the last observed tenor {hi}");
+
+        let text = crate::report::format_pillars(&table, config.locale);
+        assert!(text.starts_with("Pillars: BBB curve ("), "{text}");
+        assert!(text.contains(", as-of 2025-01-02, y in bp\n") && text.contains("* extrapolated"), "{text}");
+
+        config.pillars = vec![5.0, -1.0];
+        assert_eq!(pillar_table(&run, &config).unwrap_err().exit_code(), 2);
+    }
+}
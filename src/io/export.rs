@@ -1,38 +1,69 @@
 //! Export per-bond results to CSV.
 //!
 //! The export is meant to be easy to consume in spreadsheets or downstream scripts.
+//!
+//! `append_timeseries_csv` writes one row per fit (selected model + parameters) and
+//! appends across runs, so repeated fits over different as-of dates build a tidy
+//! time series.
+//!
+//! `write_stats_csv` (`--export-stats`) writes a one-row summary of the
+//! residual distribution (`ResidualStats`).
+//!
+//! `write_sample_csv` (`--export-sample`) saves the bonds a fit was drawn from
+//! in the `--sample-from-points` format, so the exact sample can be refitted.
+//!
+//! The results and time-series CSVs (and the curve JSON) go through
+//! `write_atomic`, so an interrupted write never leaves a truncated file.
 
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-use crate::domain::{BondResidual, FitConfig};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::data::SampleData;
+use crate::domain::{BondResidual, FitResult, ModelKind, RankBy};
 use crate::error::AppError;
+use crate::fit::fitter::TauCandidate;
+use crate::fit::selection::ModelMetrics;
 use crate::io::ingest::InputSpec;
+use crate::report::format::{rank_key, Rankings};
+use crate::report::stats::ResidualStats;
+
+/// Header of the per-bond results CSV.
+///
+/// `asof_date` and `rating` discriminate rows when several fits share one file;
+/// `highlight` is `cheap`/`rich` for bonds on the `--top-cheap`/`--top-rich`
+/// lists (empty otherwise).
+/// With `--round-tenors` a trailing `tenor_rounded` column is added
+/// ([`ROUNDED_TENOR_COLUMN`]); `tenor_years` always holds the fitted tenor.
+pub const RESULTS_HEADER: &str =
+    "id,asof_date,maturity_date,tenor_years,y_kind,y_unit,y_obs,y_fit,residual,weight,rating,oas,richness_pct,residual_se_units,signal,highlight";
+
+/// Extra results column written under `--round-tenors`.
+pub const ROUNDED_TENOR_COLUMN: &str = "tenor_rounded";
 
 /// Write per-bond results to a CSV file.
+///
+/// With `append`, rows are added to an existing file (header written only when
+/// the file is new or empty) so batch cells accumulate into one CSV. Each call
+/// replaces the file atomically, so an interrupted batch leaves whole cells
+/// behind rather than a torn row.
 pub fn write_results_csv(
     path: &Path,
     residuals: &[BondResidual],
+    rankings: &Rankings,
     input_spec: &InputSpec,
-    config: &FitConfig,
+    round_tenors: Option<f64>,
+    append: bool,
 ) -> Result<(), AppError> {
-    let mut file = File::create(path)
-        .map_err(|e| AppError::new(2, format!("Failed to create export CSV '{}': {e}", path.display())))?;
-
-    // Header
-    writeln!(
-        file,
-        "id,asof_date,maturity_date,tenor_years,y_kind,y_unit,y_obs,y_fit,residual,weight,rating,oas"
-    )
-    .map_err(|e| AppError::new(2, format!("Failed to write export CSV header: {e}")))?;
-
+    let y_kind = format!("{:?}", input_spec.y_kind).to_lowercase();
+    let mut block = String::new();
     for r in residuals {
         let p = &r.point;
-        let y_kind = format!("{:?}", input_spec.y_kind).to_lowercase();
-        writeln!(
-            file,
-            "{},{},{},{:.10},{},{},{:.4},{:.4},{:.4},{:.10},{},{}",
+        block.push_str(&format!(
+            "{},{},{},{:.10},{},{},{:.4},{:.4},{:.4},{:.10},{},{},{:.4},{},{:.6},{}",
             p.id,
             p.asof_date,
             p.maturity_date,
@@ -45,12 +76,536 @@ pub fn write_results_csv(
             p.weight,
             p.meta.rating.as_deref().unwrap_or(""),
             p.extras.oas.map(|v| format!("{v:.10}")).unwrap_or_default(),
+            r.richness_pct,
+            r.residual_se_units.map(|v| format!("{v:.6}")).unwrap_or_default(),
+            r.signal,
+            rankings.highlight(&p.id).unwrap_or(""),
+        ));
+        if round_tenors.is_some() {
+            block.push_str(&format!(",{:.10}", p.reported_tenor(round_tenors)));
+        }
+        block.push('\n');
+    }
+
+    let header = match round_tenors {
+        Some(_) => format!("{RESULTS_HEADER},{ROUNDED_TENOR_COLUMN}"),
+        None => RESULTS_HEADER.to_string(),
+    };
+    let mut contents = csv_prefix(path, &header, append, "export")?;
+    contents.extend_from_slice(block.as_bytes());
+    write_atomic(path, &contents, "export CSV")
+}
+
+/// Contents a CSV write starts from: the existing file under `append`, or just
+/// `header`.
+///
+/// With `append` the header is added when the file is new or empty; an
+/// existing file with a different header is rejected rather than silently
+/// producing a ragged CSV. The old rows are kept as raw bytes (they need not
+/// be UTF-8), and a file that exists but cannot be read is an error: the
+/// rewrite would otherwise drop its rows.
+fn csv_prefix(path: &Path, header: &str, append: bool, what: &str) -> Result<Vec<u8>, AppError> {
+    let existing = if append {
+        match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(AppError::new(
+                    2,
+                    format!("Cannot append to {what} CSV '{}': failed to read it: {e}", path.display()),
+                ))
+            }
+        }
+    } else {
+        Vec::new()
+    };
+    if existing.iter().all(u8::is_ascii_whitespace) {
+        return Ok(format!("{header}\n").into_bytes());
+    }
+
+    let first = existing.split(|&b| b == b'\n').next().unwrap_or_default();
+    if String::from_utf8_lossy(first).trim() != header {
+        return Err(AppError::new(
+            2,
+            format!("Existing {what} CSV '{}' has an unexpected header (expected {header}).", path.display()),
+        ));
+    }
+    let mut contents = existing;
+    if contents.last() != Some(&b'\n') {
+        contents.push(b'\n');
+    }
+    Ok(contents)
+}
+
+/// Replace `path` with `contents` atomically.
+///
+/// The bytes go to a temp file in the destination's own directory (so the
+/// rename never crosses filesystems), which is synced and renamed over
+/// `path`. On any failure the temp file is removed and an existing `path` is
+/// left untouched.
+pub fn write_atomic(path: &Path, contents: &[u8], what: &str) -> Result<(), AppError> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .ok_or_else(|| AppError::new(2, format!("Invalid {what} path '{}'.", path.display())))?;
+    let tmp = dir.join(format!(".{}.tmp-{}", name.to_string_lossy(), std::process::id()));
+
+    let mut file = File::create(&tmp).map_err(|e| {
+        AppError::new(
+            2,
+            format!("Cannot write {what} '{}': unable to create a file in '{}': {e}", path.display(), dir.display()),
         )
-        .map_err(|e| AppError::new(2, format!("Failed to write export CSV row: {e}")))?;
+    })?;
+    let written = file.write_all(contents).and_then(|_| file.sync_all());
+    drop(file);
+    if let Err(e) = written.and_then(|_| std::fs::rename(&tmp, path)) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(AppError::new(2, format!("Failed to write {what} '{}': {e}", path.display())));
     }
+    Ok(())
+}
 
-    // Suppress unused warning
-    let _ = config;
+/// Write a tau-search landscape: one row per evaluated candidate, taus in
+/// separate columns, and the selected candidate flagged with `best=1`.
+pub fn write_landscape_csv(path: &Path, model: ModelKind, landscape: &[TauCandidate]) -> Result<(), AppError> {
+    let mut file = File::create(path)
+        .map_err(|e| AppError::new(2, format!("Failed to create landscape CSV '{}': {e}", path.display())))?;
 
+    let mut header: Vec<String> = vec!["model".to_string()];
+    header.extend((1..=model.tau_len()).map(|i| format!("tau{i}")));
+    header.extend(["sse".to_string(), "best".to_string()]);
+    writeln!(file, "{}", header.join(","))
+        .map_err(|e| AppError::new(2, format!("Failed to write landscape CSV header: {e}")))?;
+
+    let name = model.as_str();
+    for c in landscape {
+        let taus: Vec<String> = c.taus.iter().map(|t| format!("{t:.10}")).collect();
+        writeln!(file, "{name},{},{:.10},{}", taus.join(","), c.sse, u8::from(c.best))
+            .map_err(|e| AppError::new(2, format!("Failed to write landscape CSV row: {e}")))?;
+    }
+
+    Ok(())
+}
+
+/// One `--format ndjson` line.
+#[derive(Debug, Serialize)]
+struct NdjsonResidual<'a> {
+    /// `cheap` (ranking key > 0), `rich` (< 0), or `fair`.
+    side: &'static str,
+    /// 1-based position, cheapest first.
+    rank: usize,
+    /// `cheap`/`rich` when on the `--top-cheap`/`--top-rich` list.
+    highlight: Option<&'static str>,
+    id: &'a str,
+    asof_date: NaiveDate,
+    tenor_years: f64,
+    y_unit: &'a str,
+    y_obs: f64,
+    y_fit: f64,
+    residual: f64,
+    residual_se_units: Option<f64>,
+    richness_pct: f64,
+    signal: f64,
+    weight: f64,
+    rating: Option<&'a str>,
+}
+
+/// Stream every residual to `out` as newline-delimited JSON, ordered cheapest
+/// to richest by `rank_by`, flushing after each line so a consumer sees bonds
+/// as they are written.
+pub fn write_residuals_ndjson<W: Write>(
+    mut out: W,
+    residuals: &[BondResidual],
+    rankings: &Rankings,
+    input_spec: &InputSpec,
+    rank_by: RankBy,
+) -> std::io::Result<()> {
+    let mut ordered: Vec<&BondResidual> = residuals.iter().collect();
+    ordered.sort_by(|a, b| rank_key(b, rank_by).total_cmp(&rank_key(a, rank_by)));
+    for (i, r) in ordered.into_iter().enumerate() {
+        let key = rank_key(r, rank_by);
+        let row = NdjsonResidual {
+            side: if key > 0.0 {
+                "cheap"
+            } else if key < 0.0 {
+                "rich"
+            } else {
+                "fair"
+            },
+            rank: i + 1,
+            highlight: rankings.highlight(&r.point.id),
+            id: &r.point.id,
+            asof_date: r.point.asof_date,
+            tenor_years: r.point.tenor,
+            y_unit: input_spec.y_unit_label(),
+            y_obs: r.point.y_obs,
+            y_fit: r.y_fit,
+            residual: r.residual,
+            residual_se_units: r.residual_se_units,
+            richness_pct: r.richness_pct,
+            signal: r.signal,
+            weight: r.point.weight,
+            rating: r.point.meta.rating.as_deref(),
+        };
+        serde_json::to_writer(&mut out, &row)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
     Ok(())
 }
+
+/// Header of the `--export-sample` CSV. `--sample-from-points` reads every
+/// column but `baseline`, which is kept for auditing.
+pub const SAMPLE_HEADER: &str = "id,tenor,oas,weight,rating,issuer,dv01,baseline";
+
+/// Write the run's sample (before tenor bounds and weight files are applied)
+/// as a bond CSV. Floats use shortest round-trip formatting, so refitting the
+/// file with `--sample-from-points` reproduces the same points bit for bit.
+pub fn write_sample_csv(path: &Path, sample: &SampleData) -> Result<(), AppError> {
+    let mut out = format!("{SAMPLE_HEADER}\n");
+    for (i, p) in sample.points.iter().enumerate() {
+        let opt = |v: Option<f64>| v.map(|v| v.to_string()).unwrap_or_default();
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            p.id,
+            p.tenor,
+            p.y_obs,
+            p.weight,
+            p.meta.rating.as_deref().unwrap_or(""),
+            p.meta.issuer.as_deref().unwrap_or(""),
+            opt(p.extras.dv01),
+            opt(sample.baseline.get(i).copied()),
+        ));
+    }
+    write_atomic(path, out.as_bytes(), "sample CSV")
+}
+
+/// Header of the `--export-stats` CSV.
+pub const STATS_HEADER: &str = "asof_date,rating,model,n,rmse_unweighted,mae,mean,std,skew,kurtosis,\
+worst_cheap_id,worst_cheap_tenor,worst_cheap_residual,worst_rich_id,worst_rich_tenor,worst_rich_residual";
+
+/// Write `stats` as a header plus one row. Undefined values (moments of a
+/// too-small sample, a missing cheap or rich side) are left blank.
+pub fn write_stats_csv(
+    path: &Path,
+    stats: &ResidualStats,
+    input_spec: &InputSpec,
+    rating: &str,
+    model: ModelKind,
+) -> Result<(), AppError> {
+    let num = |v: Option<f64>| v.map_or(String::new(), |v| format!("{v:.10}"));
+    let side = |b: Option<&crate::report::stats::ExtremeBond>| match b {
+        Some(b) => format!("{},{:.6},{:.10}", b.id, b.tenor, b.residual),
+        None => ",,".to_string(),
+    };
+    let row = format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+        input_spec.asof_date,
+        rating,
+        model.as_str(),
+        stats.n,
+        num(stats.rmse_unweighted),
+        num(stats.mae),
+        num(stats.mean),
+        num(stats.std),
+        num(stats.skew),
+        num(stats.kurtosis),
+        side(stats.worst_cheap.as_ref()),
+        side(stats.worst_rich.as_ref()),
+    );
+    write_atomic(path, format!("{STATS_HEADER}\n{row}").as_bytes(), "stats CSV")
+}
+
+/// Header of the `--export-models` CSV.
+const MODELS_HEADER: &str = "model,n,params,sse,rmse,mae,bic,aic,r2,selected,skip_reason";
+
+/// Write the `--compare-models` metrics: JSON when `path` ends in `.json`, CSV
+/// otherwise. Skipped models keep their row with empty metrics and a reason.
+pub fn write_model_comparison(path: &Path, rows: &[ModelMetrics]) -> Result<(), AppError> {
+    let is_json = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let body = if is_json {
+        serde_json::to_string_pretty(rows)
+            .map_err(|e| AppError::new(1, format!("Failed to serialize model comparison: {e}")))?
+    } else {
+        let num = |v: Option<f64>| v.map_or(String::new(), |v| format!("{v:.10}"));
+        let mut out = format!("{MODELS_HEADER}\n");
+        for r in rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{}\n",
+                r.model.as_str(),
+                r.n,
+                r.params,
+                num(r.sse),
+                num(r.rmse),
+                num(r.mae),
+                num(r.bic),
+                num(r.aic),
+                num(r.r2),
+                u8::from(r.selected),
+                r.skip_reason.as_deref().map(|s| format!("\"{}\"", s.replace('"', "\"\""))).unwrap_or_default(),
+            ));
+        }
+        out
+    };
+    std::fs::write(path, body)
+        .map_err(|e| AppError::new(2, format!("Failed to write model comparison '{}': {e}", path.display())))
+}
+
+/// Model kinds whose parameters define the time-series column set.
+const TIMESERIES_MODELS: [ModelKind; 4] = [ModelKind::Ns, ModelKind::NssShared, ModelKind::Nss, ModelKind::Nssc];
+
+/// Maximum (beta, tau) counts across `TIMESERIES_MODELS`.
+fn timeseries_widths() -> (usize, usize) {
+    let betas = TIMESERIES_MODELS.iter().map(|k| k.beta_len()).max().unwrap_or(0);
+    let taus = TIMESERIES_MODELS.iter().map(|k| k.tau_len()).max().unwrap_or(0);
+    (betas, taus)
+}
+
+/// Header for the time-series CSV: the union of every model's parameters, so the
+/// column set is the same whichever model a given date selects.
+pub fn timeseries_header() -> String {
+    let (betas, taus) = timeseries_widths();
+
+    let mut cols: Vec<String> = ["date", "model", "n", "rmse", "bic"].iter().map(|s| s.to_string()).collect();
+    cols.extend((0..betas).map(|i| format!("beta{i}")));
+    cols.extend((1..=taus).map(|i| format!("tau{i}")));
+    cols.join(",")
+}
+
+/// Append one row (as-of date + selected model) to a time-series CSV.
+///
+/// The header is written when the file is new or empty; an existing file with a
+/// different header is rejected rather than silently producing a ragged CSV.
+/// Parameters a model does not have are left as empty cells.
+pub fn append_timeseries_csv(path: &Path, date: NaiveDate, fit: &FitResult) -> Result<(), AppError> {
+    let mut contents = csv_prefix(path, &timeseries_header(), true, "time-series")?;
+    contents.extend_from_slice(timeseries_row(date, fit).as_bytes());
+    contents.push(b'\n');
+    write_atomic(path, &contents, "time-series CSV")
+}
+
+fn timeseries_row(date: NaiveDate, fit: &FitResult) -> String {
+    let (betas, taus) = timeseries_widths();
+    let cell = |v: Option<&f64>| v.map(|x| format!("{x:.10}")).unwrap_or_default();
+
+    let mut cols = vec![
+        date.to_string(),
+        fit.model.name.as_str().to_string(),
+        fit.quality.n.to_string(),
+        format!("{:.10}", fit.quality.rmse),
+        format!("{:.10}", fit.quality.bic),
+    ];
+    cols.extend((0..betas).map(|i| cell(fit.model.betas.get(i))));
+    cols.extend((0..taus).map(|i| cell(fit.model.taus.get(i))));
+    cols.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CurveModel, FitQuality, YKind, YUnit};
+
+    fn fit(kind: ModelKind, betas: Vec<f64>, taus: Vec<f64>) -> FitResult {
+        FitResult {
+            model: CurveModel {
+                name: kind,
+                display_name: kind.display_name().to_string(),
+                betas,
+                taus,
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: 1.0, rmse: 0.5, bic: -3.0, n: 40 },
+        }
+    }
+
+    #[test]
+    fn stats_csv_is_rectangular_with_blank_undefined_moments() {
+        use crate::report::stats::residual_stats;
+
+        let path = std::env::temp_dir().join(format!("rv-stats-{}.csv", std::process::id()));
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let spec = InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: YUnit::Bp };
+        let residual = |id: &str, r: f64| BondResidual::fixture(id, 5.0, r);
+
+        let two = [residual("CHEAP", 3.0), residual("RICH", -1.0)];
+        write_stats_csv(&path, &residual_stats(&two), &spec, "BBB", ModelKind::Ns).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], STATS_HEADER);
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields.len(), STATS_HEADER.split(',').count());
+        assert_eq!(&fields[..4], ["2025-01-02", "BBB", "ns", "2"]);
+        // n = 2: std is defined, skew and kurtosis are blank.
+        assert!(!fields[7].is_empty() && fields[8].is_empty() && fields[9].is_empty(), "{}", lines[1]);
+        assert_eq!((fields[10], fields[13]), ("CHEAP", "RICH"));
+    }
+
+    #[test]
+    fn timeseries_rows_are_rectangular_across_models() {
+        let path = std::env::temp_dir().join(format!("rv-timeseries-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let d1 = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        append_timeseries_csv(&path, d1, &fit(ModelKind::Ns, vec![1.0, 2.0, 3.0], vec![2.0])).unwrap();
+        append_timeseries_csv(
+            &path,
+            d2,
+            &fit(ModelKind::Nssc, vec![1.0, 2.0, 3.0, 4.0, 5.0], vec![1.0, 4.0, 9.0]),
+        )
+        .unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        append_timeseries_csv(&path, d3, &fit(ModelKind::NssShared, vec![1.0, 2.0, 3.0, 4.0], vec![2.0])).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "date,model,n,rmse,bic,beta0,beta1,beta2,beta3,beta4,tau1,tau2,tau3");
+        let widths: Vec<usize> = lines.iter().map(|l| l.split(',').count()).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]), "{widths:?}");
+        assert!(lines[1].starts_with("2025-01-02,ns,40,"));
+        assert!(lines[1].ends_with(",,,2.0000000000,,"), "{}", lines[1]);
+        // Model names match the serde names used in curve JSON.
+        assert!(lines[3].starts_with("2025-01-06,nss-shared,"), "{}", lines[3]);
+        assert_eq!(serde_json::to_value(ModelKind::NssShared).unwrap(), ModelKind::NssShared.as_str());
+    }
+
+    #[test]
+    fn results_append_accumulates_cells_under_one_header() {
+        let path = std::env::temp_dir().join(format!("rv-results-append-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let spec = InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: YUnit::Bp };
+        let cell = |rating: &str| {
+            let mut r = BondResidual::fixture(&format!("{rating}-1"), 5.0, 1.0);
+            r.point.meta.rating = Some(rating.to_string());
+            vec![r]
+        };
+
+        let none = Rankings::default();
+        write_results_csv(&path, &cell("AA"), &none, &spec, None, false).unwrap();
+        write_results_csv(&path, &cell("BBB"), &none, &spec, None, true).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], RESULTS_HEADER);
+        assert!(lines[2].starts_with("BBB-1,2025-01-01,"));
+
+        // Overwrite mode starts over; a foreign header refuses to append.
+        write_results_csv(&path, &cell("A"), &none, &spec, None, false).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        std::fs::write(&path, "a,b\n").unwrap();
+        assert!(write_results_csv(&path, &cell("A"), &none, &spec, None, true).is_err());
+
+        // Non-UTF-8 rows (a latin-1 issuer) survive an append byte for byte.
+        let latin1 = [format!("{RESULTS_HEADER}\n").into_bytes(), b"X-1,caf\xe9\n".to_vec()].concat();
+        std::fs::write(&path, &latin1).unwrap();
+        write_results_csv(&path, &cell("A"), &none, &spec, None, true).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(&latin1) && bytes.len() > latin1.len());
+
+        // A path that exists but can't be read is an error, not a new file.
+        let dir = std::env::temp_dir().join(format!("rv-results-unreadable-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let err = write_results_csv(&dir, &cell("A"), &none, &spec, None, true).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("failed to read"), "{err}");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // --round-tenors adds a rounded column and keeps the fitted tenor.
+        let mut odd = cell("A");
+        odd[0].point.tenor = 5.137;
+        write_results_csv(&path, &odd, &none, &spec, Some(0.25), false).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], format!("{RESULTS_HEADER},{ROUNDED_TENOR_COLUMN}"));
+        assert!(lines[1].contains(",5.1370000000,") && lines[1].ends_with(",5.2500000000"), "{}", lines[1]);
+        assert!(write_results_csv(&path, &odd, &none, &spec, None, true).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn atomic_write_replaces_whole_files_and_cleans_up_on_failure() {
+        let dir = std::env::temp_dir().join(format!("rv-atomic-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let leftovers = || {
+            std::fs::read_dir(&dir)
+                .unwrap()
+                .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().contains(".tmp-"))
+                .count()
+        };
+
+        let path = dir.join("out.csv");
+        write_atomic(&path, b"old\n", "test").unwrap();
+        write_atomic(&path, b"new\n", "test").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+
+        // The rename fails onto a directory: error, and no temp file left behind.
+        let blocked = dir.join("blocked");
+        std::fs::create_dir(&blocked).unwrap();
+        std::fs::write(blocked.join("keep"), "x").unwrap();
+        assert_eq!(write_atomic(&blocked, b"data", "test").unwrap_err().exit_code(), 2);
+        assert_eq!(leftovers(), 0);
+
+        // An unusable destination directory is named in the error.
+        let err = write_atomic(&dir.join("missing").join("out.csv"), b"data", "test").unwrap_err();
+        assert!(err.to_string().contains("unable to create a file in"), "{err}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ndjson_streams_one_flushed_line_per_bond_cheapest_first() {
+        /// Records the buffered bytes at each flush.
+        #[derive(Default)]
+        struct Flushes {
+            buf: Vec<u8>,
+            seen: Vec<usize>,
+        }
+        impl Write for Flushes {
+            fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+                self.buf.extend_from_slice(data);
+                Ok(data.len())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.seen.push(self.buf.len());
+                Ok(())
+            }
+        }
+
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let residuals: Vec<BondResidual> = [("R", -2.0), ("C", 3.0), ("F", 0.0)]
+            .iter()
+            .map(|&(id, residual)| BondResidual::fixture(id, 5.0, residual))
+            .collect();
+        let spec = InputSpec { asof_date: asof, y_kind: YKind::Oas, y_unit: YUnit::Bp };
+
+        let mut out = Flushes::default();
+        let rankings = crate::report::rank_cheap_rich(&residuals, 1, 0, RankBy::Residual);
+        write_residuals_ndjson(&mut out, &residuals, &rankings, &spec, RankBy::Residual).unwrap();
+        let text = String::from_utf8(out.buf.clone()).unwrap();
+        let lines: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        let order: Vec<(&str, &str, u64)> = lines
+            .iter()
+            .map(|v| (v["id"].as_str().unwrap(), v["side"].as_str().unwrap(), v["rank"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(order, vec![("C", "cheap", 1), ("F", "fair", 2), ("R", "rich", 3)]);
+        assert_eq!(lines[0]["y_unit"], "bp");
+        assert!(lines[0]["rating"].is_null());
+        assert_eq!((lines[0]["highlight"].as_str(), lines[2]["highlight"].as_str()), (Some("cheap"), None));
+        // Flushed once per line, each time at a line boundary.
+        assert_eq!(out.seen.len(), 3);
+        assert!(out.seen.iter().all(|&n| out.buf[n - 1] == b'\n'));
+    }
+}
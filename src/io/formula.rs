@@ -0,0 +1,286 @@
+//! Fitted curve as a paste-ready formula (`--export-formula`).
+//!
+//! Every model in the family is a sum of the same three kinds of term:
+//!
+//! ```text
+//! y(t) = β0 + β1·f1(t, τ1) + β2·f2(t, τ1) + Σ_{k≥3} βk·f2(t, τ_{k-1})
+//! ```
+//!
+//! so we generate code from that term list rather than per model. Coefficients
+//! are written with Rust's shortest round-trip formatting, so the emitted
+//! formula reproduces `predict` to floating-point tolerance.
+
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use crate::domain::{CurveModel, FormulaLang, ModelKind, RatingBand, YTransform, YUnit};
+use crate::error::AppError;
+use crate::models::SHARED_TAU_RATIO;
+
+/// Basis function multiplying a beta.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TermKind {
+    Level,
+    F1(f64),
+    F2(f64),
+}
+
+/// `(beta, term)` pairs of `model`, mirroring `models::predict`.
+fn formula_terms(model: &CurveModel) -> Vec<(f64, TermKind)> {
+    model
+        .betas
+        .iter()
+        .enumerate()
+        .map(|(i, &beta)| {
+            let term = match i {
+                0 => TermKind::Level,
+                1 => TermKind::F1(model.taus[0]),
+                2 => TermKind::F2(model.taus[0]),
+                3 if model.name == ModelKind::NssShared => TermKind::F2(SHARED_TAU_RATIO * model.taus[0]),
+                k => TermKind::F2(model.taus[k - 2]),
+            };
+            (beta, term)
+        })
+        .collect()
+}
+
+/// Render `model` in `lang`, labelled with its kind, rating, and as-of date.
+pub fn render_formula(model: &CurveModel, lang: FormulaLang, rating: RatingBand, asof: NaiveDate, unit: YUnit) -> String {
+    let label = format!(
+        "rv fitted curve: {} ({}), rating {}, as-of {asof}, y in {} (OAS), t = tenor in years",
+        model.display_name,
+        model.name.as_str(),
+        rating.display_name(),
+        unit.label()
+    );
+    let terms = formula_terms(model);
+    // A log-fitted curve is the exponential of the NS sum.
+    let log = model.y_transform == YTransform::Log;
+    let label = if log { format!("{label}; fitted on ln(y)") } else { label };
+    match lang {
+        FormulaLang::Python => {
+            let body: Vec<String> = terms
+                .iter()
+                .map(|(beta, term)| match term {
+                    TermKind::Level => format!("{beta:?}"),
+                    TermKind::F1(tau) => format!("{beta:?} * f1(t, {tau:?})"),
+                    TermKind::F2(tau) => format!("{beta:?} * f2(t, {tau:?})"),
+                })
+                .collect();
+            format!(
+                "# {label}\nimport math\n\n\
+                 def f1(t, tau):\n    x = max(t, 1e-12) / tau\n    return -math.expm1(-x) / x\n\n\
+                 def f2(t, tau):\n    x = max(t, 1e-12) / tau\n    return f1(t, tau) - math.exp(-x)\n\n\
+                 def rv_curve(t):\n    return {}(\n        {}\n    )\n",
+                if log { "math.exp" } else { "" },
+                body.join("\n        + ")
+            )
+        }
+        FormulaLang::Excel => {
+            // f1 / f2 are inlined per term; `t` is the tenor cell (A2).
+            let f1 = |tau: f64| format!("(1-EXP(-t/{tau:?}))/(t/{tau:?})");
+            let body: Vec<String> = terms
+                .iter()
+                .map(|(beta, term)| match term {
+                    TermKind::Level => format!("{beta:?}"),
+                    TermKind::F1(tau) => format!("{beta:?}*{}", f1(*tau)),
+                    TermKind::F2(tau) => format!("{beta:?}*({}-EXP(-t/{tau:?}))", f1(*tau)),
+                })
+                .collect();
+            let sum = body.join("+");
+            format!(
+                "{label}\nPaste into any cell; the tenor is read from A2.\n=LET(t,MAX(A2,1E-12),{})\n",
+                if log { format!("EXP({sum})") } else { sum }
+            )
+        }
+    }
+}
+
+/// Default output file for `--export-formula` without `--formula-out`.
+pub fn default_formula_path(lang: FormulaLang, rating: RatingBand, asof: NaiveDate) -> String {
+    let ext = match lang {
+        FormulaLang::Python => "py",
+        FormulaLang::Excel => "txt",
+    };
+    format!("rv-formula-{}-{asof}.{ext}", rating.display_name())
+}
+
+/// Write the rendered formula to `path`.
+pub fn write_formula(path: &Path, formula: &str) -> Result<(), AppError> {
+    std::fs::write(path, formula)
+        .map_err(|e| AppError::new(2, format!("Failed to write formula '{}': {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{f1, f2};
+    use crate::models::predict;
+
+    #[test]
+    fn formula_terms_reproduce_predict_and_label_the_curve() {
+        let model = CurveModel {
+            name: ModelKind::Nssc,
+            display_name: "NSSC".to_string(),
+            betas: vec![120.5, -30.25, 41.0, 7.125, -3.0625],
+            taus: vec![1.7, 6.3, 0.45],
+            y_transform: crate::domain::YTransform::None,
+            splice: None,
+        };
+        let shared = CurveModel {
+            name: ModelKind::NssShared,
+            display_name: "NSS (shared τ)".to_string(),
+            betas: vec![120.5, -30.25, 41.0, 7.125],
+            taus: vec![1.7],
+            y_transform: crate::domain::YTransform::None,
+            splice: None,
+        };
+        for m in [&model, &shared] {
+            for &t in &[0.25, 1.0, 4.5, 12.0, 30.0] {
+                let y: f64 = formula_terms(m)
+                    .iter()
+                    .map(|&(beta, term)| match term {
+                        TermKind::Level => beta,
+                        TermKind::F1(tau) => beta * f1(t, tau),
+                        TermKind::F2(tau) => beta * f2(t, tau),
+                    })
+                    .sum();
+                let expected = predict(m.name, t, &m.betas, &m.taus);
+                assert!((y - expected).abs() < 1e-12, "{:?} t={t}: {y} vs {expected}", m.name);
+            }
+        }
+
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let py = render_formula(&model, FormulaLang::Python, RatingBand::BBB, asof, YUnit::Bp);
+        assert!(py.starts_with("# rv fitted curve: NSSC (nssc), rating BBB, as-of 2025-01-02"), "{py}");
+        assert!(py.contains("-3.0625 * f2(t, 0.45)"), "{py}");
+        let xl = render_formula(&model, FormulaLang::Excel, RatingBand::BBB, asof, YUnit::Bp);
+        assert!(xl.lines().last().unwrap().starts_with("=LET(t,MAX(A2,1E-12),120.5+"), "{xl}");
+
+        let logged = CurveModel { y_transform: YTransform::Log, ..shared.clone() };
+        let py = render_formula(&logged, FormulaLang::Python, RatingBand::BBB, asof, YUnit::Bp);
+        assert!(py.contains("fitted on ln(y)") && py.contains("return math.exp(\n"), "{py}");
+        let xl = render_formula(&logged, FormulaLang::Excel, RatingBand::BBB, asof, YUnit::Bp);
+        assert!(xl.lines().last().unwrap().starts_with("=LET(t,MAX(A2,1E-12),EXP(120.5+"), "{xl}");
+
+        // The rendered text itself evaluates to the curve.
+        let logged = CurveModel { betas: vec![4.8, -0.3, 0.5, 0.1], ..logged };
+        for m in [&model, &shared, &logged] {
+            let py = render_formula(m, FormulaLang::Python, RatingBand::BBB, asof, YUnit::Bp);
+            let py = py.split("def rv_curve(t):\n    return ").nth(1).unwrap();
+            let xl = render_formula(m, FormulaLang::Excel, RatingBand::BBB, asof, YUnit::Bp);
+            let xl = xl.lines().last().unwrap().strip_prefix("=LET(t,MAX(A2,1E-12),").unwrap().strip_suffix(')').unwrap();
+            for &t in &[0.25, 1.0, 4.5, 12.0, 30.0] {
+                let expected = m.eval(t);
+                for (lang, text) in [("python", py), ("excel", xl)] {
+                    let y = Expr { s: text.as_bytes(), pos: 0, t }.parse();
+                    assert!((y - expected).abs() < 1e-9 * expected.abs(), "{:?} {lang} t={t}: {y} vs {expected}", m.name);
+                }
+            }
+        }
+    }
+
+    /// Evaluator for the arithmetic the renderers emit: numbers, `t`,
+    /// `+ - * /`, parentheses, and the calls `EXP`/`math.exp`, `f1`, `f2`.
+    struct Expr<'a> {
+        s: &'a [u8],
+        pos: usize,
+        t: f64,
+    }
+
+    impl Expr<'_> {
+        fn parse(mut self) -> f64 {
+            let v = self.sum();
+            self.skip_ws();
+            assert_eq!(self.pos, self.s.len(), "trailing input at {}", self.pos);
+            v
+        }
+
+        fn skip_ws(&mut self) {
+            while self.s.get(self.pos).is_some_and(|c| c.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+        }
+
+        fn eat(&mut self, c: u8) -> bool {
+            self.skip_ws();
+            let hit = self.s.get(self.pos) == Some(&c);
+            self.pos += usize::from(hit);
+            hit
+        }
+
+        fn sum(&mut self) -> f64 {
+            let mut v = self.product();
+            loop {
+                if self.eat(b'+') {
+                    v += self.product();
+                } else if self.eat(b'-') {
+                    v -= self.product();
+                } else {
+                    return v;
+                }
+            }
+        }
+
+        fn product(&mut self) -> f64 {
+            let mut v = self.factor();
+            loop {
+                if self.eat(b'*') {
+                    v *= self.factor();
+                } else if self.eat(b'/') {
+                    v /= self.factor();
+                } else {
+                    return v;
+                }
+            }
+        }
+
+        fn factor(&mut self) -> f64 {
+            if self.eat(b'-') {
+                return -self.factor();
+            }
+            if self.eat(b'(') {
+                let v = self.sum();
+                assert!(self.eat(b')'));
+                return v;
+            }
+            let start = self.pos;
+            let c = self.s[start];
+            if c.is_ascii_digit() || c == b'.' {
+                while self.s.get(self.pos).is_some_and(|c| c.is_ascii_digit() || *c == b'.') {
+                    self.pos += 1;
+                }
+                if self.s.get(self.pos).is_some_and(|c| c.eq_ignore_ascii_case(&b'e')) {
+                    self.pos += 1;
+                    if matches!(self.s.get(self.pos), Some(b'+' | b'-')) {
+                        self.pos += 1;
+                    }
+                    while self.s.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                        self.pos += 1;
+                    }
+                }
+                return std::str::from_utf8(&self.s[start..self.pos]).unwrap().parse().unwrap();
+            }
+            while self.s.get(self.pos).is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'.' || *c == b'_') {
+                self.pos += 1;
+            }
+            let name = std::str::from_utf8(&self.s[start..self.pos]).unwrap();
+            if name == "t" {
+                return self.t.max(1e-12);
+            }
+            assert!(self.eat(b'('), "call expected after {name}");
+            let mut args = vec![self.sum()];
+            while self.eat(b',') {
+                args.push(self.sum());
+            }
+            assert!(self.eat(b')'));
+            match (name, args.as_slice()) {
+                ("EXP" | "math.exp", &[x]) => x.exp(),
+                ("f1", &[t, tau]) => f1(t, tau),
+                ("f2", &[t, tau]) => f2(t, tau),
+                _ => panic!("unexpected call {name}{args:?}"),
+            }
+        }
+    }
+}
@@ -5,18 +5,56 @@
 
 use chrono::NaiveDate;
 
-use crate::domain::{BondPoint, DatasetStats, RunSpec, YKind};
+use crate::data::sample::compute_stats;
+use crate::domain::{BadWeight, BondPoint, DatasetStats, RunSpec, YKind, YUnit};
+use crate::error::AppError;
 
 /// High-level, resolved input conventions for the run.
 #[derive(Debug, Clone)]
 pub struct InputSpec {
     pub asof_date: NaiveDate,
     pub y_kind: YKind,
+    pub y_unit: YUnit,
 }
 
 impl InputSpec {
     pub fn y_unit_label(&self) -> &'static str {
-        self.y_kind.unit_label()
+        self.y_unit.label()
+    }
+}
+
+/// How many generated points reached the fit, and why the others did not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointAccounting {
+    pub generated: usize,
+    /// Points with a tenor outside `[tenor_min, tenor_max]`.
+    pub dropped_by_bound: usize,
+    /// Points trimmed for a non-finite tenor or observation.
+    pub dropped_by_trim: usize,
+    /// Points dropped for a non-finite or non-positive weight (`--on-bad-weight drop`).
+    pub dropped_by_weight: usize,
+    /// Ranked points outside the `--fit-min`/`--fit-max` window, left out of the fit.
+    pub outside_fit_window: usize,
+}
+
+impl PointAccounting {
+    /// Points that are ranked against the curve (`generated` minus all drops).
+    pub fn ranked(&self) -> usize {
+        self.generated - self.dropped_by_bound - self.dropped_by_trim - self.dropped_by_weight
+    }
+
+    /// Points passed to the fit (`ranked` minus those outside the fit window).
+    pub fn used(&self) -> usize {
+        self.ranked() - self.outside_fit_window
+    }
+
+    /// Itemized drops for the "no bonds left" errors. `--issuer-cap` only
+    /// reweights, so it is listed with no drops.
+    pub fn drop_summary(&self) -> String {
+        format!(
+            "{} generated; dropped {} by tenor bounds, {} by trimming (non-finite tenor or value), {} by weight, 0 by issuer cap (reweights only), {} outside the fit window",
+            self.generated, self.dropped_by_bound, self.dropped_by_trim, self.dropped_by_weight, self.outside_fit_window
+        )
     }
 }
 
@@ -26,6 +64,7 @@ pub struct IngestedData {
     pub points: Vec<BondPoint>,
     pub input_spec: InputSpec,
     pub stats: DatasetStats,
+    pub accounting: PointAccounting,
 }
 
 impl IngestedData {
@@ -35,13 +74,192 @@ impl IngestedData {
         spec: RunSpec,
         stats: DatasetStats,
     ) -> Self {
+        let accounting = PointAccounting {
+            generated: points.len(),
+            dropped_by_bound: 0,
+            dropped_by_trim: 0,
+            dropped_by_weight: 0,
+            outside_fit_window: 0,
+        };
         Self {
             points,
             input_spec: InputSpec {
                 asof_date: spec.asof_date,
                 y_kind: spec.y_kind,
+                y_unit: YUnit::Bp,
             },
             stats,
+            accounting,
+        }
+    }
+
+    /// Drop points that cannot be fitted (non-finite values, then tenors outside
+    /// `[tenor_min, tenor_max]`), recording the counts in `accounting`.
+    ///
+    /// Stats are recomputed when anything was dropped.
+    pub fn retain_usable(&mut self, tenor_min: f64, tenor_max: f64) -> Result<(), AppError> {
+        let before = self.points.len();
+        self.points.retain(|p| p.tenor.is_finite() && p.y_obs.is_finite());
+        let after_trim = self.points.len();
+        self.points.retain(|p| p.tenor >= tenor_min && p.tenor <= tenor_max);
+
+        self.accounting.dropped_by_trim += before - after_trim;
+        self.accounting.dropped_by_bound += after_trim - self.points.len();
+        if self.points.len() != before {
+            self.stats = compute_stats(&self.points).ok_or_else(|| {
+                AppError::new(
+                    3,
+                    format!(
+                        "No usable bonds after filtering (tenor bounds [{tenor_min:.2}, {tenor_max:.2}]y): {}.",
+                        self.accounting.drop_summary()
+                    ),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Apply `policy` to points whose weight is non-finite or `<= 0`, returning
+    /// how many were dropped. Points keep their order, so anything derived from
+    /// `points` afterwards (residuals, exports) stays aligned.
+    pub fn retain_valid_weights(&mut self, policy: BadWeight) -> Result<usize, AppError> {
+        let valid = |p: &BondPoint| p.weight.is_finite() && p.weight > 0.0;
+        let total = self.points.len();
+        let bad: Vec<&BondPoint> = self.points.iter().filter(|p| !valid(p)).collect();
+        let Some(first) = bad.first() else {
+            return Ok(0);
+        };
+        let n_bad = bad.len();
+        if policy == BadWeight::Error {
+            return Err(AppError::new(
+                2,
+                format!(
+                    "{n_bad} of {total} weights are invalid (non-finite or <= 0; first: bond {} weight {}). Use --on-bad-weight drop to fit without them.",
+                    first.id, first.weight
+                ),
+            ));
+        }
+
+        self.points.retain(valid);
+        self.accounting.dropped_by_weight += n_bad;
+        self.stats = compute_stats(&self.points).ok_or_else(|| {
+            AppError::new(
+                3,
+                format!(
+                    "No usable bonds: all {total} remaining weights are invalid (non-finite or <= 0); {}.",
+                    self.accounting.drop_summary()
+                ),
+            )
+        })?;
+        Ok(n_bad)
+    }
+
+    /// Convert y-values (observations, OAS extras, and stats) from bp to `unit`.
+    ///
+    /// Must be called on freshly ingested (bp) data, before fitting.
+    pub fn convert_unit(&mut self, unit: YUnit) {
+        let scale = unit.per_bp() / self.input_spec.y_unit.per_bp();
+        for p in &mut self.points {
+            p.y_obs *= scale;
+            p.extras.oas = p.extras.oas.map(|v| v * scale);
         }
+        self.stats.y_min *= scale;
+        self.stats.y_max *= scale;
+        self.input_spec.y_unit = unit;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BondExtras, BondMeta};
+
+    #[test]
+    fn retain_usable_accounts_for_every_point() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let point = |tenor: f64, y_obs: f64| BondPoint {
+            id: format!("B{tenor}"),
+            asof_date: asof,
+            maturity_date: asof,
+            tenor,
+            y_obs,
+            weight: 1.0,
+            meta: BondMeta::default(),
+            extras: BondExtras::default(),
+        };
+        let points = vec![point(1.0, 100.0), point(5.0, f64::NAN), point(40.0, 120.0), point(10.0, 110.0)];
+        let stats = compute_stats(&points[..1]).unwrap();
+        let spec = RunSpec { asof_date: asof, y_kind: YKind::Oas };
+
+        let mut ingest = IngestedData::from_sample(points, spec.clone(), stats.clone());
+        ingest.retain_usable(0.5, 30.0).unwrap();
+        let acc = ingest.accounting;
+        assert_eq!((acc.generated, acc.dropped_by_bound, acc.dropped_by_trim, acc.used()), (4, 1, 1, 2));
+        assert_eq!(acc.used(), ingest.points.len());
+        assert_eq!(ingest.stats.n_points, 2);
+        assert_eq!(ingest.stats.tenor_max, 10.0);
+
+        let mut empty = IngestedData::from_sample(vec![point(40.0, 1.0)], spec, stats);
+        assert!(empty.retain_usable(0.5, 30.0).is_err());
+    }
+
+    #[test]
+    fn empty_point_set_error_itemizes_each_filter() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let point = |tenor: f64, weight: f64| BondPoint {
+            id: format!("B{tenor}"),
+            asof_date: asof,
+            maturity_date: asof,
+            tenor,
+            y_obs: 100.0,
+            weight,
+            meta: BondMeta::default(),
+            extras: BondExtras::default(),
+        };
+        let points = vec![point(1.0, 0.0), point(f64::NAN, 1.0), point(40.0, 1.0), point(50.0, 1.0)];
+        let stats = compute_stats(&points[..1]).unwrap();
+        let spec = RunSpec { asof_date: asof, y_kind: YKind::Oas };
+
+        let mut ingest = IngestedData::from_sample(points, spec, stats);
+        ingest.retain_usable(0.5, 30.0).unwrap();
+        let err = ingest.retain_valid_weights(BadWeight::Drop).unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+        assert!(
+            err.to_string().contains(
+                "4 generated; dropped 2 by tenor bounds, 1 by trimming (non-finite tenor or value), 1 by weight, 0 by issuer cap"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn bad_weights_drop_in_order_or_fail_with_a_count() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let point = |tenor: f64, weight: f64| BondPoint {
+            id: format!("B{tenor}"),
+            asof_date: asof,
+            maturity_date: asof,
+            tenor,
+            y_obs: 100.0,
+            weight,
+            meta: BondMeta::default(),
+            extras: BondExtras::default(),
+        };
+        let points = vec![point(1.0, 1.0), point(2.0, 0.0), point(3.0, 2.0), point(4.0, f64::INFINITY)];
+        let stats = compute_stats(&points).unwrap();
+        let spec = RunSpec { asof_date: asof, y_kind: YKind::Oas };
+
+        let mut strict = IngestedData::from_sample(points.clone(), spec.clone(), stats.clone());
+        let err = strict.retain_valid_weights(BadWeight::Error).unwrap_err();
+        assert!(err.to_string().starts_with("2 of 4 weights are invalid"), "{err}");
+        assert_eq!(strict.points.len(), 4);
+
+        let mut ingest = IngestedData::from_sample(points, spec, stats);
+        assert_eq!(ingest.retain_valid_weights(BadWeight::Drop).unwrap(), 2);
+        let kept: Vec<(f64, f64)> = ingest.points.iter().map(|p| (p.tenor, p.weight)).collect();
+        assert_eq!(kept, [(1.0, 1.0), (3.0, 2.0)]);
+        assert_eq!(ingest.accounting.used(), 2);
+        assert_eq!(ingest.stats.n_points, 2);
+    }
+}
+
@@ -3,12 +3,24 @@
 //! - CSV ingest + validation (`ingest`)
 //! - result exports (CSV/JSON) (`export`)
 //! - curve JSON read/write (`curve`)
+//! - fitted curve as a Python/Excel formula (`formula`)
+//! - per-tenor weight files (`weights`)
+//! - real bond spreads from CSV (`points`)
+//! - JSON Schema for curve files (`schema`)
 
 pub mod curve;
 pub mod export;
+pub mod formula;
 pub mod ingest;
+pub mod points;
+pub mod schema;
+pub mod weights;
 
 pub use curve::*;
 pub use export::*;
+pub use formula::*;
 pub use ingest::*;
+pub use points::*;
+pub use schema::*;
+pub use weights::*;
 
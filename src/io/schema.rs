@@ -0,0 +1,185 @@
+//! JSON Schema for curve files (`rv schema curve`).
+//!
+//! Hand-written to mirror the serde layout of `CurveFile`: a field is
+//! `required` only when deserialization needs it (no `#[serde(default)]`), and
+//! enum values are taken from the serialized variants so they can't drift from
+//! what `write_curve_json` emits.
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::domain::{Basis, ModelKind, RatingBand, YKind, YTransform, YUnit, CURVE_SCHEMA_VERSION};
+
+/// Serialized names of `variants`, for an `enum` keyword.
+fn enum_values<T: Serialize>(variants: &[T]) -> Value {
+    Value::Array(variants.iter().map(|v| serde_json::to_value(v).expect("enum variants serialize")).collect())
+}
+
+fn number_array() -> Value {
+    json!({ "type": "array", "items": { "type": "number" } })
+}
+
+/// JSON Schema (draft 2020-12) describing `CurveFile`.
+pub fn curve_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "rv curve file",
+        "description": format!("Curve JSON written by `rv fit --export-curve` (schema_version {CURVE_SCHEMA_VERSION})."),
+        "type": "object",
+        "required": ["tool", "asof_date", "y", "rating", "model", "fit_quality", "grid"],
+        "properties": {
+            "tool": { "type": "string" },
+            "schema_version": {
+                "type": "integer",
+                "minimum": 1,
+                "default": 1,
+                "description": "Curve file layout version; absent in files written before it existed."
+            },
+            "asof_date": { "type": "string", "format": "date", "description": "FRED observation date used." },
+            "requested_asof": { "type": "string", "format": "date", "description": "--asof as requested." },
+            "y": { "enum": enum_values(&[YKind::Oas]) },
+            "unit": { "enum": enum_values(YUnit::value_variants()), "default": "bp" },
+            "rating": { "enum": enum_values(RatingBand::value_variants()) },
+            "basis": { "enum": enum_values(Basis::value_variants()), "default": "standard" },
+            "lambdas": number_array(),
+            "model": { "$ref": "#/$defs/CurveModel" },
+            "fit_quality": { "$ref": "#/$defs/FitQuality" },
+            "grid": { "$ref": "#/$defs/CurveGrid" },
+            "shape": { "type": "string" }
+        },
+        "$defs": {
+            "CurveModel": {
+                "type": "object",
+                "required": ["name", "display_name", "betas", "taus"],
+                "properties": {
+                    "name": { "enum": enum_values(&[ModelKind::Ns, ModelKind::NssShared, ModelKind::Nss, ModelKind::Nssc]) },
+                    "display_name": { "type": "string" },
+                    "betas": number_array(),
+                    "taus": number_array(),
+                    "y_transform": { "enum": enum_values(YTransform::value_variants()), "default": "none" },
+                    "splice": { "$ref": "#/$defs/Splice" }
+                }
+            },
+            "Splice": {
+                "type": "object",
+                "required": ["at", "window", "long"],
+                "properties": {
+                    "at": { "type": "number" },
+                    "window": { "type": "number" },
+                    "long": { "$ref": "#/$defs/CurveModel" }
+                }
+            },
+            "FitQuality": {
+                "type": "object",
+                "required": ["sse", "rmse", "bic", "n"],
+                "properties": {
+                    "sse": { "type": "number" },
+                    "rmse": { "type": "number" },
+                    "bic": { "type": "number" },
+                    "n": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "CurveGrid": {
+                "type": "object",
+                "required": ["tenor_years", "y"],
+                "properties": {
+                    "tenor_years": number_array(),
+                    "y": number_array()
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check `value` against the subset of JSON Schema `curve_schema` uses.
+    /// Stricter than the spec on one point: properties the schema doesn't
+    /// list are errors, so a new `CurveFile` field fails until it's described.
+    fn validate(root: &Value, schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/$defs/");
+            return validate(root, &root["$defs"][name], value, path, errors);
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                errors.push(format!("{path}: {value} not in {allowed:?}"));
+            }
+        }
+        let type_ok = match schema.get("type").and_then(Value::as_str) {
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            Some("string") => value.is_string(),
+            Some("number") => value.is_number(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            _ => true,
+        };
+        if !type_ok {
+            errors.push(format!("{path}: expected {}, got {value}", schema["type"]));
+            return;
+        }
+        if let (Some(min), Some(v)) = (schema.get("minimum").and_then(Value::as_f64), value.as_f64()) {
+            if v < min {
+                errors.push(format!("{path}: {v} < minimum {min}"));
+            }
+        }
+        if let Some(items) = schema.get("items") {
+            for (i, item) in value.as_array().into_iter().flatten().enumerate() {
+                validate(root, items, item, &format!("{path}[{i}]"), errors);
+            }
+        }
+        if let Some(object) = value.as_object() {
+            for key in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                if !object.contains_key(key.as_str().unwrap()) {
+                    errors.push(format!("{path}: missing required {key}"));
+                }
+            }
+            let properties = &schema["properties"];
+            for (key, field) in object {
+                match properties.get(key) {
+                    Some(sub) => validate(root, sub, field, &format!("{path}.{key}"), errors),
+                    None => errors.push(format!("{path}: property '{key}' is not in the schema")),
+                }
+            }
+        }
+    }
+
+    fn errors_for(value: &Value) -> Vec<String> {
+        let schema = curve_schema();
+        let mut errors = Vec::new();
+        validate(&schema, &schema, value, "$", &mut errors);
+        errors
+    }
+
+    #[test]
+    fn written_curve_validates_against_the_schema() {
+        use clap::Parser;
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "--asof", "2025-01-02", "--basis", "diebold-li"]).unwrap();
+        let config = crate::app::fit_config_from_args(&args);
+        let run = crate::app::pipeline::run_fit_with_snapshot(&config, crate::data::sample::canned_snapshot()).unwrap();
+        let path = std::env::temp_dir().join(format!("rv-schema-curve-{}.json", std::process::id()));
+        crate::io::write_curve_json(&path, &run.selection.best, &run.ingest, &config).unwrap();
+        let written: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(written["schema_version"], CURVE_SCHEMA_VERSION);
+        assert!(written.get("lambdas").is_some() && written.get("requested_asof").is_some());
+        assert_eq!(errors_for(&written), Vec::<String>::new());
+
+        // A spliced model exercises the recursive `$defs/CurveModel` reference.
+        let mut spliced = written.clone();
+        spliced["model"]["splice"] = json!({ "at": 5.0, "window": 1.0, "long": written["model"].clone() });
+        assert_eq!(errors_for(&spliced), Vec::<String>::new());
+
+        let mut broken = written.clone();
+        broken.as_object_mut().unwrap().remove("grid");
+        broken["rating"] = json!("ZZ");
+        broken["fit_quality"]["n"] = json!(-1);
+        let errors = errors_for(&broken);
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("missing required \"grid\"")), "{errors:?}");
+    }
+}
@@ -0,0 +1,282 @@
+//! Per-tenor weight multipliers from a user-provided CSV.
+//!
+//! File format (header required, `#` comments and blank lines ignored):
+//!
+//! ```text
+//! tenor_min,tenor_max,multiplier
+//! 0,2,0.5
+//! 2,10,1.0
+//! 10,30,2.0
+//! ```
+//!
+//! Ranges are half-open `[tenor_min, tenor_max)` and must not overlap. Bonds outside
+//! every range keep a multiplier of 1.0. Multipliers are applied on top of the
+//! existing `BondPoint.weight`.
+//!
+//! `apply_issuer_cap` (`--issuer-cap`) then limits each issuer's share of the
+//! total weight.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::domain::BondPoint;
+use crate::error::AppError;
+
+/// A tenor range and its weight multiplier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenorWeight {
+    pub tenor_min: f64,
+    pub tenor_max: f64,
+    pub multiplier: f64,
+}
+
+impl TenorWeight {
+    fn contains(&self, tenor: f64) -> bool {
+        tenor >= self.tenor_min && tenor < self.tenor_max
+    }
+}
+
+/// Read and validate a weight file.
+pub fn read_weight_file(path: &Path) -> Result<Vec<TenorWeight>, AppError> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| AppError::new(2, format!("Failed to read weight file '{}': {e}", path.display())))?;
+    parse_weight_csv(&text).map_err(|e| AppError::new(2, format!("Weight file '{}': {e}", path.display())))
+}
+
+/// Parse weight CSV text (see module docs for the format).
+pub fn parse_weight_csv(text: &str) -> Result<Vec<TenorWeight>, AppError> {
+    let mut rows = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let Some((_, header)) = rows.next() else {
+        return Err(AppError::new(2, "empty file (expected header tenor_min,tenor_max,multiplier)."));
+    };
+    let columns: Vec<&str> = header.split(',').map(|c| c.trim()).collect();
+    if columns != ["tenor_min", "tenor_max", "multiplier"] {
+        return Err(AppError::new(
+            2,
+            format!("unexpected header '{header}' (expected tenor_min,tenor_max,multiplier)."),
+        ));
+    }
+
+    let mut ranges = Vec::new();
+    for (line_no, line) in rows {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() != 3 {
+            return Err(AppError::new(2, format!("line {line_no}: expected 3 fields, got {}.", fields.len())));
+        }
+        let parse = |s: &str, name: &str| -> Result<f64, AppError> {
+            s.parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| AppError::new(2, format!("line {line_no}: invalid {name} '{s}'.")))
+        };
+        let range = TenorWeight {
+            tenor_min: parse(fields[0], "tenor_min")?,
+            tenor_max: parse(fields[1], "tenor_max")?,
+            multiplier: parse(fields[2], "multiplier")?,
+        };
+        if range.tenor_max <= range.tenor_min {
+            return Err(AppError::new(
+                2,
+                format!("line {line_no}: tenor_max {} must be > tenor_min {}.", range.tenor_max, range.tenor_min),
+            ));
+        }
+        if range.multiplier <= 0.0 {
+            return Err(AppError::new(
+                2,
+                format!("line {line_no}: multiplier must be > 0 (got {}).", range.multiplier),
+            ));
+        }
+        ranges.push(range);
+    }
+
+    ranges.sort_by(|a, b| a.tenor_min.total_cmp(&b.tenor_min));
+    for w in ranges.windows(2) {
+        if w[1].tenor_min < w[0].tenor_max {
+            return Err(AppError::new(
+                2,
+                format!(
+                    "overlapping ranges [{}, {}) and [{}, {}).",
+                    w[0].tenor_min, w[0].tenor_max, w[1].tenor_min, w[1].tenor_max
+                ),
+            ));
+        }
+    }
+
+    Ok(ranges)
+}
+
+/// Multiply each point's weight by the multiplier of the range containing its tenor.
+///
+/// Points outside every range are left unchanged (multiplier 1.0). Invalid
+/// products are left for `IngestedData::retain_valid_weights` to handle.
+pub fn apply_tenor_weights(points: &mut [BondPoint], ranges: &[TenorWeight]) {
+    for p in points.iter_mut() {
+        let multiplier = ranges
+            .iter()
+            .find(|r| r.contains(p.tenor))
+            .map(|r| r.multiplier)
+            .unwrap_or(1.0);
+        p.weight *= multiplier;
+    }
+}
+
+/// Outcome of `apply_issuer_cap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IssuerCapReport {
+    /// Issuers whose weight was scaled down.
+    pub issuers: usize,
+    /// Bonds belonging to those issuers.
+    pub bonds: usize,
+}
+
+/// Scale weights down so no issuer holds more than `cap` of the total weight.
+///
+/// Bonds without an issuer count as their own single-bond issuer. Over-cap
+/// issuers are scaled proportionally (their bonds keep their relative
+/// weights) to exactly `cap` of the new total; everyone else is unchanged.
+/// Capping one issuer shrinks the total, so the capped set is grown until no
+/// uncapped issuer exceeds the cap. Weights must already be finite and > 0,
+/// and stay so.
+pub fn apply_issuer_cap(points: &mut [BondPoint], cap: f64) -> Result<IssuerCapReport, AppError> {
+    if !(cap.is_finite() && cap > 0.0 && cap <= 1.0) {
+        return Err(AppError::new(2, format!("Invalid --issuer-cap {cap}: must be in (0, 1].")));
+    }
+    // Group index per point; issuer-less bonds get a group of their own.
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut totals: Vec<f64> = Vec::new();
+    let mut group_of = Vec::with_capacity(points.len());
+    for p in points.iter() {
+        let group = match p.meta.issuer.as_deref() {
+            Some(issuer) => *index.entry(issuer).or_insert_with(|| {
+                totals.push(0.0);
+                totals.len() - 1
+            }),
+            None => {
+                totals.push(0.0);
+                totals.len() - 1
+            }
+        };
+        totals[group] += p.weight;
+        group_of.push(group);
+    }
+    if cap * (totals.len() as f64) < 1.0 {
+        return Err(AppError::new(
+            2,
+            format!(
+                "--issuer-cap {cap} is unreachable: {} issuer(s) cannot each hold at most {:.1}% of the weight.",
+                totals.len(),
+                cap * 100.0
+            ),
+        ));
+    }
+
+    // Each capped issuer ends at `x = cap * (uncapped + n_capped * x)`.
+    let mut capped = vec![false; totals.len()];
+    let target = loop {
+        let uncapped: f64 = totals.iter().zip(&capped).filter(|(_, &c)| !c).map(|(w, _)| w).sum();
+        let n_capped = capped.iter().filter(|&&c| c).count() as f64;
+        let target = cap * uncapped / (1.0 - cap * n_capped);
+        let mut grew = false;
+        for (g, &w) in totals.iter().enumerate() {
+            if !capped[g] && w > target * (1.0 + 1e-12) {
+                capped[g] = true;
+                grew = true;
+            }
+        }
+        if !grew {
+            break target;
+        }
+    };
+
+    let mut bonds = 0;
+    for (p, &g) in points.iter_mut().zip(&group_of) {
+        if capped[g] {
+            p.weight *= target / totals[g];
+            bonds += 1;
+        }
+    }
+    Ok(IssuerCapReport { issuers: capped.iter().filter(|&&c| c).count(), bonds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BondExtras, BondMeta};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn parse_rejects_overlap_and_non_positive() {
+        let overlap = "tenor_min,tenor_max,multiplier\n0,5,1.0\n4,10,2.0\n";
+        assert!(parse_weight_csv(overlap).unwrap_err().to_string().contains("overlapping"));
+
+        let zero = "tenor_min,tenor_max,multiplier\n0,5,0\n";
+        assert!(parse_weight_csv(zero).unwrap_err().to_string().contains("multiplier must be > 0"));
+    }
+
+    #[test]
+    fn apply_multiplies_inside_ranges_only() {
+        let ranges = parse_weight_csv("# belly\ntenor_min,tenor_max,multiplier\n2,10,3.0\n").unwrap();
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut points: Vec<BondPoint> = [1.0, 2.0, 9.99, 10.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: t,
+                y_obs: 100.0,
+                weight: 2.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            })
+            .collect();
+
+        apply_tenor_weights(&mut points, &ranges);
+        let weights: Vec<f64> = points.iter().map(|p| p.weight).collect();
+        assert_eq!(weights, vec![2.0, 6.0, 6.0, 2.0]);
+    }
+
+    #[test]
+    fn issuer_cap_scales_over_cap_issuers_only() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        // ACME: 4 bonds (weights 1..4 sum 10), BETA: one bond, plus 5 bonds
+        // without an issuer; total weight 16.
+        let issuers = ["ACME", "ACME", "ACME", "ACME", "BETA", "", "", "", "", ""];
+        let weights = [1.0, 2.0, 3.0, 4.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let mut points: Vec<BondPoint> = issuers
+            .iter()
+            .zip(weights)
+            .enumerate()
+            .map(|(i, (&issuer, weight))| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 1.0 + i as f64,
+                y_obs: 100.0,
+                weight,
+                meta: BondMeta { issuer: (!issuer.is_empty()).then(|| issuer.to_string()), rating: None },
+                extras: BondExtras::default(),
+            })
+            .collect();
+
+        let report = apply_issuer_cap(&mut points, 0.25).unwrap();
+        assert_eq!(report, IssuerCapReport { issuers: 1, bonds: 4 });
+        // ACME ends at 0.25 of the new total: x = 0.25 * 6 / 0.75 = 2.
+        let acme: Vec<f64> = points[..4].iter().map(|p| p.weight).collect();
+        assert!((acme.iter().sum::<f64>() - 2.0).abs() < 1e-12);
+        assert!((acme[3] / acme[0] - 4.0).abs() < 1e-12);
+        // Issuer-less bonds are singletons, not one 5/16 "issuer".
+        assert!(points[4..].iter().all(|p| p.weight == 1.0));
+
+        assert!(apply_issuer_cap(&mut points, 0.1).unwrap_err().to_string().contains("unreachable: 7 issuer(s)"));
+        assert!(apply_issuer_cap(&mut points, 0.0).is_err());
+        assert_eq!(apply_issuer_cap(&mut points, 1.0).unwrap().issuers, 0);
+    }
+}
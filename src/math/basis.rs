@@ -14,7 +14,14 @@
 const T_EPS: f64 = 1e-12;
 
 /// Threshold below which we switch to a small-x series approximation.
-const SMALL_X: f64 = 1e-6;
+pub const SMALL_X: f64 = 1e-6;
+
+/// Largest error (vs `reference_basis`) either branch may show near
+/// `SMALL_X`: a few ulps of the O(1) operands both branches work with.
+pub const BRANCH_TOL: f64 = 64.0 * f64::EPSILON;
+
+/// Half-width of the `x` band sampled around `SMALL_X`, as a factor.
+const CHECK_SPAN: f64 = 4.0;
 
 /// Compute `f1(t, τ)` in a numerically stable way.
 pub fn f1(t: f64, tau: f64) -> f64 {
@@ -48,6 +55,78 @@ pub fn f2(t: f64, tau: f64) -> f64 {
     f1(t, tau) - exp_neg_x
 }
 
+/// `(f1, f2)` at `x` from five series terms each, exact to well below
+/// `BRANCH_TOL` for `x` near `SMALL_X` (the first dropped term is O(x^5)).
+fn reference_basis(x: f64) -> (f64, f64) {
+    let (x2, x3, x4) = (x * x, x * x * x, x * x * x * x);
+    let f1 = 1.0 - x / 2.0 + x2 / 6.0 - x3 / 24.0 + x4 / 120.0;
+    let f2 = x / 2.0 - x2 / 3.0 + x3 / 8.0 - x4 / 30.0;
+    (f1, f2)
+}
+
+/// Branch continuity of one basis function at one tau (`rv precision-check`).
+#[derive(Debug, Clone)]
+pub struct BranchCheck {
+    pub function: &'static str,
+    pub tau: f64,
+    /// Largest error vs the reference just below `SMALL_X` (series branch).
+    pub max_err_below: f64,
+    /// Largest error vs the reference just above `SMALL_X` (expm1 branch).
+    pub max_err_above: f64,
+    /// Step between the last sample below `SMALL_X` and the first above,
+    /// net of the reference's own change over that gap.
+    pub jump: f64,
+}
+
+impl BranchCheck {
+    pub fn passed(&self) -> bool {
+        self.max_err_below.max(self.max_err_above).max(self.jump) <= BRANCH_TOL
+    }
+}
+
+/// Sample `f1`/`f2` at `samples` log-spaced `x = t/τ` on each side of
+/// `SMALL_X` (within a factor `CHECK_SPAN`) for every tau.
+pub fn check_branch_continuity(taus: &[f64], samples: usize) -> Vec<BranchCheck> {
+    let mut checks = Vec::new();
+    for &tau in taus {
+        checks.push(check_branch("f1", f1, |r| r.0, tau, samples));
+        checks.push(check_branch("f2", f2, |r| r.1, tau, samples));
+    }
+    checks
+}
+
+/// `check_branch_continuity` for one function; `pick` selects its value
+/// from `reference_basis`.
+fn check_branch(
+    function: &'static str,
+    f: fn(f64, f64) -> f64,
+    pick: fn((f64, f64)) -> f64,
+    tau: f64,
+    samples: usize,
+) -> BranchCheck {
+    let samples = samples.max(2);
+    let step = CHECK_SPAN.ln() / (samples - 1) as f64;
+    // Ordered from the boundary outwards on each side.
+    let below: Vec<f64> = (0..samples).map(|i| SMALL_X * (1.0 - f64::EPSILON) * (-step * i as f64).exp()).collect();
+    let above: Vec<f64> = (0..samples).map(|i| SMALL_X * (step * i as f64).exp()).collect();
+
+    // Evaluate at the tenor the fit would pass in, so `x` goes through the
+    // same `t / tau` rounding as in `predict`.
+    let eval = |x: f64| {
+        let t = x * tau;
+        (f(t, tau), pick(reference_basis(t / tau)))
+    };
+    let max_err = |xs: &[f64]| xs.iter().map(|&x| eval(x)).map(|(v, r)| (v - r).abs()).fold(0.0, f64::max);
+    let ((v_lo, r_lo), (v_hi, r_hi)) = (eval(below[0]), eval(above[0]));
+    BranchCheck {
+        function,
+        tau,
+        max_err_below: max_err(&below),
+        max_err_above: max_err(&above),
+        jump: ((v_hi - v_lo) - (r_hi - r_lo)).abs(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,5 +152,27 @@ mod tests {
             }
         }
     }
-}
 
+    #[test]
+    fn series_and_expm1_branches_meet_at_small_x() {
+        let checks = check_branch_continuity(&[0.05, 0.25, 1.0, 5.0, 30.0], 200);
+        assert_eq!(checks.len(), 10);
+        for c in &checks {
+            assert!(c.passed(), "{c:?}");
+        }
+        // Both sides of the switch really are sampled, on either branch.
+        for c in &checks {
+            let t = SMALL_X * (1.0 - f64::EPSILON) * c.tau;
+            assert!(t / c.tau < SMALL_X && (SMALL_X * c.tau) / c.tau >= SMALL_X, "tau={}", c.tau);
+        }
+
+        // An f2 series truncated one term early opens a gap at the switch.
+        fn truncated_f2(t: f64, tau: f64) -> f64 {
+            let x = t / tau;
+            if x < SMALL_X { x / 2.0 } else { f2(t, tau) }
+        }
+        let broken = check_branch("f2", truncated_f2, |r| r.1, 1.0, 200);
+        assert!(!broken.passed() && broken.jump > BRANCH_TOL, "{broken:?}");
+        assert!(broken.max_err_above <= BRANCH_TOL);
+    }
+}
@@ -1,8 +1,13 @@
-//! Mathematical utilities: basis functions and weighted least squares.
+//! Mathematical utilities: basis functions, weighted least squares,
+//! weighted quantiles, and a stable hash.
 
 pub mod basis;
+pub mod hash;
 pub mod ols;
+pub mod stats;
 
 pub use basis::*;
+pub use hash::*;
 pub use ols::*;
+pub use stats::*;
 
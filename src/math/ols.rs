@@ -20,10 +20,22 @@
 
 use nalgebra::{DMatrix, DVector};
 
+/// Singular-value cutoffs tried, in order, by `solve_least_squares`.
+pub const SVD_TOLERANCES: [f64; 3] = [1e-10, 1e-8, 1e-6];
+
+/// Last-resort cutoff used by the fitter when no tau candidate solves under
+/// `SVD_TOLERANCES` (recorded as a warning when it is needed).
+pub const RELAXED_SVD_TOLERANCE: f64 = 1e-4;
+
 /// Solve a least squares problem using SVD.
 ///
 /// Returns `None` if the system is too ill-conditioned to solve robustly.
 pub fn solve_least_squares(x: &DMatrix<f64>, y: &DVector<f64>) -> Option<DVector<f64>> {
+    solve_least_squares_with(x, y, &SVD_TOLERANCES)
+}
+
+/// `solve_least_squares` with an explicit ladder of singular-value cutoffs.
+pub fn solve_least_squares_with(x: &DMatrix<f64>, y: &DVector<f64>, tolerances: &[f64]) -> Option<DVector<f64>> {
     // SVD solve with a relaxed tolerance to handle near-singular matrices.
     // Credit curve fitting can produce nearly collinear basis columns for
     // certain tau values, so we use a tolerance that balances numerical
@@ -31,7 +43,7 @@ pub fn solve_least_squares(x: &DMatrix<f64>, y: &DVector<f64>) -> Option<DVector
     let svd = x.clone().svd(true, true);
     
     // Try progressively looser tolerances if strict solve fails.
-    for &tol in &[1e-10, 1e-8, 1e-6] {
+    for &tol in tolerances {
         if let Ok(beta) = svd.solve(y, tol) {
             if beta.iter().all(|v| v.is_finite()) {
                 return Some(beta);
@@ -0,0 +1,67 @@
+//! Weighted order statistics.
+//!
+//! `weighted_quantile` places each sorted value at the midpoint of its share of
+//! the total weight, `p_i = (S_i - w_i / 2) / W`, and interpolates linearly
+//! between neighbours. With equal weights this is the Hazen quantile, so the
+//! 0.5 quantile is the ordinary median (mean of the middle pair for even `n`).
+
+/// Weighted `q`-quantile of `values`.
+///
+/// Pairs with a NaN value or a non-finite / non-positive weight are ignored; `q`
+/// is clamped to `[0, 1]`. Returns `None` when nothing usable remains, the
+/// slices differ in length, or `q` is NaN.
+pub fn weighted_quantile(values: &[f64], weights: &[f64], q: f64) -> Option<f64> {
+    if values.len() != weights.len() || q.is_nan() {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = values
+        .iter()
+        .zip(weights)
+        .filter(|(v, w)| !v.is_nan() && w.is_finite() && **w > 0.0)
+        .map(|(&v, &w)| (v, w))
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let total: f64 = pairs.iter().map(|(_, w)| w).sum();
+    let q = q.clamp(0.0, 1.0);
+
+    let mut cumulative = 0.0;
+    let mut prev: Option<(f64, f64)> = None;
+    for &(v, w) in &pairs {
+        let p = (cumulative + w / 2.0) / total;
+        cumulative += w;
+        if q <= p {
+            return Some(match prev {
+                None => v,
+                Some((pv, pp)) => pv + (v - pv) * (q - pp) / (p - pp),
+            });
+        }
+        prev = Some((v, p));
+    }
+    prev.map(|(v, _)| v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_quantile_matches_median_and_honours_weights() {
+        let ones = [1.0; 4];
+        assert_eq!(weighted_quantile(&[3.0, 1.0, 2.0], &ones[..3], 0.5), Some(2.0));
+        assert_eq!(weighted_quantile(&[4.0, 1.0, 3.0, 2.0], &ones, 0.5), Some(2.5));
+
+        // q is clamped; NaN values and zero weights are dropped.
+        assert_eq!(weighted_quantile(&[1.0, 2.0], &[1.0, 1.0], -1.0), Some(1.0));
+        assert_eq!(weighted_quantile(&[1.0, 2.0], &[1.0, 1.0], 7.0), Some(2.0));
+        assert_eq!(weighted_quantile(&[f64::NAN, 5.0, 9.0], &[1.0, 1.0, 0.0], 0.5), Some(5.0));
+        assert_eq!(weighted_quantile(&[1.0], &[0.0], 0.5), None);
+
+        // A heavy value pulls the median towards itself: p = [1/16, 3/16, 5/8].
+        let m = weighted_quantile(&[1.0, 2.0, 10.0], &[1.0, 1.0, 6.0], 0.5).unwrap();
+        assert!((m - (2.0 + 8.0 * (0.5 - 0.1875) / (0.625 - 0.1875))).abs() < 1e-12, "{m}");
+    }
+}
@@ -1,9 +1,11 @@
 //! Nelson–Siegel family model implementations.
 //!
 //! Models are implemented as small, pure functions so that fitting/search code can
-//! stay generic.
+//! stay generic. `shape` classifies fitted curves (increasing/humped/...).
 
 pub mod model;
+pub mod shape;
 
 pub use model::*;
+pub use shape::*;
 
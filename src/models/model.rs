@@ -1,4 +1,4 @@
-//! Model evaluation for NS / NSS / NSSC.
+//! Model evaluation for NS / NSS-shared / NSS / NSSC.
 //!
 //! The fitter relies on two primitive operations:
 //! - build a design row for a given tenor and taus (for OLS)
@@ -6,8 +6,17 @@
 //!
 //! These are implemented here for each model kind.
 
-use crate::domain::ModelKind;
-use crate::math::{f1, f2};
+use crate::domain::{CurveModel, ModelKind, Splice};
+use crate::math::{f1, f2, Fnv1a};
+
+/// Ratio `τ2 / τ1` imposed by `ModelKind::NssShared`.
+///
+/// Literally sharing one τ between both curvature terms (NSS at `τ1 = τ2`)
+/// makes the two `f2` loadings identical, so the design is rank-deficient and
+/// the curve collapses to NS. NSS-shared is instead NSS restricted to
+/// `τ2 = SHARED_TAU_RATIO * τ1`: the second hump keeps its own beta but its
+/// decay costs no extra parameter.
+pub const SHARED_TAU_RATIO: f64 = 3.0;
 
 /// Fill a design row for the given model kind.
 ///
@@ -23,6 +32,12 @@ pub fn fill_design_row(model: ModelKind, t: f64, taus: &[f64], out: &mut [f64])
             out[1] = f1(t, taus[0]);
             out[2] = f2(t, taus[0]);
         }
+        ModelKind::NssShared => {
+            out[0] = 1.0;
+            out[1] = f1(t, taus[0]);
+            out[2] = f2(t, taus[0]);
+            out[3] = f2(t, SHARED_TAU_RATIO * taus[0]);
+        }
         ModelKind::Nss => {
             out[0] = 1.0;
             out[1] = f1(t, taus[0]);
@@ -39,6 +54,71 @@ pub fn fill_design_row(model: ModelKind, t: f64, taus: &[f64], out: &mut [f64])
     }
 }
 
+impl CurveModel {
+    /// Fitted `y(t)` on the observation scale: `predict`, mapped back through
+    /// the model's `y_transform` (identical to `predict` when it is `none`).
+    pub fn eval(&self, t: f64) -> f64 {
+        self.y_transform.invert(self.predict(t))
+    }
+
+    /// `y(t)` on the fitting scale. A spliced curve crossfades from this
+    /// model's own parameters into the long segment's across the window.
+    pub fn predict(&self, t: f64) -> f64 {
+        let own = predict(self.name, t, &self.betas, &self.taus);
+        match &self.splice {
+            None => own,
+            Some(splice) => {
+                let w = splice.long_weight(t);
+                if w == 0.0 {
+                    own
+                } else {
+                    (1.0 - w) * own + w * splice.long.predict(t)
+                }
+            }
+        }
+    }
+
+    /// Fitted parameters, counting both segments of a spliced curve.
+    pub fn param_count(&self) -> usize {
+        self.name.param_count() + self.splice.as_ref().map_or(0, |s| s.long.param_count())
+    }
+
+    /// Short fingerprint of the kind, transform, betas, and taus (and any
+    /// spliced segment), for telling curves apart at a glance (`--plot-hash`).
+    ///
+    /// FNV-1a over the little-endian float bit patterns, so the same curve
+    /// prints the same hash on every platform and toolchain.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Fnv1a::new();
+        self.hash_into(&mut hasher);
+        let hash = hasher.finish();
+        format!("{:08x}", (hash >> 32) as u32 ^ hash as u32)
+    }
+
+    fn hash_into(&self, hasher: &mut Fnv1a) {
+        hasher.write(format!("{:?}/{:?}", self.name, self.y_transform).as_bytes());
+        for &v in self.betas.iter().chain(&self.taus) {
+            hasher.write_f64(v);
+        }
+        if let Some(splice) = &self.splice {
+            hasher.write_f64(splice.at);
+            hasher.write_f64(splice.window);
+            splice.long.hash_into(hasher);
+        }
+    }
+}
+
+impl Splice {
+    /// Weight of the long segment at `t`: 0 below the window, 1 above it,
+    /// linear in between (a step at `at` when the window is 0).
+    pub fn long_weight(&self, t: f64) -> f64 {
+        if self.window <= 0.0 {
+            return if t >= self.at { 1.0 } else { 0.0 };
+        }
+        ((t - (self.at - self.window / 2.0)) / self.window).clamp(0.0, 1.0)
+    }
+}
+
 /// Predict `y(t)` for the given model kind.
 pub fn predict(model: ModelKind, t: f64, betas: &[f64], taus: &[f64]) -> f64 {
     match model {
@@ -47,6 +127,12 @@ pub fn predict(model: ModelKind, t: f64, betas: &[f64], taus: &[f64]) -> f64 {
             let g2 = f2(t, taus[0]);
             betas[0] + betas[1] * g1 + betas[2] * g2
         }
+        ModelKind::NssShared => {
+            let g1 = f1(t, taus[0]);
+            let g2 = f2(t, taus[0]);
+            let g3 = f2(t, SHARED_TAU_RATIO * taus[0]);
+            betas[0] + betas[1] * g1 + betas[2] * g2 + betas[3] * g3
+        }
         ModelKind::Nss => {
             let g1 = f1(t, taus[0]);
             let g2 = f2(t, taus[0]);
@@ -66,6 +152,7 @@ pub fn predict(model: ModelKind, t: f64, betas: &[f64], taus: &[f64]) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::YTransform;
 
     #[test]
     fn predict_ns_smoke() {
@@ -74,4 +161,47 @@ mod tests {
         let y = predict(ModelKind::Ns, 2.0, &betas, &taus);
         assert!(y.is_finite());
     }
+
+    #[test]
+    fn nss_shared_is_nss_with_tied_second_tau() {
+        let betas = [100.0, -20.0, 50.0, 30.0];
+        for &t in &[0.25, 2.0, 7.5, 30.0] {
+            let shared = predict(ModelKind::NssShared, t, &betas, &[2.0]);
+            let nss = predict(ModelKind::Nss, t, &betas, &[2.0, 2.0 * SHARED_TAU_RATIO]);
+            assert_eq!(shared, nss);
+        }
+        assert_eq!(ModelKind::NssShared.param_count(), ModelKind::Nss.param_count() - 1);
+    }
+
+    #[test]
+    fn fingerprint_is_pinned_and_tracks_every_parameter() {
+        let model = CurveModel {
+            name: ModelKind::Ns,
+            display_name: "NS".to_string(),
+            betas: vec![100.0, -20.0, 30.0],
+            taus: vec![2.0],
+            y_transform: YTransform::None,
+            splice: None,
+        };
+        // Pinned: a change here changes every `--plot-hash` header.
+        assert_eq!(model.fingerprint(), "e5bffb98");
+
+        let mut renamed = model.clone();
+        renamed.display_name = "other".to_string();
+        assert_eq!(renamed.fingerprint(), model.fingerprint());
+
+        let mut beta = model.clone();
+        beta.betas[2] = 30.000000000000004;
+        let mut tau = model.clone();
+        tau.taus[0] = 2.5;
+        let mut transform = model.clone();
+        transform.y_transform = YTransform::Log;
+        let spliced = CurveModel {
+            splice: Some(Box::new(Splice { at: 5.0, window: 1.0, long: model.clone() })),
+            ..model.clone()
+        };
+        for other in [beta, tau, transform, spliced] {
+            assert_ne!(other.fingerprint(), model.fingerprint(), "{other:?}");
+        }
+    }
 }
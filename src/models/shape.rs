@@ -0,0 +1,169 @@
+//! Curve shape classification on a fitted grid.
+//!
+//! Shapes are derived from the sign pattern of successive grid differences, with
+//! differences below a small relative tolerance treated as flat. The result only
+//! depends on the grid values, so it is deterministic and can be re-derived from
+//! a saved curve's grid (see `rv validate`).
+//!
+//! `find_peak` locates the interior maximum (hump) of a fitted model directly.
+
+use crate::domain::CurveModel;
+
+/// Relative tolerance (vs the largest |y|) below which a step counts as flat.
+const FLAT_REL_TOL: f64 = 1e-9;
+
+/// Uniform scan points for `find_peak` (spacing ~0.015y over a 30y range, well
+/// under the width of a hump from the smallest grid tau).
+const PEAK_SCAN_POINTS: usize = 2001;
+
+/// Golden-section iterations refining the scanned peak.
+const PEAK_REFINE_ITERS: usize = 80;
+
+/// Monotonicity class of a curve grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveShape {
+    /// No step exceeds the flat tolerance.
+    Flat,
+    /// Non-decreasing throughout.
+    Increasing,
+    /// Non-increasing throughout.
+    Decreasing,
+    /// Rises then falls (single interior maximum).
+    Humped,
+    /// Falls then rises (single interior minimum).
+    Trough,
+    /// More than one change of direction.
+    Mixed,
+}
+
+impl CurveShape {
+    /// Label stored in curve JSON (`shape` field).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CurveShape::Flat => "flat",
+            CurveShape::Increasing => "increasing",
+            CurveShape::Decreasing => "decreasing",
+            CurveShape::Humped => "humped",
+            CurveShape::Trough => "trough",
+            CurveShape::Mixed => "mixed",
+        }
+    }
+}
+
+/// Classify a grid of y-values ordered by tenor.
+pub fn classify_shape(y: &[f64]) -> CurveShape {
+    let scale = y.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+    let tol = FLAT_REL_TOL * scale.max(f64::MIN_POSITIVE);
+
+    // Direction of each non-flat step, with consecutive repeats collapsed.
+    let mut runs: Vec<bool> = Vec::new();
+    for w in y.windows(2) {
+        let d = w[1] - w[0];
+        if d.abs() <= tol {
+            continue;
+        }
+        let up = d > 0.0;
+        if runs.last() != Some(&up) {
+            runs.push(up);
+        }
+    }
+
+    match runs.as_slice() {
+        [] => CurveShape::Flat,
+        [true] => CurveShape::Increasing,
+        [false] => CurveShape::Decreasing,
+        [true, false] => CurveShape::Humped,
+        [false, true] => CurveShape::Trough,
+        _ => CurveShape::Mixed,
+    }
+}
+
+/// Locate the interior maximum of `model` on `[t0, t1]` as `(tenor, level)`.
+///
+/// A uniform scan finds the best grid point, then golden-section search refines
+/// it between its neighbours. Returns `None` ("no interior peak") when the scan
+/// maximum sits on an endpoint or does not rise above both endpoints by more
+/// than the flat tolerance, so flat and monotone curves never report an
+/// endpoint as a hump.
+pub fn find_peak(model: &CurveModel, t0: f64, t1: f64) -> Option<(f64, f64)> {
+    if !(t0.is_finite() && t1.is_finite() && t1 > t0) {
+        return None;
+    }
+    let f = |t: f64| model.eval(t);
+    let step = (t1 - t0) / (PEAK_SCAN_POINTS - 1) as f64;
+    let ys: Vec<f64> = (0..PEAK_SCAN_POINTS).map(|i| f(t0 + step * i as f64)).collect();
+    if ys.iter().any(|y| !y.is_finite()) {
+        return None;
+    }
+
+    let (best, &y_best) = ys.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))?;
+    let scale = ys.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+    let tol = FLAT_REL_TOL * scale.max(f64::MIN_POSITIVE);
+    let last = PEAK_SCAN_POINTS - 1;
+    if best == 0 || best == last || y_best - ys[0] <= tol || y_best - ys[last] <= tol {
+        return None;
+    }
+
+    // Golden-section search for the maximum on the bracketing cell pair.
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (t0 + step * (best - 1) as f64, t0 + step * (best + 1) as f64);
+    let mut c = b - inv_phi * (b - a);
+    let mut d = a + inv_phi * (b - a);
+    for _ in 0..PEAK_REFINE_ITERS {
+        if f(c) > f(d) {
+            b = d;
+        } else {
+            a = c;
+        }
+        c = b - inv_phi * (b - a);
+        d = a + inv_phi * (b - a);
+    }
+    let t = 0.5 * (a + b);
+    Some((t, f(t)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ModelKind;
+
+    fn ns(betas: [f64; 3], tau: f64) -> CurveModel {
+        CurveModel {
+            name: ModelKind::Ns,
+            display_name: "NS".to_string(),
+            betas: betas.to_vec(),
+            taus: vec![tau],
+            y_transform: crate::domain::YTransform::None,
+            splice: None,
+        }
+    }
+
+    #[test]
+    fn peak_is_interior_or_none() {
+        // beta2 > 0 gives a hump; its peak solves f2'(t) = 0 (t ~ 1.7933 tau).
+        let (t, y) = find_peak(&ns([100.0, 0.0, 50.0], 2.0), 0.25, 30.0).unwrap();
+        assert!((t - 3.5866).abs() < 1e-3, "{t}");
+        assert!(y > 100.0);
+
+        // A narrow hump (tau at the grid minimum) is still found.
+        let (t, _) = find_peak(&ns([100.0, 0.0, 50.0], 0.05), 0.01, 30.0).unwrap();
+        assert!((t - 0.0897).abs() < 2e-3, "{t}");
+
+        // Flat and monotone curves have no interior peak.
+        assert!(find_peak(&ns([100.0, 0.0, 0.0], 2.0), 0.25, 30.0).is_none());
+        assert!(find_peak(&ns([100.0, -40.0, 0.0], 2.0), 0.25, 30.0).is_none());
+        assert!(find_peak(&ns([100.0, 40.0, 0.0], 2.0), 0.25, 30.0).is_none());
+    }
+
+    #[test]
+    fn classifies_basic_shapes() {
+        assert_eq!(classify_shape(&[1.0, 1.0, 1.0]), CurveShape::Flat);
+        assert_eq!(classify_shape(&[1.0, 2.0, 2.0, 3.0]), CurveShape::Increasing);
+        assert_eq!(classify_shape(&[3.0, 2.0, 1.0]), CurveShape::Decreasing);
+        assert_eq!(classify_shape(&[1.0, 3.0, 2.0]), CurveShape::Humped);
+        assert_eq!(classify_shape(&[3.0, 1.0, 2.0]), CurveShape::Trough);
+        assert_eq!(classify_shape(&[1.0, 3.0, 2.0, 4.0]), CurveShape::Mixed);
+        // Sub-tolerance wiggles do not count as a change of direction.
+        assert_eq!(classify_shape(&[100.0, 101.0, 101.0 - 1e-12, 102.0]), CurveShape::Increasing);
+    }
+}
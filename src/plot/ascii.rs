@@ -7,25 +7,48 @@
 //! Plot elements:
 //! - observed points: `o`
 //! - fitted curve: `-` line
+//! - optional generating baseline: `.` line (`--plot-baseline`)
 //! - optional highlights: `C` (cheap), `R` (rich)
+//! - optional bucket volatility: `~` line on its own scale, labelled `~x.xx%`
+//!   at the right edge (`--plot-vol`)
+//! - optional curve fingerprint in the header: `hash=xxxxxxxx` (`--plot-hash`)
 
 use std::collections::HashSet;
 
-use crate::domain::{BondResidual, CurveFile, FitResult};
-use crate::models::predict;
+use crate::domain::{BondResidual, CurveFile, FitResult, YUnit};
 use crate::report::Rankings;
 
-/// Render a plot for an in-memory fit result.
+/// Render a plot for an in-memory fit result, optionally overlaying the
+/// generating baseline (already in `unit`) and a volatility series (percent,
+/// right-hand axis). With `plot_hash` the header carries the curve's
+/// fingerprint.
+#[allow(clippy::too_many_arguments)]
 pub fn render_ascii_plot(
     residuals: &[BondResidual],
     fit: &FitResult,
     width: usize,
     height: usize,
     rankings: Option<&Rankings>,
+    unit: YUnit,
+    baseline: Option<&[(f64, f64)]>,
+    vol: Option<&[(f64, f64)]>,
+    plot_hash: bool,
 ) -> String {
     let (t_min, t_max) = tenor_range_from_residuals(residuals).unwrap_or((0.25, 30.0));
     let curve = sample_curve(&fit.model, t_min, t_max, width.max(2));
-    render_plot(residuals, Some(&curve), t_min, t_max, width, height, rankings)
+    let fingerprint = plot_hash.then(|| fit.model.fingerprint());
+    let lines = PlotLines { curve: Some(&curve), baseline, vol, fingerprint: fingerprint.as_deref() };
+    render_plot(residuals, lines, t_min, t_max, width, height, rankings, unit)
+}
+
+/// Line series of a plot; the `vol` series is scaled to its own range.
+#[derive(Clone, Copy, Default)]
+struct PlotLines<'a> {
+    curve: Option<&'a [(f64, f64)]>,
+    baseline: Option<&'a [(f64, f64)]>,
+    vol: Option<&'a [(f64, f64)]>,
+    /// `CurveModel::fingerprint` of `curve`, appended to the header.
+    fingerprint: Option<&'a str>,
 }
 
 /// Render a plot from a saved curve JSON file (curve only, no overlay points).
@@ -33,6 +56,7 @@ pub fn render_ascii_plot_from_curve_file_only(
     curve: &CurveFile,
     width: usize,
     height: usize,
+    plot_hash: bool,
 ) -> String {
     let (t_min, t_max) = curve_tenor_range(curve).unwrap_or((0.25, 30.0));
     let curve_points: Vec<(f64, f64)> = curve
@@ -43,7 +67,9 @@ pub fn render_ascii_plot_from_curve_file_only(
         .map(|(&t, &y)| (t, y))
         .collect();
 
-    render_plot(&[], Some(&curve_points), t_min, t_max, width, height, None)
+    let fingerprint = plot_hash.then(|| curve.model.fingerprint());
+    let lines = PlotLines { curve: Some(&curve_points), fingerprint: fingerprint.as_deref(), ..PlotLines::default() };
+    render_plot(&[], lines, t_min, t_max, width, height, None, curve.unit)
 }
 
 /// Render a plot from a saved curve JSON file with overlay points.
@@ -52,6 +78,7 @@ pub fn render_ascii_plot_from_curve_file(
     curve: &CurveFile,
     width: usize,
     height: usize,
+    plot_hash: bool,
 ) -> String {
     let (t_min, t_max) = curve_tenor_range(curve).unwrap_or((0.25, 30.0));
     let curve_points: Vec<(f64, f64)> = curve
@@ -62,30 +89,43 @@ pub fn render_ascii_plot_from_curve_file(
         .map(|(&t, &y)| (t, y))
         .collect();
 
-    render_plot(residuals, Some(&curve_points), t_min, t_max, width, height, None)
+    let fingerprint = plot_hash.then(|| curve.model.fingerprint());
+    let lines = PlotLines { curve: Some(&curve_points), fingerprint: fingerprint.as_deref(), ..PlotLines::default() };
+    render_plot(residuals, lines, t_min, t_max, width, height, None, curve.unit)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_plot(
     residuals: &[BondResidual],
-    curve_points: Option<&[(f64, f64)]>,
+    lines: PlotLines<'_>,
     t_min: f64,
     t_max: f64,
     width: usize,
     height: usize,
     rankings: Option<&Rankings>,
+    unit: YUnit,
 ) -> String {
     let width = width.max(10);
     let height = height.max(5);
 
-    // Determine y-range from observed points and curve points.
-    let (y_min, y_max) = y_range(residuals, curve_points).unwrap_or((0.0, 1.0));
+    let PlotLines { curve: curve_points, baseline, vol, fingerprint } = lines;
+    // Determine y-range from observed points and curve points (and the baseline).
+    let (y_min, y_max) = y_range(residuals, curve_points, baseline).unwrap_or((0.0, 1.0));
     let (y_min, y_max) = pad_range(y_min, y_max, 0.05);
 
     let mut grid = vec![vec![' '; width]; height];
 
-    // Draw curve first (so points can overlay).
+    // Draw curve first (so points can overlay); the baseline only fills gaps.
     if let Some(curve) = curve_points {
-        draw_curve(&mut grid, curve, t_min, t_max, y_min, y_max);
+        draw_curve(&mut grid, curve, t_min, t_max, y_min, y_max, '-');
+    }
+    if let Some(baseline) = baseline {
+        draw_curve(&mut grid, baseline, t_min, t_max, y_min, y_max, '.');
+    }
+    // Volatility on its own range; it never widens the spread axis.
+    let vol_range = vol.and_then(|v| y_range(&[], Some(v), None)).map(|(lo, hi)| pad_range(lo, hi, 0.05));
+    if let (Some(vol), Some((v_min, v_max))) = (vol, vol_range) {
+        draw_curve(&mut grid, vol, t_min, t_max, v_min, v_max, '~');
     }
 
     // Highlight sets (ids).
@@ -116,11 +156,28 @@ fn render_plot(
     // Build final string. We include a small header with ranges.
     let mut out = String::new();
     out.push_str(&format!(
-        "Plot: tenor=[{t_min:.3}, {t_max:.3}] years | y=[{y_min:.2}, {y_max:.2}]bp\n"
+        "Plot: tenor=[{t_min:.3}, {t_max:.3}] years | y=[{y_min:.*}, {y_max:.*}]{}",
+        unit.decimals(),
+        unit.decimals(),
+        unit.label()
     ));
+    if let Some((v_min, v_max)) = vol_range {
+        out.push_str(&format!(" | ~ vol=[{v_min:.2}, {v_max:.2}]% daily (right axis)"));
+    }
+    if let Some(hash) = fingerprint {
+        out.push_str(&format!(" | hash={hash}"));
+    }
+    out.push('\n');
 
-    for row in grid {
+    let last = grid.len() - 1;
+    for (i, row) in grid.into_iter().enumerate() {
         out.push_str(&row.into_iter().collect::<String>());
+        // Right-hand vol axis: its top and bottom values, marked with `~`.
+        match vol_range {
+            Some((_, v_max)) if i == 0 => out.push_str(&format!(" ~{v_max:.2}%")),
+            Some((v_min, _)) if i == last => out.push_str(&format!(" ~{v_min:.2}%")),
+            _ => {}
+        }
         out.push('\n');
     }
 
@@ -161,13 +218,17 @@ fn sample_curve(model: &crate::domain::CurveModel, t_min: f64, t_max: f64, n: us
     for i in 0..n {
         let u = i as f64 / (n as f64 - 1.0);
         let t = t_min + u * (t_max - t_min);
-        let y = predict(model.name, t, &model.betas, &model.taus);
+        let y = model.eval(t);
         out.push((t, y));
     }
     out
 }
 
-fn y_range(residuals: &[BondResidual], curve: Option<&[(f64, f64)]>) -> Option<(f64, f64)> {
+fn y_range(
+    residuals: &[BondResidual],
+    curve: Option<&[(f64, f64)]>,
+    baseline: Option<&[(f64, f64)]>,
+) -> Option<(f64, f64)> {
     let mut min_y = f64::INFINITY;
     let mut max_y = f64::NEG_INFINITY;
 
@@ -175,8 +236,8 @@ fn y_range(residuals: &[BondResidual], curve: Option<&[(f64, f64)]>) -> Option<(
         min_y = min_y.min(r.point.y_obs);
         max_y = max_y.max(r.point.y_obs);
     }
-    if let Some(curve) = curve {
-        for &(_, y) in curve {
+    for line in [curve, baseline].into_iter().flatten() {
+        for &(_, y) in line {
             min_y = min_y.min(y);
             max_y = max_y.max(y);
         }
@@ -208,7 +269,7 @@ fn map_y(y: f64, y_min: f64, y_max: f64, height: usize) -> usize {
     (height as f64 - 1.0 - (u * (height as f64 - 1.0))).round() as usize
 }
 
-fn draw_curve(grid: &mut [Vec<char>], curve: &[(f64, f64)], t_min: f64, t_max: f64, y_min: f64, y_max: f64) {
+fn draw_curve(grid: &mut [Vec<char>], curve: &[(f64, f64)], t_min: f64, t_max: f64, y_min: f64, y_max: f64, ch: char) {
     if curve.len() < 2 {
         return;
     }
@@ -220,9 +281,9 @@ fn draw_curve(grid: &mut [Vec<char>], curve: &[(f64, f64)], t_min: f64, t_max: f
         let x = map_x(t, t_min, t_max, width);
         let yy = map_y(y, y_min, y_max, height);
         if let Some((x0, y0)) = prev {
-            draw_line(grid, x0, y0, x, yy, '-');
-        } else {
-            grid[yy][x] = '-';
+            draw_line(grid, x0, y0, x, yy, ch);
+        } else if grid[yy][x] == ' ' {
+            grid[yy][x] = ch;
         }
         prev = Some((x, yy));
     }
@@ -269,42 +330,11 @@ fn draw_line(grid: &mut [Vec<char>], x0: usize, y0: usize, x1: usize, y1: usize,
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
-    use crate::domain::{BondExtras, BondMeta, BondPoint, FitQuality, CurveModel, ModelKind};
+    use crate::domain::{FitQuality, CurveModel, ModelKind};
 
     #[test]
     fn plot_golden_snapshot_small() {
-        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
-        let points = vec![
-            BondResidual {
-                point: BondPoint {
-                    id: "B1".to_string(),
-                    asof_date: asof,
-                    maturity_date: asof,
-                    tenor: 1.0,
-                    y_obs: 100.0,
-                    weight: 1.0,
-                    meta: BondMeta::default(),
-                    extras: BondExtras::default(),
-                },
-                y_fit: 100.0,
-                residual: 0.0,
-            },
-            BondResidual {
-                point: BondPoint {
-                    id: "B2".to_string(),
-                    asof_date: asof,
-                    maturity_date: asof,
-                    tenor: 10.0,
-                    y_obs: 110.0,
-                    weight: 1.0,
-                    meta: BondMeta::default(),
-                    extras: BondExtras::default(),
-                },
-                y_fit: 100.0,
-                residual: 10.0,
-            },
-        ];
+        let points = vec![BondResidual::fixture("B1", 1.0, 0.0), BondResidual::fixture("B2", 10.0, 10.0)];
 
         let fit = FitResult {
             model: CurveModel {
@@ -312,11 +342,13 @@ mod tests {
                 display_name: "NS".to_string(),
                 betas: vec![100.0, 0.0, 0.0],
                 taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
             },
             quality: FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 1 },
         };
 
-        let txt = render_ascii_plot(&points, &fit, 10, 5, None);
+        let txt = render_ascii_plot(&points, &fit, 10, 5, None, YUnit::Bp, None, None, false);
         let expected = concat!(
             "Plot: tenor=[1.000, 10.000] years | y=[99.50, 110.50]bp\n",
             "         o\n",
@@ -327,4 +359,57 @@ mod tests {
         );
         assert_eq!(txt, expected);
     }
+
+    /// The golden-snapshot inputs: two bonds around a flat 100bp NS curve.
+    fn golden_inputs() -> (Vec<BondResidual>, FitResult) {
+        let points = vec![BondResidual::fixture("B1", 1.0, 0.0), BondResidual::fixture("B2", 10.0, 10.0)];
+        let fit = FitResult {
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, 0.0, 0.0],
+                taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 1 },
+        };
+        (points, fit)
+    }
+
+    #[test]
+    fn baseline_overlay_is_dotted_and_widens_the_range() {
+        let (points, fit) = golden_inputs();
+        // The baseline fills only empty cells.
+        let baseline = [(1.0, 90.0), (10.0, 110.0)];
+        let txt = render_ascii_plot(&points, &fit, 10, 5, None, YUnit::Bp, Some(&baseline), None, false);
+        let lines: Vec<&str> = txt.lines().collect();
+        assert!(lines[0].ends_with("y=[89.00, 111.00]bp"), "{txt}");
+        assert_eq!(lines[5], "..        ");
+        assert!(lines[1..].iter().any(|l| l.contains('.') && l.contains('o')), "{txt}");
+    }
+
+    #[test]
+    fn vol_overlay_uses_its_own_right_hand_scale() {
+        let (points, fit) = golden_inputs();
+        // The spread range is unchanged.
+        let vol = [(1.0, 0.8), (10.0, 1.2)];
+        let txt = render_ascii_plot(&points, &fit, 10, 5, None, YUnit::Bp, None, Some(&vol), false);
+        let lines: Vec<&str> = txt.lines().collect();
+        assert!(lines[0].contains("y=[99.50, 110.50]bp | ~ vol=[0.78, 1.22]% daily (right axis)"), "{txt}");
+        assert_eq!(lines[1], "        ~o ~1.22%");
+        assert_eq!(lines[5], "o--------- ~0.78%");
+        assert!(lines[2..5].iter().any(|l| l.contains('~')), "{txt}");
+    }
+
+    #[test]
+    fn plot_hash_only_extends_the_header() {
+        let (points, fit) = golden_inputs();
+        let plain = render_ascii_plot(&points, &fit, 10, 5, None, YUnit::Bp, None, None, false);
+        let txt = render_ascii_plot(&points, &fit, 10, 5, None, YUnit::Bp, None, None, true);
+        let (header, body) = txt.split_once('\n').unwrap();
+        let (plain_header, plain_body) = plain.split_once('\n').unwrap();
+        assert_eq!(header, format!("{plain_header} | hash={}", fit.model.fingerprint()));
+        assert_eq!(body, plain_body);
+    }
 }
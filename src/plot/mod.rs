@@ -1,6 +1,7 @@
-//! Terminal plotting (ASCII/Unicode).
+//! Plotting: terminal (ASCII/Unicode) and SVG snapshots.
 
 pub mod ascii;
+pub mod svg;
 
 pub use ascii::*;
-
+pub use svg::*;
@@ -0,0 +1,245 @@
+//! Minimal SVG rendering of the RV chart.
+//!
+//! This mirrors what the TUI chart shows (observed points, cheap/rich highlights,
+//! fitted curve) so a snapshot of the current view can be saved to disk.
+//!
+//! The output is hand-written SVG (no plotting dependency) and deterministic for
+//! identical inputs.
+
+use std::fmt::Write;
+
+use crate::domain::YUnit;
+
+/// Chart data to render as SVG.
+pub struct SvgChart<'a> {
+    /// Chart title (rendered above the plot area).
+    pub title: &'a str,
+    /// Line series for the fitted curve.
+    pub curve: &'a [(f64, f64)],
+    /// Dashed series for the generating baseline (may be empty).
+    pub baseline: &'a [(f64, f64)],
+    /// Scatter series for all observed bonds.
+    pub points: &'a [(f64, f64)],
+    /// Scatter series for the highlighted cheap names.
+    pub cheap: &'a [(f64, f64)],
+    /// Scatter series for the highlighted rich names.
+    pub rich: &'a [(f64, f64)],
+    /// X bounds (tenor in years).
+    pub x_bounds: [f64; 2],
+    /// Y bounds.
+    pub y_bounds: [f64; 2],
+    /// X axis label.
+    pub x_label: &'a str,
+    /// Y axis label.
+    pub y_label: &'a str,
+    /// Unit of the y values (sets the tick label precision).
+    pub y_unit: YUnit,
+}
+
+/// Canvas size in pixels.
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 540.0;
+
+/// Margins around the plot area (left, right, top, bottom).
+const MARGIN_L: f64 = 70.0;
+const MARGIN_R: f64 = 20.0;
+const MARGIN_T: f64 = 40.0;
+const MARGIN_B: f64 = 55.0;
+
+/// Number of tick labels per axis.
+const TICKS: usize = 5;
+
+/// Render the chart as a standalone SVG document.
+pub fn render_svg_chart(chart: &SvgChart<'_>) -> String {
+    let [x0, x1] = chart.x_bounds;
+    let [y0, y1] = chart.y_bounds;
+    let valid = x0.is_finite() && x1.is_finite() && y0.is_finite() && y1.is_finite() && x1 > x0 && y1 > y0;
+    let (x0, x1, y0, y1) = if valid { (x0, x1, y0, y1) } else { (0.0, 1.0, 0.0, 1.0) };
+
+    let plot_w = WIDTH - MARGIN_L - MARGIN_R;
+    let plot_h = HEIGHT - MARGIN_T - MARGIN_B;
+    let map_x = |x: f64| MARGIN_L + (x - x0) / (x1 - x0) * plot_w;
+    let map_y = |y: f64| MARGIN_T + (1.0 - (y - y0) / (y1 - y0)) * plot_h;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="monospace" font-size="12">"#
+    );
+    let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        out,
+        r#"<text x="{:.1}" y="24" text-anchor="middle" font-size="15">{}</text>"#,
+        WIDTH / 2.0,
+        escape(chart.title)
+    );
+
+    // Plot frame.
+    let _ = writeln!(
+        out,
+        r##"<rect x="{MARGIN_L}" y="{MARGIN_T}" width="{plot_w}" height="{plot_h}" fill="none" stroke="#888"/>"##
+    );
+
+    // Ticks + labels.
+    for i in 0..TICKS {
+        let u = i as f64 / (TICKS - 1) as f64;
+        let xv = x0 + u * (x1 - x0);
+        let yv = y0 + u * (y1 - y0);
+        let px = map_x(xv);
+        let py = map_y(yv);
+        let _ = writeln!(
+            out,
+            r##"<line x1="{px:.1}" y1="{:.1}" x2="{px:.1}" y2="{:.1}" stroke="#888"/>"##,
+            MARGIN_T + plot_h,
+            MARGIN_T + plot_h + 5.0
+        );
+        let _ = writeln!(
+            out,
+            r#"<text x="{px:.1}" y="{:.1}" text-anchor="middle">{xv:.1}</text>"#,
+            MARGIN_T + plot_h + 18.0
+        );
+        let _ = writeln!(
+            out,
+            r##"<line x1="{:.1}" y1="{py:.1}" x2="{MARGIN_L}" y2="{py:.1}" stroke="#888"/>"##,
+            MARGIN_L - 5.0
+        );
+        let _ = writeln!(
+            out,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{yv:.prec$}</text>"#,
+            MARGIN_L - 8.0,
+            py + 4.0,
+            prec = chart.y_unit.decimals()
+        );
+    }
+
+    // Axis labels.
+    let _ = writeln!(
+        out,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+        MARGIN_L + plot_w / 2.0,
+        HEIGHT - 12.0,
+        escape(chart.x_label)
+    );
+    let _ = writeln!(
+        out,
+        r#"<text x="16" y="{:.1}" text-anchor="middle" transform="rotate(-90 16 {:.1})">{}</text>"#,
+        MARGIN_T + plot_h / 2.0,
+        MARGIN_T + plot_h / 2.0,
+        escape(chart.y_label)
+    );
+
+    // Same render order as the TUI: points, highlights, then the curve on top.
+    write_scatter(&mut out, chart.points, "#555", 2.5, &map_x, &map_y);
+    write_scatter(&mut out, chart.cheap, "#2a9d2a", 3.5, &map_x, &map_y);
+    write_scatter(&mut out, chart.rich, "#d62828", 3.5, &map_x, &map_y);
+
+    if chart.baseline.len() >= 2 {
+        let pts: Vec<String> = chart
+            .baseline
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|&(x, y)| format!("{:.2},{:.2}", map_x(x), map_y(y)))
+            .collect();
+        let _ = writeln!(
+            out,
+            r##"<polyline points="{}" fill="none" stroke="#888" stroke-width="1.5" stroke-dasharray="2 4"/>"##,
+            pts.join(" ")
+        );
+    }
+
+    if chart.curve.len() >= 2 {
+        let pts: Vec<String> = chart
+            .curve
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|&(x, y)| format!("{:.2},{:.2}", map_x(x), map_y(y)))
+            .collect();
+        let _ = writeln!(
+            out,
+            r##"<polyline points="{}" fill="none" stroke="#0096c7" stroke-width="2"/>"##,
+            pts.join(" ")
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn write_scatter(
+    out: &mut String,
+    points: &[(f64, f64)],
+    color: &str,
+    radius: f64,
+    map_x: &dyn Fn(f64) -> f64,
+    map_y: &dyn Fn(f64) -> f64,
+) {
+    for &(x, y) in points {
+        if !(x.is_finite() && y.is_finite()) {
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{radius}" fill="{color}"/>"#,
+            map_x(x),
+            map_y(y)
+        );
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_contains_all_series() {
+        let curve = [(1.0, 100.0), (10.0, 110.0)];
+        let points = [(2.0, 101.0)];
+        let cheap = [(3.0, 108.0)];
+        let rich = [(4.0, 95.0)];
+        let svg = render_svg_chart(&SvgChart {
+            title: "RV Curve - BBB <NS>",
+            curve: &curve,
+            baseline: &[],
+            points: &points,
+            cheap: &cheap,
+            rich: &rich,
+            x_bounds: [1.0, 10.0],
+            y_bounds: [90.0, 120.0],
+            x_label: "tenor (yrs)",
+            y_label: "oas (bp)",
+            y_unit: YUnit::Bp,
+        });
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("<polyline").count(), 1);
+        assert!(svg.contains("RV Curve - BBB &lt;NS&gt;"));
+        assert!(svg.contains(">97.50</text>"), "{svg}");
+    }
+
+    #[test]
+    fn decimal_ticks_keep_their_precision() {
+        let curve = [(1.0, 0.0100), (10.0, 0.0110)];
+        let svg = render_svg_chart(&SvgChart {
+            title: "RV Curve - BBB",
+            curve: &curve,
+            baseline: &[],
+            points: &[],
+            cheap: &[],
+            rich: &[],
+            x_bounds: [1.0, 10.0],
+            y_bounds: [0.0090, 0.0120],
+            x_label: "tenor (yrs)",
+            y_label: "oas (decimal)",
+            y_unit: YUnit::Decimal,
+        });
+        // `{:.0}` would label every tick "0".
+        assert!(svg.contains(">0.009750</text>"), "{svg}");
+        assert!(!svg.contains(">0</text>"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Residual serial-correlation diagnostics in tenor order.
+//!
+//! A well-specified curve leaves residuals that look like noise when sorted by
+//! tenor. Long stretches of same-signed residuals (few sign runs, high lag-1
+//! autocorrelation) mean the model shape is missing a feature of the data.
+
+use crate::domain::BondResidual;
+
+/// Below this many residuals the statistics are too noisy to report.
+pub const MIN_DIAGNOSTIC_N: usize = 12;
+
+/// Runs-test z below `-RUNS_Z_CRIT` (too few sign changes) is flagged. Only the
+/// low tail matters here: too many runs means alternation, not a missed shape.
+const RUNS_Z_CRIT: f64 = 1.96;
+
+/// Lag-1 autocorrelation above which residuals are flagged.
+const AUTOCORR_CRIT: f64 = 0.5;
+
+/// Serial-correlation statistics for residuals sorted by tenor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResidualDiagnostics {
+    pub n: usize,
+    /// Lag-1 autocorrelation of residuals in tenor order.
+    pub lag1_autocorr: f64,
+    /// Number of sign runs (zero residuals are skipped).
+    pub runs: usize,
+    /// Runs expected under randomness for the observed sign counts.
+    pub expected_runs: f64,
+    /// Wald-Wolfowitz z-score (`None` when all residuals share a sign).
+    pub runs_z: Option<f64>,
+    /// True when either statistic indicates strong serial correlation.
+    pub correlated: bool,
+}
+
+/// Compute tenor-order diagnostics, or `None` below `MIN_DIAGNOSTIC_N`.
+pub fn residual_diagnostics(residuals: &[BondResidual]) -> Option<ResidualDiagnostics> {
+    if residuals.len() < MIN_DIAGNOSTIC_N {
+        return None;
+    }
+    let mut sorted: Vec<(f64, f64)> = residuals.iter().map(|r| (r.point.tenor, r.residual)).collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let e: Vec<f64> = sorted.into_iter().map(|(_, r)| r).collect();
+
+    let n = e.len();
+    let mean = e.iter().sum::<f64>() / n as f64;
+    let var: f64 = e.iter().map(|r| (r - mean).powi(2)).sum();
+    let cov: f64 = e.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum();
+    let lag1_autocorr = if var > 0.0 { cov / var } else { 0.0 };
+
+    let signs: Vec<bool> = e.iter().filter(|r| **r != 0.0).map(|r| *r > 0.0).collect();
+    let runs = if signs.is_empty() { 0 } else { 1 + signs.windows(2).filter(|w| w[0] != w[1]).count() };
+    let pos = signs.iter().filter(|s| **s).count() as f64;
+    let neg = signs.len() as f64 - pos;
+    let total = pos + neg;
+    let expected_runs = if total > 0.0 { 1.0 + 2.0 * pos * neg / total } else { 0.0 };
+    let var_runs = if total > 1.0 {
+        2.0 * pos * neg * (2.0 * pos * neg - total) / (total * total * (total - 1.0))
+    } else {
+        0.0
+    };
+    let runs_z = (var_runs > 0.0).then(|| (runs as f64 - expected_runs) / var_runs.sqrt());
+
+    let correlated = lag1_autocorr > AUTOCORR_CRIT || runs_z.is_some_and(|z| z < -RUNS_Z_CRIT);
+    Some(ResidualDiagnostics {
+        n,
+        lag1_autocorr,
+        runs,
+        expected_runs,
+        runs_z,
+        correlated,
+    })
+}
+
+/// Format the `--diagnostics` section.
+pub fn format_residual_diagnostics(residuals: &[BondResidual]) -> String {
+    let mut out = String::from("Residual diagnostics (tenor order):\n");
+    let Some(d) = residual_diagnostics(residuals) else {
+        out.push_str(&format!(
+            "  insufficient data (n={} < {MIN_DIAGNOSTIC_N})\n",
+            residuals.len()
+        ));
+        return out;
+    };
+    out.push_str(&format!("  lag-1 autocorrelation: {:.3}\n", d.lag1_autocorr));
+    let z = d.runs_z.map(|z| format!("{z:.2}")).unwrap_or_else(|| "n/a".to_string());
+    out.push_str(&format!(
+        "  sign runs: {} (expected {:.1}, z={z})\n",
+        d.runs, d.expected_runs
+    ));
+    if d.correlated {
+        out.push_str("  FLAG: residuals are serially correlated in tenor; the curve shape may be misspecified\n");
+    } else {
+        out.push_str("  no strong serial correlation\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_blocks_of_same_signed_residuals() {
+        // Alternating signs: many runs, negative autocorrelation, not flagged.
+        let alternating: Vec<f64> = (0..20).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+        let d = residual_diagnostics(&BondResidual::fixtures(&alternating)).unwrap();
+        assert_eq!(d.runs, 20);
+        assert!(d.lag1_autocorr < 0.0);
+        assert!(!d.correlated);
+
+        // Two long blocks: too few runs and strong positive autocorrelation.
+        let blocks: Vec<f64> = (0..20).map(|i| if i < 10 { 2.0 } else { -2.0 }).collect();
+        let d = residual_diagnostics(&BondResidual::fixtures(&blocks)).unwrap();
+        assert_eq!(d.runs, 2);
+        assert!((d.expected_runs - 11.0).abs() < 1e-12);
+        assert!(d.runs_z.unwrap() < -RUNS_Z_CRIT);
+        assert!(d.correlated);
+
+        let few = BondResidual::fixtures(&[1.0, -1.0, 1.0]);
+        assert!(residual_diagnostics(&few).is_none());
+        assert!(format_residual_diagnostics(&few).contains("insufficient data (n=3 < 12)"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Saved curve vs today's FRED baseline (`rv check`).
+//!
+//! Every grid tenor of a curve file is compared with `baseline_curve` for the
+//! file's `rating` on a fresh snapshot; the largest absolute gap decides
+//! whether the curve has drifted past `--max-deviation`.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::data::FredSnapshot;
+use crate::domain::{CurveFile, RatingBand, YUnit};
+use crate::error::AppError;
+
+/// Exit code of `rv check` when the curve has drifted past the threshold.
+pub const DRIFT_EXIT_CODE: u8 = 5;
+
+/// A curve file checked against one snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurveDrift {
+    /// The curve's as-of date.
+    pub curve_date: NaiveDate,
+    /// The snapshot's observation date.
+    pub snapshot_date: NaiveDate,
+    pub rating: RatingBand,
+    pub unit: YUnit,
+    /// Threshold in `unit`.
+    pub max_deviation: f64,
+    pub rows: Vec<DriftRow>,
+}
+
+/// Saved curve and baseline at one grid tenor, in `CurveDrift::unit`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftRow {
+    pub tenor: f64,
+    pub curve: f64,
+    pub baseline: f64,
+    /// `curve - baseline`.
+    pub deviation: f64,
+}
+
+impl CurveDrift {
+    /// Row with the largest absolute deviation.
+    pub fn worst(&self) -> Option<&DriftRow> {
+        self.rows.iter().max_by(|a, b| a.deviation.abs().total_cmp(&b.deviation.abs()))
+    }
+
+    /// Whether the worst deviation is beyond `max_deviation`.
+    pub fn drifted(&self) -> bool {
+        self.worst().is_some_and(|r| r.deviation.abs() > self.max_deviation)
+    }
+}
+
+/// Compare `curve`'s grid with `snapshot`'s baseline for the curve's rating;
+/// `max_deviation_bp` is converted to the curve's unit.
+pub fn curve_drift(curve: &CurveFile, snapshot: &FredSnapshot, max_deviation_bp: f64) -> Result<CurveDrift, AppError> {
+    if !(max_deviation_bp.is_finite() && max_deviation_bp >= 0.0) {
+        return Err(AppError::new(2, format!("Invalid --max-deviation {max_deviation_bp}: must be a finite number >= 0.")));
+    }
+    let grid = &curve.grid;
+    if grid.tenor_years.is_empty() || grid.tenor_years.len() != grid.y.len() {
+        return Err(AppError::new(
+            2,
+            format!(
+                "Curve grid has {} tenor(s) and {} value(s); nothing to check.",
+                grid.tenor_years.len(),
+                grid.y.len()
+            ),
+        ));
+    }
+
+    let unit = curve.unit;
+    let rows = grid
+        .tenor_years
+        .iter()
+        .zip(&grid.y)
+        .map(|(&tenor, &y)| {
+            let baseline = crate::data::sample::baseline_curve(snapshot, curve.rating, tenor)? * unit.per_bp();
+            Ok(DriftRow { tenor, curve: y, baseline, deviation: y - baseline })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    Ok(CurveDrift {
+        curve_date: curve.asof_date,
+        snapshot_date: snapshot.date,
+        rating: curve.rating,
+        unit,
+        max_deviation: max_deviation_bp * unit.per_bp(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn drift_uses_the_curve_rating_and_unit() {
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "-r", "bb", "--output-unit", "decimal"]).unwrap();
+        let config = crate::app::fit_config_from_args(&args);
+        let snapshot = crate::data::sample::canned_snapshot();
+        let run = crate::app::pipeline::run_fit_with_snapshot(&config, snapshot.clone()).unwrap();
+        let path = std::env::temp_dir().join(format!("rv-drift-curve-{}.json", std::process::id()));
+        crate::io::write_curve_json(&path, &run.selection.best, &run.ingest, &config).unwrap();
+        let curve = crate::io::curve::read_curve_json(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let drift = curve_drift(&curve, &snapshot, 1000.0).unwrap();
+        assert_eq!((drift.rating, drift.unit), (RatingBand::BB, YUnit::Decimal));
+        assert_eq!(drift.rows.len(), curve.grid.tenor_years.len());
+        assert!((drift.max_deviation - 0.1).abs() < 1e-12);
+        let row = &drift.rows[0];
+        let expected = crate::data::sample::baseline_curve(&snapshot, RatingBand::BB, row.tenor).unwrap() * 1e-4;
+        assert!((row.baseline - expected).abs() < 1e-12, "{row:?}");
+        assert!(!drift.drifted());
+
+        // Widening the BB index by 100bp leaves the saved curve well below today's baseline.
+        let mut wider = snapshot.clone();
+        *wider.ratings_bp.get_mut(&RatingBand::BB).unwrap() += 100.0;
+        let after = curve_drift(&curve, &wider, 25.0).unwrap();
+        assert!(after.drifted() && after.worst().unwrap().deviation < 0.0, "{:?}", after.worst());
+
+        let text = crate::report::format_curve_drift(&after);
+        assert!(text.contains("DRIFT") && text.contains("rating BB"), "{text}");
+        assert!(curve_drift(&curve, &snapshot, -1.0).is_err());
+
+        // `rv check` skips missing bands: only the curve's own band matters.
+        let mut skipped = snapshot.clone();
+        skipped.ratings_bp.remove(&RatingBand::CCC);
+        assert!(!curve_drift(&curve, &skipped, 25.0).unwrap().drifted());
+        skipped.ratings_bp.remove(&RatingBand::BB);
+        assert_eq!(curve_drift(&curve, &skipped, 25.0).unwrap_err().exit_code(), 4);
+    }
+}
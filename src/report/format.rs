@@ -4,24 +4,483 @@
 //! - the math/fitting code stays clean and testable
 //! - output changes are localized (important for future snapshot tests)
 
-use crate::domain::{BondPoint, BondResidual, FitConfig, FitResult, YKind};
+use serde::Serialize;
+
+use crate::report::{BatchCell, PhaseTimings, RunOutput};
+use crate::data::{SeriesMap, SnapshotDiff};
+use crate::domain::{Basis, BondPoint, BondResidual, BinEdges, CurveFile, FitConfig, FitResult, ModelSpec, NumberLocale, RankBy, RatingBand, SignalWeights, TenorPair, YTransform, YUnit};
 use crate::error::AppError;
-use crate::fit::selection::FitSelection;
+use crate::fit::influence::BondInfluence;
+use crate::fit::selection::{FitSelection, ModelMetrics};
+use crate::io::curve::{grid_range, CurveValue, PillarTable};
 use crate::io::ingest::{IngestedData, InputSpec};
-use crate::models::predict;
+use crate::math::BranchCheck;
+use crate::report::drift::CurveDrift;
+use crate::report::moves::CurveMoves;
+use crate::report::summary::RunSummary;
 
-/// Cheap/rich rankings (top-N each side).
-#[derive(Debug, Clone)]
+/// Cheap/rich rankings (top-N each side; a bond is on at most one side).
+#[derive(Debug, Clone, Default)]
 pub struct Rankings {
     pub cheap: Vec<BondResidual>,
     pub rich: Vec<BondResidual>,
 }
 
+impl Rankings {
+    /// `cheap` or `rich` when bond `id` is on that list.
+    pub fn highlight(&self, id: &str) -> Option<&'static str> {
+        if self.cheap.iter().any(|r| r.point.id == id) {
+            Some("cheap")
+        } else if self.rich.iter().any(|r| r.point.id == id) {
+            Some("rich")
+        } else {
+            None
+        }
+    }
+}
+
+/// A bond's residual today vs its residual against a previously saved curve.
+#[derive(Debug, Clone)]
+pub struct ResidualShift {
+    pub id: String,
+    pub tenor: f64,
+    pub residual: f64,
+    /// Residual against the previous curve; `None` when the tenor lies outside the
+    /// previous curve's grid range (we don't extrapolate an old curve).
+    pub prev_residual: Option<f64>,
+    /// `residual - prev_residual` (positive = cheapened since the previous curve).
+    pub shift: Option<f64>,
+}
+
+/// Compute per-bond residual shifts against a previous curve.
+pub fn compute_residual_shifts(residuals: &[BondResidual], previous: &CurveFile) -> Vec<ResidualShift> {
+    let (t_lo, t_hi) = grid_range(previous).unwrap_or((f64::INFINITY, f64::NEG_INFINITY));
+    let model = &previous.model;
+
+    residuals
+        .iter()
+        .map(|r| {
+            let tenor = r.point.tenor;
+            let prev_residual = if tenor >= t_lo && tenor <= t_hi {
+                let prev_fit = model.eval(tenor);
+                prev_fit.is_finite().then_some(r.point.y_obs - prev_fit)
+            } else {
+                None
+            };
+            ResidualShift {
+                id: r.point.id.clone(),
+                tenor,
+                residual: r.residual,
+                prev_residual,
+                shift: prev_residual.map(|prev| r.residual - prev),
+            }
+        })
+        .collect()
+}
+
+/// Format the residual-shift report.
+///
+/// Rows are ordered by today's residual (cheap first), or by shift when
+/// `by_shift` is set. Bonds outside the previous curve's range are listed last.
+pub fn format_residual_shifts(shifts: &[ResidualShift], previous: &CurveFile, by_shift: bool) -> String {
+    let mut rows: Vec<&ResidualShift> = shifts.iter().collect();
+    rows.sort_by(|a, b| {
+        let key = |s: &ResidualShift| if by_shift { s.shift } else { s.prev_residual.map(|_| s.residual) };
+        match (key(a), key(b)) {
+            (Some(x), Some(y)) => y.partial_cmp(&x).unwrap_or(std::cmp::Ordering::Equal),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.residual.partial_cmp(&a.residual).unwrap_or(std::cmp::Ordering::Equal),
+        }
+    });
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Residual shift vs previous curve (as-of {}, {} {}):\n",
+        previous.asof_date,
+        previous.rating.display_name(),
+        previous.model.display_name
+    ));
+    out.push_str(&format!(
+        "{:<24} {:>8} {:>12} {:>12} {:>12}\n",
+        "id", "tenor", "resid_now", "resid_prev", "shift"
+    ));
+    out.push_str(&format!("{:-<24} {:-<8} {:-<12} {:-<12} {:-<12}\n", "", "", "", "", ""));
+
+    let mut out_of_range = 0;
+    for s in rows {
+        let fmt_opt = |v: Option<f64>| v.map(|x| format!("{x:.2}")).unwrap_or_else(|| "n/a".to_string());
+        if s.prev_residual.is_none() {
+            out_of_range += 1;
+        }
+        out.push_str(&format!(
+            "{:<24} {:>8.3} {:>12.2} {:>12} {:>12}\n",
+            truncate(&s.id, 24),
+            s.tenor,
+            s.residual,
+            fmt_opt(s.prev_residual),
+            fmt_opt(s.shift),
+        ));
+    }
+    if out_of_range > 0 {
+        out.push_str(&format!(
+            "({out_of_range} bond(s) outside the previous curve's tenor range: n/a)\n"
+        ));
+    }
+
+    out
+}
+
+/// Format the most influential bonds (leave-one-out DFFIT), top-N by magnitude.
+pub fn format_influence(influence: &[BondInfluence], top_n: usize) -> String {
+    let mut out = String::new();
+    out.push_str("Most influential bonds (leave-one-out change in fit at own tenor):\n");
+    out.push_str(&format!("{:<24} {:>8} {:>12}\n", "id", "tenor", "dffit"));
+    out.push_str(&format!("{:-<24} {:-<8} {:-<12}\n", "", "", ""));
+    for b in influence.iter().take(top_n) {
+        out.push_str(&format!("{:<24} {:>8.3} {:>12.3}\n", truncate(&b.id, 24), b.tenor, b.dffit));
+    }
+    out
+}
+
+/// Format the model-selection trace recorded by `fit_and_select`.
+pub fn format_selection_explanation(selection: &FitSelection) -> String {
+    let mut out = String::new();
+    out.push_str("Selection explanation:\n");
+    for (i, step) in selection.explanation.iter().enumerate() {
+        out.push_str(&format!("  {}. {step}\n", i + 1));
+    }
+    out
+}
+
+/// Format `rv list-ratings` (ratings only) or `rv list-series` (every input).
+pub fn format_series_map(map: &SeriesMap, all_series: bool) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{:<12} {}\n", "input", "series"));
+    out.push_str(&format!("{:-<12} {:-<16}\n", "", ""));
+    if all_series {
+        out.push_str(&format!("{:<12} {}\n", "overall", map.overall));
+        let labels = ["bucket 1-3y", "bucket 3-5y", "bucket 5-7y", "bucket 7-10y"];
+        for (label, id) in labels.iter().zip(map.buckets()) {
+            out.push_str(&format!("{label:<12} {id}\n"));
+        }
+    }
+    for band in RatingBand::ALL {
+        out.push_str(&format!("{:<12} {}\n", band.display_name(), map.rating(band)));
+    }
+    out
+}
+
+/// Format `rv compare-snapshot` output: one row per input, `n/a` where either
+/// date has no value.
+pub fn format_snapshot_diff(diff: &SnapshotDiff) -> String {
+    let cell = |v: Option<f64>, prec: usize| match v {
+        Some(v) => format!("{v:.prec$}"),
+        None => "n/a".to_string(),
+    };
+    let delta = |v: Option<f64>, prec: usize| match v {
+        Some(v) => format!("{v:+.prec$}"),
+        None => "n/a".to_string(),
+    };
+    let mut out = String::new();
+    out.push_str(&format!("Snapshot {} -> {}\n", diff.from_date, diff.to_date));
+    out.push_str(&format!(
+        "{:<12} {:<14} {:>9} {:>9} {:>8} {:>9} {:>9} {:>8}\n",
+        "input", "series", "from bp", "to bp", "Δ bp", "from vol%", "to vol%", "Δ vol%"
+    ));
+    out.push_str(&format!("{:-<12} {:-<14} {:-<9} {:-<9} {:-<8} {:-<9} {:-<9} {:-<8}\n", "", "", "", "", "", "", "", ""));
+    // Vols are daily log-return std devs, shown in percent.
+    let pct = |v: Option<f64>| v.map(|v| v * 100.0);
+    for r in &diff.rows {
+        out.push_str(&format!(
+            "{:<12} {:<14} {:>9} {:>9} {:>8} {:>9} {:>9} {:>8}\n",
+            r.input,
+            r.series,
+            cell(r.level_from, 2),
+            cell(r.level_to, 2),
+            delta(r.level_delta, 2),
+            cell(pct(r.vol_from), 3),
+            cell(pct(r.vol_to), 3),
+            delta(pct(r.vol_delta), 3)
+        ));
+    }
+    out
+}
+
+/// Format `rv compare-dates` output: both selected curves, then one row per
+/// tenor splitting the curve move into its input- and structure-driven parts.
+pub fn format_curve_moves(moves: &CurveMoves) -> String {
+    let unit = moves.unit;
+    let prec = unit.decimals();
+    let opt = |v: Option<f64>, prec: usize| match v {
+        Some(v) => format!("{v:+.prec$}"),
+        None => "n/a".to_string(),
+    };
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Curve moves {} -> {} | rating {} | {} | sample seed {} on both dates\n",
+        moves.from_date,
+        moves.to_date,
+        moves.rating.display_name(),
+        unit.label(),
+        moves.seed
+    ));
+    out.push_str(&format!("  from: {} taus={:.3?}\n", moves.from_model.display_name, moves.from_model.taus));
+    out.push_str(&format!("  to:   {} taus={:.3?}\n", moves.to_model.display_name, moves.to_model.taus));
+    out.push_str(if moves.structure_changed {
+        "  model structure changed: part of the move comes from re-selection\n"
+    } else {
+        "  model structure unchanged: the move is input-driven\n"
+    });
+    out.push_str(&format!(
+        "{:>8} {:>10} {:>10} {:>10} {:>10} {:>12} {:>12} {:>8}\n",
+        "tenor", "Δ input", "from", "to", "Δ curve", "input-driven", "structure", "ampl"
+    ));
+    out.push_str(&format!("{:-<8} {:-<10} {:-<10} {:-<10} {:-<10} {:-<12} {:-<12} {:-<8}\n", "", "", "", "", "", "", "", ""));
+    for r in &moves.rows {
+        out.push_str(&format!(
+            "{:>8.2} {:>+10.prec$} {:>10.prec$} {:>10.prec$} {:>+10.prec$} {:>12} {:>12} {:>8}\n",
+            r.tenor,
+            r.input_delta,
+            r.curve_from,
+            r.curve_to,
+            r.curve_delta,
+            opt(r.input_driven, prec),
+            opt(r.structure_driven, prec),
+            r.amplification.map_or("n/a".to_string(), |a| format!("{a:.2}x"))
+        ));
+    }
+    out.push_str("Δ input: FRED baseline move; input-driven: refit with the earlier model kind and taus;\n");
+    out.push_str("structure: the rest, from re-selecting them; ampl: Δ curve / Δ input.\n");
+    out
+}
+
+/// Format `rv check` output: the worst gap, then the grid (every
+/// `DRIFT_GRID_STEP`-th tenor, plus the worst one).
+pub fn format_curve_drift(drift: &CurveDrift) -> String {
+    const DRIFT_GRID_STEP: usize = 10;
+    let prec = drift.unit.decimals();
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Curve {} vs FRED baseline {} | rating {} | {}\n",
+        drift.curve_date,
+        drift.snapshot_date,
+        drift.rating.display_name(),
+        drift.unit.label()
+    ));
+    out.push_str(&format!("{:>8} {:>10} {:>10} {:>10}\n", "tenor", "curve", "baseline", "deviation"));
+    out.push_str(&format!("{:-<8} {:-<10} {:-<10} {:-<10}\n", "", "", "", ""));
+    let worst = drift.worst();
+    let last = drift.rows.len().saturating_sub(1);
+    for (i, r) in drift.rows.iter().enumerate() {
+        let is_worst = worst.is_some_and(|w| std::ptr::eq(w, r));
+        if i % DRIFT_GRID_STEP != 0 && i != last && !is_worst {
+            continue;
+        }
+        out.push_str(&format!(
+            "{:>8.2} {:>10.prec$} {:>10.prec$} {:>+10.prec$}{}\n",
+            r.tenor,
+            r.curve,
+            r.baseline,
+            r.deviation,
+            if is_worst { "  <- max" } else { "" }
+        ));
+    }
+    if let Some(w) = worst {
+        out.push_str(&format!(
+            "{}: max |deviation| {:.prec$} at {:.2}y (threshold {:.prec$})\n",
+            if drift.drifted() { "DRIFT" } else { "OK" },
+            w.deviation.abs(),
+            w.tenor,
+            drift.max_deviation
+        ));
+    }
+    out
+}
+
+/// Format `rv precision-check` output: one row per function and tau.
+pub fn format_precision_check(checks: &[BranchCheck]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Basis branch continuity at x = t/tau = {:e} (tolerance {:.1e})\n",
+        crate::math::SMALL_X,
+        crate::math::BRANCH_TOL
+    ));
+    out.push_str(&format!("{:<4} {:>8} {:>12} {:>12} {:>12}  {}\n", "fn", "tau", "err below", "err above", "jump", "result"));
+    for c in checks {
+        out.push_str(&format!(
+            "{:<4} {:>8} {:>12.3e} {:>12.3e} {:>12.3e}  {}\n",
+            c.function,
+            c.tau,
+            c.max_err_below,
+            c.max_err_above,
+            c.jump,
+            if c.passed() { "PASS" } else { "FAIL" }
+        ));
+    }
+    out
+}
+
+/// Format the `--timing` section: wall-clock milliseconds per pipeline phase,
+/// with the fit split by model.
+pub fn format_timing(timings: &PhaseTimings) -> String {
+    let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    let mut out = String::new();
+    out.push_str("Timing:\n");
+    if let Some(fetch) = timings.fetch {
+        out.push_str(&format!("  {:<8} {:>10.1} ms\n", "fetch", ms(fetch)));
+    }
+    out.push_str(&format!("  {:<8} {:>10.1} ms\n", "sample", ms(timings.sample)));
+    out.push_str(&format!("  {:<8} {:>10.1} ms\n", "fit", ms(timings.fit)));
+    for (kind, elapsed) in &timings.models {
+        out.push_str(&format!("    {:<16} {:>8.1} ms\n", kind.display_name(), ms(*elapsed)));
+    }
+    out.push_str(&format!("  {:<8} {:>10.1} ms\n", "rank", ms(timings.rank)));
+    out.push_str(&format!("  {:<8} {:>10.1} ms\n", "total", ms(timings.total())));
+    out
+}
+
+/// Format `rv eval` output: one row per requested tenor, flagging extrapolation.
+pub fn format_curve_values(curve: &CurveFile, values: &[CurveValue]) -> String {
+    let unit = curve.unit.label();
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{} curve ({}, as-of {})\n",
+        curve.rating.display_name(),
+        curve.model.display_name,
+        curve.asof_date
+    ));
+    out.push_str(&format!("{:>8} {:>12}\n", "tenor", format!("y ({unit})")));
+    out.push_str(&format!("{:-<8} {:-<12}\n", "", ""));
+    for v in values {
+        let flag = if v.out_of_range { "  * out of range" } else { "" };
+        out.push_str(&format!("{:>8.3} {:>12.*}{flag}\n", v.tenor, curve.unit.decimals() + 2, v.y));
+    }
+    if values.iter().any(|v| v.out_of_range) {
+        match grid_range(curve) {
+            Some((lo, hi)) => out.push_str(&format!(
+                "* extrapolated outside the curve's fitted range [{lo:.2}, {hi:.2}]y\n"
+            )),
+            None => out.push_str("* curve has no fitted grid; all values are extrapolated\n"),
+        }
+    }
+    out
+}
+
+/// Format the `--pillars` table: fitted level per key tenor, labelled with the
+/// unit and as-of date, flagging pillars outside the observed tenor range.
+pub fn format_pillars(table: &PillarTable, locale: NumberLocale) -> String {
+    let (lo, hi) = table.fitted_range;
+    let mut out = format!(
+        "Pillars: {} curve ({}), as-of {}, y in {}\n",
+        table.rating.display_name(),
+        table.model,
+        table.asof_date,
+        table.unit.label()
+    );
+    out.push_str(&format!("{:>8} {:>12}\n", "tenor", format!("y ({})", table.unit.label())));
+    out.push_str(&format!("{:-<8} {:-<12}\n", "", ""));
+    for p in &table.pillars {
+        let flag = if p.out_of_range { "  * extrapolated" } else { "" };
+        out.push_str(&format!(
+            "{:>7}y {:>12}{flag}\n",
+            fmt_num(p.tenor, 2, locale),
+            fmt_num(p.y, table.unit.decimals(), locale)
+        ));
+    }
+    if table.pillars.iter().any(|p| p.out_of_range) {
+        out.push_str(&format!(
+            "* outside the observed tenor range [{}, {}]y\n",
+            fmt_num(lo, 2, locale),
+            fmt_num(hi, 2, locale)
+        ));
+    }
+    out
+}
+
+/// Format `--compact` output: a single `key=value` line with a fixed key order.
+pub fn format_compact_summary(ingest: &IngestedData, selection: &FitSelection, config: &FitConfig, run_hash: u64) -> String {
+    let best = &selection.best;
+    format!(
+        "date={} rating={} model={} n={} rmse={:.4} bic={:.3} run={run_hash:016x}",
+        ingest.input_spec.asof_date,
+        config.rating.display_name(),
+        best.model.name.as_str(),
+        best.quality.n,
+        best.quality.rmse,
+        best.quality.bic,
+    )
+}
+
+/// Format the `--all-ratings` batch table (failed cells show their error).
+pub fn format_batch_summary(cells: &[BatchCell]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{:<10} {:<6} {:<14} {:>5} {:>10} {:>12}\n",
+        "date", "rating", "model", "n", "rmse", "bic"
+    ));
+    out.push_str(&format!("{:-<10} {:-<6} {:-<14} {:-<5} {:-<10} {:-<12}\n", "", "", "", "", "", ""));
+    for cell in cells {
+        let rating = cell.rating.display_name();
+        match &cell.result {
+            Ok(run) => {
+                let best = &run.selection.best;
+                out.push_str(&format!(
+                    "{:<10} {:<6} {:<14} {:>5} {:>10.4} {:>12.3}\n",
+                    cell.date, rating, best.model.display_name, best.quality.n, best.quality.rmse, best.quality.bic
+                ));
+            }
+            Err(_) => out.push_str(&format!("{:<10} {:<6} {:<14}\n", cell.date, rating, "FAILED")),
+        }
+    }
+    out
+}
+
+/// Format the `--compare-models` table: one row per model, `*` marks the
+/// selected fit and skipped models show their guardrail reason.
+pub fn format_model_comparison(rows: &[ModelMetrics], unit: YUnit, locale: NumberLocale) -> String {
+    let d = unit.decimals() + 1;
+    // Width pads by chars, so "NSS (shared τ)" counts 14.
+    let w = rows.iter().map(|r| r.model.display_name().chars().count()).fold("model".len(), usize::max);
+    let mut out = String::from("Model comparison:\n");
+    out.push_str(&format!(
+        "  {:<w$} {:>5} {:>6} {:>12} {:>10} {:>10} {:>10} {:>10} {:>7}\n",
+        "model", "n", "params", "sse", "rmse", "mae", "bic", "aic", "r2"
+    ));
+    for row in rows {
+        let mark = if row.selected { "*" } else { " " };
+        let name = row.model.display_name();
+        match &row.skip_reason {
+            Some(reason) => out.push_str(&format!(
+                "{mark} {name:<w$} {:>5} {:>6} skipped: {reason}\n",
+                row.n, row.params
+            )),
+            None => {
+                let num = |v: Option<f64>, decimals: usize| v.map_or("-".to_string(), |v| fmt_num(v, decimals, locale));
+                out.push_str(&format!(
+                    "{mark} {name:<w$} {:>5} {:>6} {:>12} {:>10} {:>10} {:>10} {:>10} {:>7}\n",
+                    row.n,
+                    row.params,
+                    row.sse.map_or("-".to_string(), |v| fmt_sse(v, unit, locale)),
+                    num(row.rmse, d),
+                    num(row.mae, d),
+                    num(row.bic, 3),
+                    num(row.aic, 3),
+                    num(row.r2, 4),
+                ));
+            }
+        }
+    }
+    out
+}
+
 /// Compute fitted values and residuals for each bond.
 pub fn compute_residuals(points: &[BondPoint], fit: &FitResult) -> Result<Vec<BondResidual>, AppError> {
+    let se = fit.residual_se().filter(|&se| se > 0.0);
+    let scale = fit.model.y_transform;
     let mut out = Vec::with_capacity(points.len());
     for p in points {
-        let y_fit = predict(fit.model.name, p.tenor, &fit.model.betas, &fit.model.taus);
+        let y_fit = fit.model.eval(p.tenor);
         if !y_fit.is_finite() {
             return Err(AppError::new(4, "Non-finite model prediction during residual computation."));
         }
@@ -30,107 +489,470 @@ pub fn compute_residuals(points: &[BondPoint], fit: &FitResult) -> Result<Vec<Bo
             point: p.clone(),
             y_fit,
             residual,
+            richness_pct: 0.0,
+            // The SE comes from the weighted SSE on the fitting scale, so the
+            // residual is measured there too and scaled by sqrt(weight).
+            residual_se_units: se.map(|se| p.weight.sqrt() * (scale.apply(p.y_obs) - scale.apply(y_fit)) / se),
+            signal: 0.0,
         });
     }
+    assign_richness_percentiles(&mut out);
     Ok(out)
 }
 
-/// Rank the top cheap and rich bonds by residual.
-pub fn rank_cheap_rich(residuals: &[BondResidual], top_n: usize) -> Rankings {
+/// |z-score| at which the signal's z term saturates.
+pub const SIGNAL_Z_FULL: f64 = 3.0;
+
+/// Fill each bond's composite `signal` from its residual, z-score and DV01.
+///
+/// Every term is scaled to `[0, 1]` so none dominates through its units:
+///
+/// ```text
+/// m = |residual| / max |residual|          (over this fit's bonds)
+/// z = min(|residual_se_units| / 3, 1)
+/// d = dv01 / max dv01                      (over bonds that have one)
+/// signal = sign(residual) * (wR*m + wZ*z + wD*d) / (wR + wZ + wD)
+/// ```
+///
+/// A term the bond lacks (no DV01, or no residual SE) is dropped from both
+/// sums, so the remaining weights are renormalized; with no usable weight the
+/// signal is 0. Positive is cheap, negative rich, like the residual.
+pub fn assign_signals(residuals: &mut [BondResidual], weights: &SignalWeights) {
+    // Largest positive finite value, the scale of a term.
+    fn scale(values: impl Iterator<Item = f64>) -> Option<f64> {
+        values.filter(|v| v.is_finite() && *v > 0.0).reduce(f64::max)
+    }
+    let max_resid = scale(residuals.iter().map(|r| r.residual.abs()));
+    let max_dv01 = scale(residuals.iter().filter_map(|r| r.point.extras.dv01));
+
+    for r in residuals.iter_mut() {
+        let terms = [
+            (weights.residual, Some(max_resid.map_or(0.0, |m| r.residual.abs() / m))),
+            (weights.z, r.residual_se_units.map(|z| (z.abs() / SIGNAL_Z_FULL).min(1.0))),
+            (weights.dv01, r.point.extras.dv01.zip(max_dv01).map(|(d, m)| d / m)),
+        ];
+        let (sum, weight) = terms
+            .iter()
+            .filter_map(|&(w, term)| term.map(|t| (w * t, w)))
+            .fold((0.0, 0.0), |(s, ws), (t, w)| (s + t, ws + w));
+        r.signal = if weight > 0.0 && r.residual != 0.0 { r.residual.signum() * sum / weight } else { 0.0 };
+    }
+}
+
+/// Fill `richness_pct` from residual ranks (mid-rank for ties, so equal
+/// residuals share a percentile and the mapping is monotone in the residual).
+fn assign_richness_percentiles(residuals: &mut [BondResidual]) {
+    let n = residuals.len();
+    if n < 2 {
+        residuals.iter_mut().for_each(|r| r.richness_pct = 50.0);
+        return;
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| residuals[b].residual.total_cmp(&residuals[a].residual));
+
+    // `order` runs cheapest -> richest; each tie group gets its mean position.
+    let mut start = 0;
+    while start < n {
+        let r = residuals[order[start]].residual;
+        let mut end = start + 1;
+        while end < n && residuals[order[end]].residual == r {
+            end += 1;
+        }
+        let mid = (start + end - 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            residuals[i].richness_pct = 100.0 * mid / (n - 1) as f64;
+        }
+        start = end;
+    }
+}
+
+/// Sort key for `rank_by`; SE units fall back to the raw residual when the
+/// fit has no residual standard error.
+pub(crate) fn rank_key(r: &BondResidual, rank_by: RankBy) -> f64 {
+    match rank_by {
+        RankBy::Residual => r.residual,
+        RankBy::Se => r.residual_se_units.unwrap_or(r.residual),
+        RankBy::Signal => r.signal,
+    }
+}
+
+/// Rank the top `top_cheap` cheap and `top_rich` rich bonds by residual (or
+/// SE units, per `rank_by`).
+///
+/// The lists only meet when they outnumber the sample; a bond already listed
+/// as cheap is then left off the rich list rather than shown twice.
+pub fn rank_cheap_rich(residuals: &[BondResidual], top_cheap: usize, top_rich: usize, rank_by: RankBy) -> Rankings {
+    let key = |r: &BondResidual| rank_key(r, rank_by);
     let mut sorted = residuals.to_vec();
-    sorted.sort_by(|a, b| b.residual.partial_cmp(&a.residual).unwrap_or(std::cmp::Ordering::Equal));
+    sorted.sort_by(|a, b| key(b).partial_cmp(&key(a)).unwrap_or(std::cmp::Ordering::Equal));
 
-    let cheap = sorted.iter().take(top_n).cloned().collect();
+    let cheap: Vec<BondResidual> = sorted.iter().take(top_cheap).cloned().collect();
 
     let mut sorted_rich = residuals.to_vec();
-    sorted_rich.sort_by(|a, b| a.residual.partial_cmp(&b.residual).unwrap_or(std::cmp::Ordering::Equal));
-    let rich = sorted_rich.iter().take(top_n).cloned().collect();
+    sorted_rich.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(std::cmp::Ordering::Equal));
+    let rich = sorted_rich
+        .into_iter()
+        .filter(|r| !cheap.iter().any(|c| c.point.id == r.point.id))
+        .take(top_rich)
+        .collect();
 
     Rankings { cheap, rich }
 }
 
-/// Format the full run summary (dataset stats + fit diagnostics + chosen model).
-pub fn format_run_summary(ingest: &IngestedData, selection: &FitSelection, config: &FitConfig) -> String {
+/// How consistently a bond lands in the cheap/rich lists across a seed sweep.
+#[derive(Debug, Clone)]
+pub struct BondStability {
+    pub id: String,
+    /// The bond's tenor, held fixed across the sweep.
+    pub tenor: f64,
+    /// Seeds in which the bond was among the top-N cheap.
+    pub cheap_hits: usize,
+    /// Seeds in which the bond was among the top-N rich.
+    pub rich_hits: usize,
+    /// Mean residual across all seeds.
+    pub mean_residual: f64,
+    /// Stability-adjusted signal: `mean_residual * |cheap_frac - rich_frac|`.
+    pub score: f64,
+}
+
+/// Aggregate top-N membership per bond ID over a seed sweep, ranked by
+/// `|score|` (strongest, most repeatable signal first).
+///
+/// Bonds are matched by sample ID (`BBB-007`). The sweep holds tenors fixed
+/// and redraws only the noise, so an ID is the same bond in every run.
+pub fn compute_bond_stability(runs: &[RunOutput]) -> Vec<BondStability> {
+    use std::collections::BTreeMap;
+
+    let seeds = runs.len().max(1) as f64;
+    let mut by_id: BTreeMap<&str, (f64, usize, usize, f64)> = BTreeMap::new();
+    for run in runs {
+        for r in &run.residuals {
+            let entry = by_id.entry(&r.point.id).or_default();
+            entry.0 = r.point.tenor;
+            entry.3 += r.residual;
+        }
+        for r in &run.rankings.cheap {
+            by_id.entry(&r.point.id).or_default().1 += 1;
+        }
+        for r in &run.rankings.rich {
+            by_id.entry(&r.point.id).or_default().2 += 1;
+        }
+    }
+
+    let mut out: Vec<BondStability> = by_id
+        .into_iter()
+        .map(|(id, (tenor, cheap_hits, rich_hits, sum))| {
+            let mean_residual = sum / seeds;
+            let consistency = (cheap_hits as f64 - rich_hits as f64).abs() / seeds;
+            BondStability {
+                id: id.to_string(),
+                tenor,
+                cheap_hits,
+                rich_hits,
+                mean_residual,
+                score: mean_residual * consistency,
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| b.score.abs().partial_cmp(&a.score.abs()).unwrap_or(std::cmp::Ordering::Equal));
+    out
+}
+
+/// Format the `--seed-sweep` stability table (top-N rows).
+pub fn format_bond_stability(
+    stability: &[BondStability],
+    seeds: usize,
+    top_n: usize,
+    unit: YUnit,
+    locale: NumberLocale,
+) -> String {
     let mut out = String::new();
+    out.push_str(&format!(
+        "Seed sweep: {seeds} seeds (tenors held, noise redrawn), top-{top_n} cheap/rich membership (ranked by stability-adjusted signal)\n"
+    ));
+    out.push_str(&format!(
+        "{:<24} {:>8} {:>7} {:>7} {:>12} {:>12}\n",
+        "id",
+        "tenor",
+        "cheap%",
+        "rich%",
+        format!("mean ({})", unit.label()),
+        "score"
+    ));
+    out.push_str(&format!("{:-<24} {:-<8} {:-<7} {:-<7} {:-<12} {:-<12}\n", "", "", "", "", "", ""));
+    let pct = |hits: usize| fmt_num(100.0 * hits as f64 / seeds.max(1) as f64, 1, locale);
+    for b in stability.iter().take(top_n) {
+        out.push_str(&format!(
+            "{:<24} {:>8} {:>7} {:>7} {} {}\n",
+            truncate(&b.id, 24),
+            fmt_num(b.tenor, 3, locale),
+            pct(b.cheap_hits),
+            pct(b.rich_hits),
+            fmt_y(b.mean_residual, unit, locale),
+            fmt_y(b.score, unit, locale)
+        ));
+    }
+    out
+}
+
+/// A slope metric evaluated on the selected curve.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlopeValue {
+    pub label: String,
+    /// `y(t2) - y(t1)`.
+    pub value: f64,
+    /// True when either tenor lies outside the fitted tenor range.
+    pub extrapolated: bool,
+}
+
+/// Evaluate `y(t2) - y(t1)` for each pair on the fitted curve.
+///
+/// Tenors outside `[tenor_min, tenor_max]` are evaluated on the model (as
+/// `rv eval` does) and flagged as extrapolated.
+pub fn compute_slopes(fit: &FitResult, pairs: &[TenorPair], tenor_min: f64, tenor_max: f64) -> Vec<SlopeValue> {
+    let m = &fit.model;
+    let y = |t: f64| m.eval(t);
+    let outside = |t: f64| t < tenor_min || t > tenor_max;
+    pairs
+        .iter()
+        .map(|p| SlopeValue {
+            label: p.label.clone(),
+            value: y(p.t2) - y(p.t1),
+            extrapolated: outside(p.t1) || outside(p.t2),
+        })
+        .collect()
+}
+
+/// Weighted residual fit within one tenor bin.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResidualBin {
+    pub lo: f64,
+    pub hi: f64,
+    /// Whether `hi` is inside the bin (only the last bin; the rest are `[lo, hi)`).
+    pub closed: bool,
+    pub n: usize,
+    /// `sqrt(sum(w r^2) / sum(w))` with the fit's observation weights.
+    pub wrmse: f64,
+}
+
+/// Bin residuals by tenor (`[lo, hi)`, the last bin closed) and compute each
+/// non-empty bin's weighted RMSE. Points outside every bin are ignored.
+pub fn residual_bins(residuals: &[BondResidual], edges: &BinEdges) -> Vec<ResidualBin> {
+    let edges = &edges.0;
+    let last = edges.len().saturating_sub(2);
+    edges
+        .windows(2)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let (lo, hi, closed) = (w[0], w[1], i == last);
+            let inside = |t: f64| t >= lo && (t < hi || (closed && t <= hi));
+            let (mut n, mut sw, mut swr2) = (0usize, 0.0, 0.0);
+            for r in residuals.iter().filter(|r| inside(r.point.tenor)) {
+                n += 1;
+                sw += r.point.weight;
+                swr2 += r.point.weight * r.residual * r.residual;
+            }
+            (n > 0 && sw > 0.0).then(|| ResidualBin { lo, hi, closed, n, wrmse: (swr2 / sw).sqrt() })
+        })
+        .collect()
+}
+
+/// The bin with the largest weighted RMSE (the first one on ties).
+pub fn worst_fit_bin(residuals: &[BondResidual], edges: &BinEdges) -> Option<ResidualBin> {
+    residual_bins(residuals, edges)
+        .into_iter()
+        .fold(None, |worst: Option<ResidualBin>, bin| match worst {
+            Some(w) if w.wrmse >= bin.wrmse => Some(w),
+            _ => Some(bin),
+        })
+}
+
+/// Format the full run summary (dataset stats + fit diagnostics + chosen model)
+/// from its structured form (`build_run_summary`).
+pub fn format_run_summary(summary: &RunSummary, loc: NumberLocale) -> String {
+    let mut out = String::new();
+    let unit = summary.y_unit;
 
     out.push_str("=== rv - RV Curve Fit (FRED-based) ===\n");
-    out.push_str(&format!("Rating: {}\n", config.rating.display_name()));
-    out.push_str(&format!("As-of: {}\n", ingest.input_spec.asof_date));
+    out.push_str(&format!("Rating: {}\n", summary.rating.display_name()));
+    match summary.requested_asof {
+        Some(requested) => out.push_str(&format!(
+            "As-of: {} (FRED observation date; requested {requested})\n",
+            summary.asof_date
+        )),
+        None => out.push_str(&format!(
+            "As-of: {} (FRED observation date)\n",
+            summary.asof_date
+        )),
+    }
+    out.push_str(&format!("Y: {:?} ({})\n", summary.y_kind, unit.label()));
     out.push_str(&format!(
-        "Y: {:?} ({})\n",
-        ingest.input_spec.y_kind,
-        ingest.input_spec.y_unit_label(),
+        "Sample: n={} | tenor=[{}, {}]y\n",
+        fmt_num(summary.sample.count as f64, 0, loc),
+        fmt_num(summary.sample.tenor_min, 2, loc),
+        fmt_num(summary.sample.tenor_max, 2, loc),
     ));
+    if let Some(window) = &summary.sample.fit_window {
+        out.push_str(&format!(
+            "Fit window: tenor=[{}, {}]y | fitted={} of {} (all ranked)\n",
+            fmt_num(window.tenor_min, 2, loc),
+            fmt_num(window.tenor_max, 2, loc),
+            window.fitted,
+            summary.accounting.used + summary.accounting.outside_fit_window
+        ));
+    }
+
+    let acc = &summary.accounting;
+    let window = match summary.sample.fit_window {
+        Some(_) => format!(" | outside fit window={}", acc.outside_fit_window),
+        None => String::new(),
+    };
     out.push_str(&format!(
-        "Sample: n={} | tenor=[{:.2}, {:.2}]y\n",
-        config.sample_count,
-        config.tenor_min,
-        config.tenor_max,
+        "Accounting: generated={} | dropped by bounds={} | dropped by trim={} | dropped by weight={}{window} | used={}\n",
+        acc.generated,
+        acc.dropped_by_bound,
+        acc.dropped_by_trim,
+        acc.dropped_by_weight,
+        acc.used
     ));
 
+    let pts = &summary.points;
     out.push_str(&format!(
-        "Points: n={} | tenor=[{:.3}, {:.3}] | y=[{:.2}, {:.2}]bp\n",
-        ingest.stats.n_points,
-        ingest.stats.tenor_min,
-        ingest.stats.tenor_max,
-        ingest.stats.y_min,
-        ingest.stats.y_max
+        "Points: n={} | tenor=[{}, {}] | y=[{}, {}]{}\n",
+        fmt_num(pts.n as f64, 0, loc),
+        fmt_num(pts.tenor_min, 3, loc),
+        fmt_num(pts.tenor_max, 3, loc),
+        fmt_num(pts.y_min, unit.decimals(), loc),
+        fmt_num(pts.y_max, unit.decimals(), loc),
+        unit.label()
     ));
 
     out.push_str("\nModel diagnostics:\n");
-    for fit in &selection.fits {
-        let chosen = if fit.model.name == selection.best.model.name { "*" } else { " " };
+    for m in &summary.models {
+        let chosen = if m.selected { "*" } else { " " };
         out.push_str(&format!(
-            "{chosen} {:<12} SSE={:.3} RMSE={:.3}bp BIC={:.3}\n",
-            fit.model.display_name,
-            fit.quality.sse,
-            fit.quality.rmse,
-            fit.quality.bic
+            "{chosen} {:<12} SSE={} RMSE={}{} BIC={} w(BIC)={} candidates={}\n",
+            m.display_name,
+            fmt_sse(m.sse, unit, loc),
+            fmt_num(m.rmse, unit.decimals() + 1, loc),
+            unit.label(),
+            fmt_num(m.bic, 3, loc),
+            fmt_num(m.bic_weight, 3, loc),
+            m.candidates
         ));
     }
-    for (kind, reason) in &selection.skipped {
-        out.push_str(&format!("  (skipped {}) {reason}\n", kind.display_name()));
+    for s in &summary.skipped {
+        out.push_str(&format!("  (skipped {}) {}\n", s.kind.display_name(), s.reason));
     }
 
+    let chosen = &summary.chosen;
     out.push_str("\nChosen model:\n");
-    out.push_str(&format!(
-        "- {} (kind={:?})\n",
-        selection.best.model.display_name, selection.best.model.name
-    ));
-    out.push_str(&format!("- betas: {}\n", fmt_vec(&selection.best.model.betas)));
-    out.push_str(&format!("- taus : {}\n", fmt_vec(&selection.best.model.taus)));
+    out.push_str(&format!("- {} (kind={:?})\n", chosen.display_name, chosen.kind));
+    out.push_str(&format!("- betas: {}\n", fmt_vec(&chosen.betas, loc)));
+    if chosen.y_transform == YTransform::Log {
+        out.push_str("- fitted on ln(y): betas, RMSE, and BIC are on the log scale\n");
+    }
+    match chosen.basis {
+        Basis::Standard => out.push_str(&format!("- taus : {}\n", fmt_vec(&chosen.decay, loc))),
+        Basis::DieboldLi => out.push_str(&format!("- lambdas: {} (Diebold-Li, 1/years)\n", fmt_vec(&chosen.decay, loc))),
+    }
+    match chosen.peak {
+        Some((tenor, level)) => out.push_str(&format!(
+            "- peak : {}y at {}{}\n",
+            fmt_num(tenor, 2, loc),
+            fmt_num(level, unit.decimals(), loc),
+            unit.label()
+        )),
+        None => out.push_str("- peak : no interior peak\n"),
+    }
+
+    if let Some(bin) = &chosen.worst_fit_bin {
+        out.push_str(&format!(
+            "- worst-fit region: [{}, {}{}y wRMSE={}{} (n={})\n",
+            fmt_num(bin.lo, 2, loc),
+            fmt_num(bin.hi, 2, loc),
+            if bin.closed { "]" } else { ")" },
+            fmt_num(bin.wrmse, unit.decimals() + 1, loc),
+            unit.label(),
+            bin.n
+        ));
+    }
+
+    if !summary.slopes.is_empty() {
+        out.push_str(&format!("\nSlopes (y(t2) - y(t1), {}):\n", unit.label()));
+        for s in &summary.slopes {
+            let flag = if s.extrapolated { " (extrapolated)" } else { "" };
+            out.push_str(&format!("  {} = {}{flag}\n", s.label, fmt_num(s.value, unit.decimals(), loc)));
+        }
+    }
     out.push('\n');
 
     out
 }
 
 /// Format the cheap/rich tables.
-pub fn format_rankings(rankings: &Rankings, input_spec: &InputSpec) -> String {
+///
+/// With `RankBy::Se` the residual column is shown in residual-SE units;
+/// `round_tenors` snaps the displayed tenor (`BondPoint::reported_tenor`).
+/// One-line note of the curve `rv rank` ranked against, and whether it won a
+/// model comparison or was the only model fitted (`--model` other than
+/// `auto`/`all`).
+pub fn format_rank_model(selection: &FitSelection, spec: ModelSpec) -> String {
+    let model = &selection.best.model.display_name;
+    match spec {
+        ModelSpec::Auto | ModelSpec::All => {
+            format!("model: {model} (selected by BIC from {} fitted)", selection.fits.len())
+        }
+        _ => {
+            let name = clap::ValueEnum::to_possible_value(&spec).map(|v| v.get_name().to_string()).unwrap_or_default();
+            format!("model: {model} (--model {name}; no other models fitted)")
+        }
+    }
+}
+
+pub fn format_rankings(
+    rankings: &Rankings,
+    input_spec: &InputSpec,
+    rank_by: RankBy,
+    round_tenors: Option<f64>,
+    locale: NumberLocale,
+) -> String {
     let mut out = String::new();
 
     out.push_str("Top cheap (positive residual):\n");
-    out.push_str(&format_table(&rankings.cheap, input_spec));
+    out.push_str(&format_table(&rankings.cheap, input_spec, rank_by, round_tenors, locale));
     out.push('\n');
 
     out.push_str("Top rich (negative residual):\n");
-    out.push_str(&format_table(&rankings.rich, input_spec));
+    out.push_str(&format_table(&rankings.rich, input_spec, rank_by, round_tenors, locale));
 
     out
 }
 
-fn format_table(rows: &[BondResidual], input_spec: &InputSpec) -> String {
+fn format_table(
+    rows: &[BondResidual],
+    input_spec: &InputSpec,
+    rank_by: RankBy,
+    round_tenors: Option<f64>,
+    locale: NumberLocale,
+) -> String {
     let mut out = String::new();
+    let resid_label = match rank_by {
+        RankBy::Residual => "residual",
+        RankBy::Se => "resid_se",
+        RankBy::Signal => "signal",
+    };
     out.push_str(format!(
-        "{:<24} {:>8} {:>12} {:>12} {:>12} {:<10}\n",
-        "id", "tenor", "y_obs", "y_fit", "residual", "rating"
+        "{:<24} {:>8} {:>12} {:>12} {:>12} {:>6} {:<10}\n",
+        "id", "tenor", "y_obs", "y_fit", resid_label, "rich%", "rating"
     )
     .trim_end());
     out.push('\n');
 
     out.push_str(
         format!(
-        "{:-<24} {:-<8} {:-<12} {:-<12} {:-<12} {:-<10}\n",
-        "", "", "", "", "", ""
+        "{:-<24} {:-<8} {:-<12} {:-<12} {:-<12} {:-<6} {:-<10}\n",
+        "", "", "", "", "", "", ""
     )
         .trim_end(),
     );
@@ -140,12 +962,17 @@ fn format_table(rows: &[BondResidual], input_spec: &InputSpec) -> String {
         let p = &r.point;
         out.push_str(
             format!(
-            "{:<24} {:>8.3} {:>12} {:>12} {:>12} {:<10}\n",
+            "{:<24} {:>8} {:>12} {:>12} {:>12} {:>6} {:<10}\n",
             truncate(&p.id, 24),
-            p.tenor,
-            fmt_y(p.y_obs, input_spec.y_kind),
-            fmt_y(r.y_fit, input_spec.y_kind),
-            fmt_y(r.residual, input_spec.y_kind),
+            fmt_num(p.reported_tenor(round_tenors), 3, locale),
+            fmt_y(p.y_obs, input_spec.y_unit, locale),
+            fmt_y(r.y_fit, input_spec.y_unit, locale),
+            match rank_by {
+                RankBy::Residual => fmt_y(r.residual, input_spec.y_unit, locale),
+                RankBy::Se => r.residual_se_units.map(|u| fmt_num(u, 2, locale)).unwrap_or_else(|| "-".to_string()),
+                RankBy::Signal => fmt_num(r.signal, 3, locale),
+            },
+            fmt_num(r.richness_pct, 1, locale),
             truncate(p.meta.rating.as_deref().unwrap_or(""), 10),
         )
             .trim_end(),
@@ -156,15 +983,62 @@ fn format_table(rows: &[BondResidual], input_spec: &InputSpec) -> String {
     out
 }
 
-fn fmt_y(v: f64, kind: YKind) -> String {
-    match kind {
-        YKind::Oas => format!("{v:>12.2}"),
+/// SSE is in squared y-units: tiny in decimal, so switch to scientific notation.
+fn fmt_sse(v: f64, unit: YUnit, locale: NumberLocale) -> String {
+    match unit {
+        YUnit::Bp => fmt_num(v, 3, locale),
+        YUnit::Decimal => localize(&format!("{v:.3e}"), locale),
     }
 }
 
-fn fmt_vec(v: &[f64]) -> String {
-    let parts: Vec<String> = v.iter().map(|x| format!("{x:.6}")).collect();
-    format!("[{}]", parts.join(", "))
+fn fmt_y(v: f64, unit: YUnit, locale: NumberLocale) -> String {
+    format!("{:>12}", fmt_num(v, unit.decimals(), locale))
+}
+
+fn fmt_vec(v: &[f64], locale: NumberLocale) -> String {
+    let parts: Vec<String> = v.iter().map(|x| fmt_num(*x, 6, locale)).collect();
+    // `eu` uses `,` as the decimal mark, so separate elements with `;`.
+    let sep = if locale == NumberLocale::Eu { "; " } else { ", " };
+    format!("[{}]", parts.join(sep))
+}
+
+/// Format `v` with `decimals` places using the locale's grouping/decimal marks.
+///
+/// Only used for terminal output; exports always use plain formatting.
+fn fmt_num(v: f64, decimals: usize, locale: NumberLocale) -> String {
+    let plain = format!("{v:.decimals$}");
+    if !v.is_finite() {
+        return plain;
+    }
+    let (group, mark) = match locale {
+        NumberLocale::Plain => return plain,
+        NumberLocale::Us => (',', '.'),
+        NumberLocale::Eu => ('.', ','),
+    };
+
+    let (sign, digits) = plain.strip_prefix('-').map_or(("", plain.as_str()), |d| ("-", d));
+    let (int, frac) = digits.split_once('.').map_or((digits, None), |(i, f)| (i, Some(f)));
+
+    let mut grouped = String::with_capacity(int.len() + int.len() / 3);
+    for (i, ch) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(group);
+        }
+        grouped.push(ch);
+    }
+
+    match frac {
+        Some(frac) => format!("{sign}{grouped}{mark}{frac}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+/// Swap the decimal mark of an already formatted number (no grouping).
+fn localize(plain: &str, locale: NumberLocale) -> String {
+    match locale {
+        NumberLocale::Eu => plain.replace('.', ","),
+        NumberLocale::Plain | NumberLocale::Us => plain.to_string(),
+    }
 }
 
 fn truncate(s: &str, max: usize) -> String {
@@ -186,7 +1060,8 @@ fn truncate(s: &str, max: usize) -> String {
 mod tests {
     use super::*;
     use chrono::NaiveDate;
-    use crate::domain::{BondExtras, BondMeta, BondPoint, ModelKind};
+    use crate::domain::{BondExtras, BondMeta, BondPoint, CurveModel, FitQuality, ModelKind};
+    use crate::models::predict;
 
     #[test]
     fn compute_residuals_basic() {
@@ -220,6 +1095,8 @@ mod tests {
                 display_name: "NS".to_string(),
                 betas: vec![100.0, 0.0, 0.0],
                 taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
             },
             quality: crate::domain::FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 2 },
         };
@@ -231,57 +1108,318 @@ mod tests {
     }
 
     #[test]
-    fn rank_cheap_rich_basic() {
+    fn residual_se_units_need_degrees_of_freedom() {
+        let points: Vec<BondPoint> = BondResidual::fixtures(&[0.0, 1.0]).into_iter().map(|r| r.point).collect();
+        let fit = FitResult {
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, 0.0, 0.0],
+                taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: 1.0, rmse: 0.0, bic: 0.0, n: 2 },
+        };
+        // NS has k = 4 > n: no degrees of freedom, so no SE units.
+        assert_eq!(fit.residual_se(), None);
+        let residuals = compute_residuals(&points, &fit).unwrap();
+        assert!(residuals.iter().all(|r| r.residual_se_units.is_none()));
+    }
+
+    #[test]
+    fn residual_se_units_use_fit_degrees_of_freedom() {
         let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
-        let residuals = vec![
-            BondResidual {
-                point: BondPoint {
-                    id: "B1".to_string(),
-                    asof_date: asof,
-                    maturity_date: asof,
-                    tenor: 1.0,
-                    y_obs: 100.0,
-                    weight: 1.0,
-                    meta: BondMeta::default(),
-                    extras: BondExtras::default(),
-                },
-                y_fit: 100.0,
-                residual: 0.0,
+        let points: Vec<BondPoint> = [100.0, 103.0, 97.0, 101.0, 99.0, 100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &y_obs)| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 1.0 + i as f64,
+                y_obs,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            })
+            .collect();
+        // SSE = 9 + 9 + 1 + 1 = 20 over n - k = 6 - 4 = 2 dof -> SE = sqrt(10).
+        let fit = FitResult {
+            model: crate::domain::CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, 0.0, 0.0],
+                taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
             },
-            BondResidual {
-                point: BondPoint {
-                    id: "B2".to_string(),
-                    asof_date: asof,
-                    maturity_date: asof,
-                    tenor: 2.0,
-                    y_obs: 105.0,
-                    weight: 1.0,
-                    meta: BondMeta::default(),
-                    extras: BondExtras::default(),
-                },
-                y_fit: 100.0,
-                residual: 5.0,
+            quality: crate::domain::FitQuality { sse: 20.0, rmse: 0.0, bic: 0.0, n: 6 },
+        };
+        let se = 10.0_f64.sqrt();
+        assert!((fit.residual_se().unwrap() - se).abs() < 1e-12);
+
+        let residuals = compute_residuals(&points, &fit).unwrap();
+        assert!((residuals[1].residual_se_units.unwrap() - 3.0 / se).abs() < 1e-12);
+        let rankings = rank_cheap_rich(&residuals, 1, 1, RankBy::Se);
+        assert_eq!((rankings.cheap[0].point.id.as_str(), rankings.rich[0].point.id.as_str()), ("B1", "B2"));
+        let table = format_rankings(
+            &rankings,
+            &InputSpec { asof_date: asof, y_kind: crate::domain::YKind::Oas, y_unit: YUnit::Bp },
+            RankBy::Se,
+            None,
+            NumberLocale::Plain,
+        );
+        assert!(table.contains("resid_se") && table.contains(&format!("{:.2}", 3.0 / se)), "{table}");
+    }
+
+    #[test]
+    fn residual_se_units_scale_by_the_bond_weight() {
+        // Weighted SSE = 4*1^2 + 0.25*4^2 + 1*2^2 + 1*0 + 1*0 + 1*2^2 = 16 over 2 dof -> SE = sqrt(8).
+        let mut points: Vec<BondPoint> = [101.0, 104.0, 98.0, 100.0, 100.0, 102.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &y)| BondResidual::fixture(&format!("B{i}"), 1.0 + i as f64, y - 100.0).point)
+            .collect();
+        points[0].weight = 4.0;
+        points[1].weight = 0.25;
+        let fit = FitResult {
+            model: crate::domain::CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, 0.0, 0.0],
+                taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
             },
-            BondResidual {
-                point: BondPoint {
-                    id: "B3".to_string(),
-                    asof_date: asof,
-                    maturity_date: asof,
-                    tenor: 3.0,
-                    y_obs: 95.0,
-                    weight: 1.0,
-                    meta: BondMeta::default(),
-                    extras: BondExtras::default(),
-                },
-                y_fit: 100.0,
-                residual: -5.0,
+            quality: crate::domain::FitQuality { sse: 16.0, rmse: 0.0, bic: 0.0, n: 6 },
+        };
+        let se = 8.0_f64.sqrt();
+        let residuals = compute_residuals(&points, &fit).unwrap();
+        let z: Vec<f64> = residuals.iter().map(|r| r.residual_se_units.unwrap()).collect();
+        assert!((z[0] - 2.0 * 1.0 / se).abs() < 1e-12, "{z:?}");
+        assert!((z[1] - 0.5 * 4.0 / se).abs() < 1e-12, "{z:?}");
+        assert!((z[2] + 2.0 / se).abs() < 1e-12, "{z:?}");
+        // Squared z-scores add back up to the weighted SSE over the SE^2.
+        assert!((z.iter().map(|z| z * z).sum::<f64>() - 16.0 / 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn richness_percentile_is_monotone_and_shares_ties() {
+        let asof = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let points: Vec<BondPoint> = [100.0, 103.0, 103.0, 98.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &y_obs)| BondPoint {
+                id: format!("B{i}"),
+                asof_date: asof,
+                maturity_date: asof,
+                tenor: 1.0 + i as f64,
+                y_obs,
+                weight: 1.0,
+                meta: BondMeta::default(),
+                extras: BondExtras::default(),
+            })
+            .collect();
+        let fit = FitResult {
+            model: crate::domain::CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, 0.0, 0.0],
+                taus: vec![1.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
             },
+            quality: crate::domain::FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 4 },
+        };
+
+        let pct: Vec<f64> = compute_residuals(&points, &fit)
+            .unwrap()
+            .iter()
+            .map(|r| r.richness_pct)
+            .collect();
+        // Residuals [0, 3, 3, -2]: the tied cheap pair shares the mid-rank.
+        let expected = [200.0 / 3.0, 50.0 / 3.0, 50.0 / 3.0, 100.0];
+        for (got, want) in pct.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-9, "{pct:?}");
+        }
+    }
+
+    #[test]
+    fn residual_shift_flags_out_of_range_tenors() {
+        let residual = |id: &str, tenor: f64, y_obs: f64| BondResidual::fixture(id, tenor, y_obs - 100.0);
+        let residuals = vec![residual("B1", 2.0, 105.0), residual("B2", 20.0, 95.0)];
+
+        // Previous curve: flat 102bp over [1, 10]y.
+        let previous = CurveFile::fixture(&[102.0, 0.0, 0.0], &[1.0], &[1.0, 10.0], &[102.0, 102.0]);
+
+        let shifts = compute_residual_shifts(&residuals, &previous);
+        assert!((shifts[0].prev_residual.unwrap() - 3.0).abs() < 1e-9);
+        assert!((shifts[0].shift.unwrap() - 2.0).abs() < 1e-9);
+        assert!(shifts[1].prev_residual.is_none());
+        assert!(shifts[1].shift.is_none());
+
+        let txt = format_residual_shifts(&shifts, &previous, true);
+        assert!(txt.contains("1 bond(s) outside"));
+    }
+
+    #[test]
+    fn rank_cheap_rich_basic() {
+        let residuals = vec![
+            BondResidual::fixture("B1", 1.0, 0.0),
+            BondResidual::fixture("B2", 2.0, 5.0),
+            BondResidual::fixture("B3", 3.0, -5.0),
         ];
 
-        let rankings = rank_cheap_rich(&residuals, 1);
+        let rankings = rank_cheap_rich(&residuals, 1, 1, RankBy::Residual);
         assert_eq!(rankings.cheap.len(), 1);
         assert_eq!(rankings.cheap[0].point.id, "B2");
         assert_eq!(rankings.rich.len(), 1);
         assert_eq!(rankings.rich[0].point.id, "B3");
     }
+
+    #[test]
+    fn rank_cheap_rich_takes_asymmetric_counts_without_sharing_bonds() {
+        let residuals = BondResidual::fixtures(&[0.0, 5.0, -5.0]);
+        // Lists longer than the sample never share a bond.
+        fn ids(rows: &[BondResidual]) -> Vec<&str> {
+            rows.iter().map(|r| r.point.id.as_str()).collect()
+        }
+        let long_biased = rank_cheap_rich(&residuals, 2, 0, RankBy::Residual);
+        assert_eq!((ids(&long_biased.cheap), ids(&long_biased.rich)), (vec!["B1", "B0"], vec![]));
+        let overlapping = rank_cheap_rich(&residuals, 2, 3, RankBy::Residual);
+        assert_eq!((ids(&overlapping.cheap), ids(&overlapping.rich)), (vec!["B1", "B0"], vec!["B2"]));
+        assert_eq!(overlapping.highlight("B0"), Some("cheap"));
+        assert_eq!(overlapping.highlight("B2"), Some("rich"));
+    }
+
+    #[test]
+    fn signal_blends_normalized_terms_and_drops_missing_dv01() {
+        let residual = |id: &str, residual: f64, z: Option<f64>, dv01: Option<f64>| {
+            let mut r = BondResidual::fixture(id, 5.0, residual);
+            r.residual_se_units = z;
+            r.point.extras.dv01 = dv01;
+            r
+        };
+        let mut residuals = vec![
+            residual("BIG", 10.0, Some(4.5), Some(0.02)),
+            residual("LONG", 5.0, Some(1.5), Some(0.08)),
+            residual("RICH", -5.0, Some(-1.5), None),
+            residual("FLAT", 0.0, Some(0.0), Some(0.05)),
+        ];
+        assign_signals(&mut residuals, &SignalWeights::default());
+        let signal: Vec<f64> = residuals.iter().map(|r| r.signal).collect();
+        // BIG: (1 + 1 + 0.25) / 3; LONG: (0.5 + 0.5 + 1) / 3; RICH drops DV01: -(0.5 + 0.5) / 2.
+        let expected = [0.75, 2.0 / 3.0, -0.5, 0.0];
+        for (got, want) in signal.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-12, "{signal:?}");
+        }
+
+        // DV01-heavy weights reorder the cheap side.
+        assign_signals(&mut residuals, &"0,0,1".parse().unwrap());
+        let rankings = rank_cheap_rich(&residuals, 1, 1, RankBy::Signal);
+        assert_eq!(rankings.cheap[0].point.id, "LONG");
+        // With only DV01 weighted, a bond without DV01 has no usable term.
+        assert_eq!(residuals[2].signal, 0.0);
+
+        assert!("1,1".parse::<SignalWeights>().unwrap_err().contains("three weights"));
+        assert!("0,0,0".parse::<SignalWeights>().is_err());
+    }
+
+    #[test]
+    fn slopes_keep_requested_labels_and_flag_extrapolation() {
+        let fit = FitResult {
+            model: CurveModel {
+                name: ModelKind::Ns,
+                display_name: "NS".to_string(),
+                betas: vec![100.0, -20.0, 30.0],
+                taus: vec![2.0],
+                y_transform: crate::domain::YTransform::None,
+                splice: None,
+            },
+            quality: FitQuality { sse: 0.0, rmse: 0.0, bic: 0.0, n: 0 },
+        };
+        let pairs: Vec<TenorPair> = ["2-10", " 5-30", "10-2"].iter().map(|s| s.parse().unwrap()).collect();
+        let slopes = compute_slopes(&fit, &pairs, 0.5, 20.0);
+
+        let labels: Vec<&str> = slopes.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["2-10", "5-30", "10-2"]);
+        let y = |t: f64| predict(ModelKind::Ns, t, &fit.model.betas, &fit.model.taus);
+        assert_eq!(slopes[0].value, y(10.0) - y(2.0));
+        assert_eq!(slopes[2].value, -slopes[0].value);
+        assert_eq!(slopes.iter().map(|s| s.extrapolated).collect::<Vec<_>>(), vec![false, true, false]);
+
+        for bad in ["2", "2-", "a-10", "5-5", "-1-3"] {
+            assert!(bad.parse::<TenorPair>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn worst_fit_bin_uses_weights_and_skips_empty_bins() {
+        let residual = |tenor: f64, residual: f64, weight: f64| {
+            let mut r = BondResidual::fixture(&format!("B{tenor}"), tenor, residual);
+            r.point.weight = weight;
+            r
+        };
+        // [0,2): one big residual with tiny weight; [2,5): moderate, full weight;
+        // [5,10): empty; [10,30]: includes the closing edge.
+        let residuals = vec![
+            residual(1.0, 10.0, 0.01),
+            residual(1.5, 0.0, 1.0),
+            residual(3.0, 3.0, 1.0),
+            residual(4.0, -3.0, 1.0),
+            residual(30.0, 1.0, 1.0),
+        ];
+        let edges: BinEdges = "0,2,5,10,30".parse().unwrap();
+
+        let bins = residual_bins(&residuals, &edges);
+        assert_eq!(bins.iter().map(|b| (b.lo, b.n)).collect::<Vec<_>>(), vec![(0.0, 2), (2.0, 2), (10.0, 1)]);
+        let worst = worst_fit_bin(&residuals, &edges).unwrap();
+        assert_eq!((worst.lo, worst.hi, worst.closed), (2.0, 5.0, false));
+        assert!((worst.wrmse - 3.0).abs() < 1e-12);
+        assert!(bins[2].closed);
+
+        assert!("5,2".parse::<BinEdges>().is_err());
+        assert!("5".parse::<BinEdges>().is_err());
+    }
+
+    #[test]
+    fn fmt_num_groups_and_swaps_marks_per_locale() {
+        assert_eq!(fmt_num(1234567.891, 2, NumberLocale::Plain), "1234567.89");
+        assert_eq!(fmt_num(1234567.891, 2, NumberLocale::Us), "1,234,567.89");
+        assert_eq!(fmt_num(1234567.891, 2, NumberLocale::Eu), "1.234.567,89");
+        assert_eq!(fmt_num(-1234.5, 1, NumberLocale::Us), "-1,234.5");
+        assert_eq!(fmt_num(999.0, 0, NumberLocale::Eu), "999");
+        assert_eq!(fmt_num(-0.25, 3, NumberLocale::Eu), "-0,250");
+        assert_eq!(fmt_vec(&[1.5, 2.0], NumberLocale::Eu), "[1,500000; 2,000000]");
+    }
+
+    #[test]
+    fn model_comparison_columns_fit_the_longest_name() {
+        let row = |model: ModelKind, skip_reason: Option<&str>| ModelMetrics {
+            model,
+            n: 40,
+            params: model.param_count(),
+            sse: skip_reason.is_none().then_some(12.5),
+            rmse: skip_reason.is_none().then_some(0.5),
+            mae: skip_reason.is_none().then_some(0.4),
+            bic: skip_reason.is_none().then_some(-20.0),
+            aic: skip_reason.is_none().then_some(-25.0),
+            r2: skip_reason.is_none().then_some(0.9),
+            selected: model == ModelKind::Ns,
+            skip_reason: skip_reason.map(str::to_string),
+        };
+        let rows = [
+            row(ModelKind::Ns, None),
+            row(ModelKind::NssShared, None),
+            row(ModelKind::Nssc, Some("Underdetermined")),
+        ];
+        let text = format_model_comparison(&rows, YUnit::Bp, NumberLocale::Plain);
+        // The `n` column sits at the same char offset on the header and every row.
+        let col = |line: &str, cell: &str| line[..line.find(cell).unwrap()].chars().count();
+        let lines: Vec<&str> = text.lines().skip(1).collect();
+        let header = col(lines[0], "    n ");
+        assert!(lines[1..].iter().all(|line| col(line, "   40 ") == header), "{text}");
+        assert!(text.contains("NSS (shared τ) "), "{text}");
+    }
 }
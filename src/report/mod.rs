@@ -1,6 +1,18 @@
 //! Reporting and formatting for terminal output.
 
+pub mod diagnostics;
+pub mod drift;
 pub mod format;
+pub mod moves;
+pub mod run;
+pub mod stats;
+pub mod summary;
 
+pub use diagnostics::*;
+pub use drift::*;
 pub use format::*;
+pub use moves::*;
+pub use run::*;
+pub use stats::*;
+pub use summary::*;
 
@@ -0,0 +1,160 @@
+//! Realized vs model-implied curve moves between two dates (`rv compare-dates`).
+//!
+//! At each tenor the fitted curve's move is set against the move of the FRED
+//! baseline it was sampled from, and split in two by refitting the later
+//! bonds with the earlier model kind and taus:
+//!
+//! ```text
+//! Δcurve = (held - from)  +  (to - held)
+//!          input-driven      structure-driven
+//! ```
+//!
+//! `Δcurve / Δinput` above 1 means the fit amplified the market move, below 1
+//! that it damped it.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::domain::{FitConfig, RatingBand, YUnit};
+use crate::error::AppError;
+use crate::report::{DatePair, RunOutput};
+
+/// Below this input move (bp) the amplification ratio is not reported.
+const MIN_INPUT_MOVE_BP: f64 = 0.01;
+
+/// Both fits and the per-tenor moves between them.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurveMoves {
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+    pub rating: RatingBand,
+    pub unit: YUnit,
+    /// Sample seed both fits drew their bonds with.
+    pub seed: u64,
+    pub from_model: MoveModel,
+    pub to_model: MoveModel,
+    /// The selected kind or taus differ between the dates.
+    pub structure_changed: bool,
+    pub rows: Vec<TenorMove>,
+}
+
+/// A selected curve's structure.
+#[derive(Debug, Clone, Serialize)]
+pub struct MoveModel {
+    pub display_name: String,
+    pub taus: Vec<f64>,
+}
+
+/// Moves at one tenor, in `CurveMoves::unit`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TenorMove {
+    pub tenor: f64,
+    /// FRED baseline move (what the market inputs did).
+    pub input_delta: f64,
+    pub curve_from: f64,
+    pub curve_to: f64,
+    pub curve_delta: f64,
+    /// Curve move with the model structure held at `from`'s (`None` for a
+    /// spliced curve).
+    pub input_driven: Option<f64>,
+    /// Remainder from re-selecting the model kind and taus.
+    pub structure_driven: Option<f64>,
+    /// `curve_delta / input_delta`; `None` when the inputs barely moved.
+    pub amplification: Option<f64>,
+}
+
+/// Per-tenor moves between the two fits of `pair` at `tenors`.
+pub fn curve_moves(pair: &DatePair, config: &FitConfig, tenors: &[f64]) -> Result<CurveMoves, AppError> {
+    let (from, to) = (&pair.from, &pair.to);
+    let unit = to.ingest.input_spec.y_unit;
+    let blend = config.sample_blend();
+    let baseline = |run: &RunOutput, t: f64| {
+        crate::data::sample::blended_baseline_curve(&run.snapshot, &blend, t).map(|y| y * unit.per_bp())
+    };
+    let (from_curve, to_curve) = (&from.selection.best.model, &to.selection.best.model);
+
+    let rows = tenors
+        .iter()
+        .map(|&tenor| {
+            if !(tenor.is_finite() && tenor > 0.0) {
+                return Err(AppError::new(2, format!("Invalid tenor {tenor}: must be a positive number of years.")));
+            }
+            let input_delta = baseline(to, tenor)? - baseline(from, tenor)?;
+            let (curve_from, curve_to) = (from_curve.eval(tenor), to_curve.eval(tenor));
+            let curve_delta = curve_to - curve_from;
+            let held = pair.held.as_ref().map(|m| m.eval(tenor));
+            Ok(TenorMove {
+                tenor,
+                input_delta,
+                curve_from,
+                curve_to,
+                curve_delta,
+                input_driven: held.map(|h| h - curve_from),
+                structure_driven: held.map(|h| curve_to - h),
+                amplification: (input_delta.abs() / unit.per_bp() >= MIN_INPUT_MOVE_BP)
+                    .then(|| curve_delta / input_delta),
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+    let model = |m: &crate::domain::CurveModel| MoveModel { display_name: m.display_name.clone(), taus: m.taus.clone() };
+    Ok(CurveMoves {
+        from_date: from.ingest.input_spec.asof_date,
+        to_date: to.ingest.input_spec.asof_date,
+        rating: config.rating,
+        unit,
+        seed: to.sample.seed,
+        from_model: model(from_curve),
+        to_model: model(to_curve),
+        structure_changed: from_curve.name != to_curve.name
+            || from_curve.taus != to_curve.taus
+            || from_curve.splice.is_some() != to_curve.splice.is_some(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn moves_pin_the_seed_and_split_into_input_and_structure() {
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "--model", "ns"]).unwrap();
+        let config = crate::app::fit_config_from_args(&args);
+        let from = crate::data::sample::canned_snapshot();
+        let mut to = from.clone();
+        to.date = from.date.succ_opt().unwrap();
+        // A proportional 10% widening of every input. With the same seed (and
+        // unchanged vols) every sampled spread scales by exactly 1.1, which
+        // scales each tau's SSE alike: the held NS fit keeps its taus, so the
+        // whole move is input-driven.
+        to.overall_bp *= 1.1;
+        for level in [&mut to.buckets.y_13y, &mut to.buckets.y_35y, &mut to.buckets.y_57y, &mut to.buckets.y_710y] {
+            *level *= 1.1;
+        }
+        for level in to.ratings_bp.values_mut() {
+            *level *= 1.1;
+        }
+
+        let pair = crate::app::pipeline::run_date_pair(&config, from, to).unwrap();
+        assert_eq!(pair.from.sample.seed, pair.to.sample.seed);
+        let tenors: Vec<f64> = pair.from.sample.points.iter().map(|p| p.tenor).collect();
+        assert_eq!(tenors, pair.to.sample.points.iter().map(|p| p.tenor).collect::<Vec<_>>());
+
+        let moves = curve_moves(&pair, &config, &[2.0, 5.0, 10.0]).unwrap();
+        assert_eq!(moves.rows.len(), 3);
+        for row in &moves.rows {
+            assert!(row.input_delta > 0.0, "{row:?}");
+            let split = row.input_driven.unwrap() + row.structure_driven.unwrap();
+            assert!((split - row.curve_delta).abs() < 1e-9, "{row:?}");
+            assert!((row.amplification.unwrap() - row.curve_delta / row.input_delta).abs() < 1e-12);
+        }
+        assert!(!moves.structure_changed, "{:?} -> {:?}", moves.from_model.taus, moves.to_model.taus);
+        assert!(moves.rows.iter().all(|r| r.structure_driven.unwrap().abs() < 1e-6), "{:?}", moves.rows);
+
+        let text = crate::report::format_curve_moves(&moves);
+        assert!(text.contains("input-driven") && text.contains(&format!("seed {}", moves.seed)), "{text}");
+        assert!(curve_moves(&pair, &config, &[-1.0]).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! Outputs of a pipeline run (`app::pipeline`), shared by the front-ends,
+//! the formatters, and the exporters.
+
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use crate::data::{FredSnapshot, SampleData};
+use crate::domain::{BondPoint, BondResidual, CurveModel, ModelKind, RatingBand};
+use crate::error::AppError;
+use crate::fit::selection::FitSelection;
+use crate::io::ingest::IngestedData;
+use crate::report::Rankings;
+
+/// All computed outputs of a single `rv fit` run.
+#[derive(Debug, Clone)]
+pub struct RunOutput {
+    pub ingest: IngestedData,
+    /// Bonds inside the `--fit-min`/`--fit-max` window; `None` when the fit
+    /// used every ingested bond (see `fitted_points`).
+    pub fit_points: Option<Vec<BondPoint>>,
+    pub selection: FitSelection,
+    pub residuals: Vec<BondResidual>,
+    pub rankings: Rankings,
+    pub sample: SampleData,
+    pub snapshot: FredSnapshot,
+    /// Non-fatal issues worth surfacing to the user (printed to stderr by the CLI).
+    pub warnings: Vec<String>,
+    /// Wall-clock time spent in each pipeline phase (printed with `--timing`).
+    pub timings: PhaseTimings,
+}
+
+impl RunOutput {
+    /// The bonds the selected curve was fitted to.
+    pub fn fitted_points(&self) -> &[BondPoint] {
+        self.fit_points.as_deref().unwrap_or(&self.ingest.points)
+    }
+}
+
+/// Wall-clock durations of the pipeline phases.
+///
+/// Always recorded (it is cheap) and never fed back into the fit, so timing
+/// cannot affect results or the run hash.
+#[derive(Debug, Clone, Default)]
+pub struct PhaseTimings {
+    /// FRED fetch; `None` when the snapshot was supplied by the caller.
+    pub fetch: Option<Duration>,
+    /// Sample generation, ingest, and weighting.
+    pub sample: Duration,
+    /// Tau search and model selection.
+    pub fit: Duration,
+    /// Share of `fit` spent in each model's tau search.
+    pub models: Vec<(ModelKind, Duration)>,
+    /// Residuals and cheap/rich rankings.
+    pub rank: Duration,
+}
+
+impl PhaseTimings {
+    /// Total of all recorded phases.
+    pub fn total(&self) -> Duration {
+        self.fetch.unwrap_or_default() + self.sample + self.fit + self.rank
+    }
+}
+
+/// Outcome of a batch run.
+#[derive(Debug)]
+pub struct BatchRun {
+    /// Fitted cells, sorted by date then rating.
+    pub cells: Vec<BatchCell>,
+    /// Ratings left out because they are missing from the snapshot
+    /// (`--missing-rating skip`).
+    pub skipped: Vec<RatingBand>,
+}
+
+/// One cell of a batch run: a (date, rating) pair and its outcome.
+#[derive(Debug)]
+pub struct BatchCell {
+    pub date: NaiveDate,
+    pub rating: RatingBand,
+    pub result: Result<RunOutput, AppError>,
+}
+
+/// The same fit run on two snapshots (`rv compare-dates`).
+#[derive(Debug)]
+pub struct DatePair {
+    pub from: RunOutput,
+    pub to: RunOutput,
+    /// `to`'s bonds refitted with `from`'s model kind and taus (betas
+    /// re-solved): the curve move with the model structure held fixed.
+    /// `None` for a spliced `from` curve.
+    pub held: Option<CurveModel>,
+}
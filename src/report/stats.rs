@@ -0,0 +1,128 @@
+//! Aggregate residual statistics (`--export-stats`).
+//!
+//! Moments are taken about the mean in a second pass over the residuals
+//! rather than from raw power sums, so a large common offset does not cancel
+//! away the spread. Skewness and excess kurtosis use the sample-adjusted
+//! estimators (as spreadsheet `SKEW`/`KURT`) and are `None` when the sample is
+//! too small or has no spread.
+
+use serde::Serialize;
+
+use crate::domain::BondResidual;
+
+/// One-row summary of a fit's residuals (unweighted, in the output unit).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResidualStats {
+    pub n: usize,
+    /// Unweighted, unlike the fit's `rmse` in the summary.
+    pub rmse_unweighted: Option<f64>,
+    pub mae: Option<f64>,
+    pub mean: Option<f64>,
+    /// Sample standard deviation (n - 1); needs n >= 2.
+    pub std: Option<f64>,
+    /// Adjusted Fisher-Pearson skewness; needs n >= 3 and a nonzero spread.
+    pub skew: Option<f64>,
+    /// Sample excess kurtosis; needs n >= 4 and a nonzero spread.
+    pub kurtosis: Option<f64>,
+    /// Largest positive residual.
+    pub worst_cheap: Option<ExtremeBond>,
+    /// Most negative residual.
+    pub worst_rich: Option<ExtremeBond>,
+}
+
+/// A bond at one end of the residual distribution.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtremeBond {
+    pub id: String,
+    pub tenor: f64,
+    pub residual: f64,
+}
+
+/// Summarize `residuals`; every field but `n` is `None` for an empty slice.
+pub fn residual_stats(residuals: &[BondResidual]) -> ResidualStats {
+    let n = residuals.len();
+    if n == 0 {
+        return ResidualStats::default();
+    }
+    let nf = n as f64;
+    let values = || residuals.iter().map(|r| r.residual);
+
+    let mean = values().sum::<f64>() / nf;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for d in values().map(|v| v - mean) {
+        let d2 = d * d;
+        m2 += d2;
+        m3 += d2 * d;
+        m4 += d2 * d2;
+    }
+    let spread = m2 > 0.0;
+    let skew = (n >= 3 && spread).then(|| {
+        let g1 = (m3 / nf) / (m2 / nf).powf(1.5);
+        g1 * (nf * (nf - 1.0)).sqrt() / (nf - 2.0)
+    });
+    let kurtosis = (n >= 4 && spread).then(|| {
+        let g2 = (m4 / nf) / (m2 / nf).powi(2) - 3.0;
+        (nf - 1.0) / ((nf - 2.0) * (nf - 3.0)) * ((nf + 1.0) * g2 + 6.0)
+    });
+
+    let extreme = |pick: fn(f64, f64) -> bool| {
+        residuals
+            .iter()
+            .reduce(|best, r| if pick(r.residual, best.residual) { r } else { best })
+            .map(|r| ExtremeBond { id: r.point.id.clone(), tenor: r.point.tenor, residual: r.residual })
+    };
+    let worst_cheap = extreme(|a, b| a > b).filter(|b| b.residual > 0.0);
+    let worst_rich = extreme(|a, b| a < b).filter(|b| b.residual < 0.0);
+
+    ResidualStats {
+        n,
+        rmse_unweighted: Some((values().map(|v| v * v).sum::<f64>() / nf).sqrt()),
+        mae: Some(values().map(f64::abs).sum::<f64>() / nf),
+        mean: Some(mean),
+        std: (n >= 2).then(|| (m2 / (nf - 1.0)).sqrt()),
+        skew,
+        kurtosis,
+        worst_cheap,
+        worst_rich,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moments_match_reference_values_and_survive_a_large_offset() {
+        // Spreadsheet SKEW/KURT of this sample: 0.818487..., 0.940625.
+        let sample = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let stats = residual_stats(&BondResidual::fixtures(&sample));
+        assert_eq!(stats.n, 8);
+        assert!((stats.mean.unwrap() - 5.0).abs() < 1e-12);
+        assert!((stats.std.unwrap() - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert!((stats.skew.unwrap() - 0.818_487_553_356_058).abs() < 1e-9, "{stats:?}");
+        assert!((stats.kurtosis.unwrap() - 0.940_625).abs() < 1e-9, "{stats:?}");
+        assert_eq!(stats.worst_cheap.as_ref().unwrap().id, "B7");
+        assert!(stats.worst_rich.is_none());
+
+        // Raw power sums would lose every digit here; central moments keep them.
+        let shifted: Vec<f64> = sample.iter().map(|v| v + 1e9).collect();
+        let far = residual_stats(&BondResidual::fixtures(&shifted));
+        assert!((far.std.unwrap() - stats.std.unwrap()).abs() < 1e-6);
+        assert!((far.skew.unwrap() - stats.skew.unwrap()).abs() < 1e-6);
+        assert!((far.kurtosis.unwrap() - stats.kurtosis.unwrap()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn small_samples_leave_undefined_moments_empty() {
+        assert!(residual_stats(&[]).mean.is_none());
+        let one = residual_stats(&BondResidual::fixtures(&[-1.5]));
+        assert_eq!((one.mean, one.mae), (Some(-1.5), Some(1.5)));
+        assert!(one.std.is_none() && one.skew.is_none() && one.kurtosis.is_none());
+        assert_eq!(one.worst_rich.unwrap().id, "B0");
+        let three = residual_stats(&BondResidual::fixtures(&[1.0, -2.0, 4.0]));
+        assert!(three.skew.is_some() && three.kurtosis.is_none());
+        let flat = residual_stats(&BondResidual::fixtures(&[0.5; 5]));
+        assert_eq!(flat.std, Some(0.0));
+        assert!(flat.skew.is_none() && flat.kurtosis.is_none());
+    }
+}
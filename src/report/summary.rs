@@ -0,0 +1,319 @@
+//! Structured run summary for library consumers.
+//!
+//! `build_run_summary` collects everything the terminal summary shows into a
+//! serializable `RunSummary`; `format_run_summary` renders the text from it,
+//! so notebooks and GUIs read the same numbers the CLI prints without
+//! scraping the text.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::data::SnapshotInputs;
+use crate::domain::{Basis, FitConfig, ModelKind, RatingBand, YKind, YTransform, YUnit};
+use crate::fit::selection::bic_weights;
+use crate::models::find_peak;
+use crate::report::{compute_slopes, worst_fit_bin, PhaseTimings, ResidualBin, RunOutput, SlopeValue};
+
+/// Everything `format_run_summary` prints, as data.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub rating: RatingBand,
+    /// FRED observation date the run used.
+    pub asof_date: NaiveDate,
+    /// `--asof` as requested, when given.
+    pub requested_asof: Option<NaiveDate>,
+    pub y_kind: YKind,
+    pub y_unit: YUnit,
+    pub sample: SampleSettings,
+    pub accounting: AccountingSummary,
+    pub points: PointStats,
+    /// Fitted models in selection order.
+    pub models: Vec<ModelDiagnostics>,
+    /// Models the guardrails skipped, with the reason.
+    pub skipped: Vec<SkippedModel>,
+    pub chosen: ChosenModel,
+    /// `--slopes` evaluated on the chosen curve (empty without the flag).
+    pub slopes: Vec<SlopeValue>,
+    /// FRED inputs behind the sample (`--embed-inputs`; omitted otherwise).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<SnapshotInputs>,
+    /// Pipeline phase wall times (`--timing`; omitted otherwise).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingSummary>,
+}
+
+/// `PhaseTimings` in milliseconds; per-model search times are in
+/// `ModelDiagnostics::fit_ms`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingSummary {
+    /// `None` when the snapshot was supplied by the caller.
+    pub fetch_ms: Option<f64>,
+    pub sample_ms: f64,
+    pub fit_ms: f64,
+    pub rank_ms: f64,
+    pub total_ms: f64,
+}
+
+impl From<&PhaseTimings> for TimingSummary {
+    fn from(timings: &PhaseTimings) -> Self {
+        let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+        TimingSummary {
+            fetch_ms: timings.fetch.map(ms),
+            sample_ms: ms(timings.sample),
+            fit_ms: ms(timings.fit),
+            rank_ms: ms(timings.rank),
+            total_ms: ms(timings.total()),
+        }
+    }
+}
+
+/// Sample size and the configured tenor bounds.
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleSettings {
+    /// Bonds generated (or loaded with `--sample-from-points`).
+    pub count: usize,
+    pub tenor_min: f64,
+    pub tenor_max: f64,
+    /// `--fit-min`/`--fit-max` window (omitted when unset).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fit_window: Option<FitWindow>,
+}
+
+/// Tenor sub-range the curve was fitted to; every used bond is still ranked.
+#[derive(Debug, Clone, Serialize)]
+pub struct FitWindow {
+    pub tenor_min: f64,
+    pub tenor_max: f64,
+    /// Bonds inside the window (the fit's `n`).
+    pub fitted: usize,
+}
+
+/// How many generated points reached the fit (`PointAccounting`).
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountingSummary {
+    pub generated: usize,
+    pub dropped_by_bound: usize,
+    pub dropped_by_trim: usize,
+    pub dropped_by_weight: usize,
+    /// Ranked but outside the `--fit-min`/`--fit-max` window.
+    pub outside_fit_window: usize,
+    /// Bonds the curve was fitted to.
+    pub used: usize,
+}
+
+/// Ranges of the points that were fitted.
+#[derive(Debug, Clone, Serialize)]
+pub struct PointStats {
+    pub n: usize,
+    pub tenor_min: f64,
+    pub tenor_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+/// One fitted model's quality line.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDiagnostics {
+    pub kind: ModelKind,
+    pub display_name: String,
+    pub sse: f64,
+    pub rmse: f64,
+    pub bic: f64,
+    /// Akaike-style BIC weight across the fitted models.
+    pub bic_weight: f64,
+    pub selected: bool,
+    /// Tau tuples the grid search evaluated for this model.
+    pub candidates: usize,
+    /// Wall time of this model's tau search (ms).
+    pub fit_ms: f64,
+}
+
+/// A model left out of the comparison.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedModel {
+    pub kind: ModelKind,
+    pub reason: String,
+}
+
+/// The selected curve and what the summary derives from it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChosenModel {
+    pub kind: ModelKind,
+    pub display_name: String,
+    pub betas: Vec<f64>,
+    pub y_transform: YTransform,
+    pub basis: Basis,
+    /// Decay parameters in `basis` units (taus, or Diebold-Li lambdas).
+    pub decay: Vec<f64>,
+    /// Interior hump `(tenor, level)` within the observed range.
+    pub peak: Option<(f64, f64)>,
+    /// Tenor bin (`--resid-bins`) with the largest weighted RMSE.
+    pub worst_fit_bin: Option<ResidualBin>,
+}
+
+/// Collect the run summary for `run` under `config`.
+pub fn build_run_summary(run: &RunOutput, config: &FitConfig) -> RunSummary {
+    let ingest = &run.ingest;
+    let selection = &run.selection;
+    let best = &selection.best;
+    let stats = &ingest.stats;
+    let acc = &ingest.accounting;
+
+    let weights = bic_weights(&selection.fits);
+    let models = selection
+        .fits
+        .iter()
+        .zip(&weights)
+        .map(|(fit, &bic_weight)| ModelDiagnostics {
+            kind: fit.model.name,
+            display_name: fit.model.display_name.clone(),
+            sse: fit.quality.sse,
+            rmse: fit.quality.rmse,
+            bic: fit.quality.bic,
+            bic_weight,
+            selected: fit.model.name == best.model.name,
+            candidates: selection
+                .candidates
+                .iter()
+                .find(|(kind, _)| *kind == fit.model.name)
+                .map_or(0, |&(_, n)| n),
+            fit_ms: selection
+                .durations
+                .iter()
+                .find(|(kind, _)| *kind == fit.model.name)
+                .map_or(0.0, |(_, d)| d.as_secs_f64() * 1000.0),
+        })
+        .collect();
+
+    RunSummary {
+        rating: config.rating,
+        asof_date: ingest.input_spec.asof_date,
+        requested_asof: config.asof,
+        y_kind: ingest.input_spec.y_kind,
+        y_unit: ingest.input_spec.y_unit,
+        sample: SampleSettings {
+            count: run.sample.points.len(),
+            tenor_min: config.tenor_min,
+            tenor_max: config.tenor_max,
+            fit_window: config.fit_window().map(|(tenor_min, tenor_max)| FitWindow {
+                tenor_min,
+                tenor_max,
+                fitted: best.quality.n,
+            }),
+        },
+        accounting: AccountingSummary {
+            generated: acc.generated,
+            dropped_by_bound: acc.dropped_by_bound,
+            dropped_by_trim: acc.dropped_by_trim,
+            dropped_by_weight: acc.dropped_by_weight,
+            outside_fit_window: acc.outside_fit_window,
+            used: acc.used(),
+        },
+        points: PointStats {
+            n: stats.n_points,
+            tenor_min: stats.tenor_min,
+            tenor_max: stats.tenor_max,
+            y_min: stats.y_min,
+            y_max: stats.y_max,
+        },
+        models,
+        skipped: selection
+            .skipped
+            .iter()
+            .map(|(kind, reason)| SkippedModel { kind: *kind, reason: reason.clone() })
+            .collect(),
+        chosen: ChosenModel {
+            kind: best.model.name,
+            display_name: best.model.display_name.clone(),
+            betas: best.model.betas.clone(),
+            y_transform: best.model.y_transform,
+            basis: config.basis,
+            decay: config.basis.decay_params(&best.model.taus),
+            peak: find_peak(&best.model, stats.tenor_min, stats.tenor_max),
+            worst_fit_bin: worst_fit_bin(&run.residuals, &config.resid_bins),
+        },
+        slopes: compute_slopes(best, &config.slopes, stats.tenor_min, stats.tenor_max),
+        inputs: config.embed_inputs.then(|| run.snapshot.inputs(config.smooth_days)),
+        timings: config.timing.then(|| TimingSummary::from(&run.timings)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn summary_serializes_and_drives_the_text_output() {
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "--model", "all", "--slopes", "2-10"]).unwrap();
+        let config = crate::app::fit_config_from_args(&args);
+        let run = crate::app::pipeline::run_fit_with_snapshot(&config, crate::data::sample::canned_snapshot()).unwrap();
+        let summary = build_run_summary(&run, &config);
+
+        assert_eq!(summary.models.len(), run.selection.fits.len());
+        // Post-filter grid sizes: NS searches every step, NSS only tau1 < tau2 pairs.
+        let steps = config.tau_steps_nss;
+        let nss = summary.models.iter().find(|m| m.kind == ModelKind::Nss).unwrap();
+        assert_eq!(nss.candidates, steps * (steps - 1) / 2);
+        assert_eq!(summary.models[0].candidates, config.tau_steps_ns);
+        assert_eq!(summary.models.iter().filter(|m| m.selected).count(), 1);
+        assert_eq!(summary.chosen.kind, run.selection.best.model.name);
+        assert_eq!(summary.accounting.used, run.ingest.points.len());
+
+        // Per-model fit time: one entry per fitted model, within the fit phase.
+        assert_eq!(run.timings.models.len(), run.selection.fits.len());
+        let per_model: std::time::Duration = run.timings.models.iter().map(|(_, d)| *d).sum();
+        assert!(per_model <= run.timings.fit);
+        let timing = crate::report::format_timing(&run.timings);
+        assert!(timing.contains(&format!("\n    {:<16} ", ModelKind::Nssc.display_name())), "{timing}");
+
+        let json: serde_json::Value = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["rating"], "BBB");
+        assert_eq!(json["chosen"]["basis"], "standard");
+        assert_eq!(json["slopes"][0]["label"], "2-10");
+        assert!(json["models"][0]["fit_ms"].as_f64().unwrap() >= 0.0);
+        assert!(json.get("timings").is_none());
+        let timed = FitConfig { timing: true, ..config.clone() };
+        let json = serde_json::to_value(build_run_summary(&run, &timed)).unwrap();
+        assert!(json["timings"]["fetch_ms"].is_null());
+        let phases: f64 = ["sample_ms", "fit_ms", "rank_ms"].iter().map(|k| json["timings"][k].as_f64().unwrap()).sum();
+        assert!((json["timings"]["total_ms"].as_f64().unwrap() - phases).abs() < 1e-6, "{}", json["timings"]);
+
+        // The text is a rendering of the struct: edit a field, see it printed.
+        let mut edited = summary.clone();
+        edited.chosen.display_name = "EDITED".to_string();
+        edited.chosen.worst_fit_bin = Some(ResidualBin { lo: 2.0, hi: 5.0, closed: false, n: 3, wrmse: 1.0 });
+        let text = crate::report::format_run_summary(&edited, config.locale);
+        assert!(text.contains("- EDITED (kind="), "{text}");
+        assert!(text.contains("- worst-fit region: [2.00, 5.00)y "), "{text}");
+        assert!(text.contains("  2-10 = "), "{text}");
+        assert!(text.contains(&format!(" candidates={}\n", nss.candidates)), "{text}");
+    }
+
+    #[test]
+    fn embedded_inputs_follow_the_snapshot_in_rating_order() {
+        let args = crate::cli::FitArgs::try_parse_from(["rv", "--embed-inputs", "--export-diagnostics", "d.json"]).unwrap();
+        let config = crate::app::fit_config_from_args(&args);
+        let mut snapshot = crate::data::sample::canned_snapshot();
+        // Stand-in for a smoothed level: the summary reports what the fit used.
+        snapshot.ratings_bp.insert(RatingBand::BBB, 123.5);
+        snapshot.volatility.fallback_series.push(snapshot.series.rating(RatingBand::CCC).to_string());
+        let run = crate::app::pipeline::run_fit_with_snapshot(&config, snapshot).unwrap();
+        let inputs = build_run_summary(&run, &config).inputs.unwrap();
+        assert_eq!(inputs.ratings[&RatingBand::BBB].level_bp, 123.5);
+        assert!(inputs.ratings[&RatingBand::CCC].vol_fallback && !inputs.overall.vol_fallback);
+        assert_eq!(inputs.buckets["7-10y"].level_bp, 91.0);
+
+        let json = serde_json::to_string(&inputs).unwrap();
+        let positions: Vec<usize> = RatingBand::ALL
+            .iter()
+            .map(|band| json.find(&format!("\"{}\":{{", band.display_name())).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{json}");
+
+        let plain = crate::app::fit_config_from_args(&crate::cli::FitArgs::try_parse_from(["rv"]).unwrap());
+        let value = serde_json::to_value(build_run_summary(&run, &plain)).unwrap();
+        assert!(value.get("inputs").is_none());
+        assert!(crate::cli::FitArgs::try_parse_from(["rv", "--embed-inputs"]).is_err());
+    }
+}
@@ -11,7 +11,12 @@
 //! - g: regenerate sample (new random seed)
 //! - m: cycle model (Auto → NS → NSS → NSS+)
 //! - e: export results
+//! - p: save the current chart as SVG
+//! - `:`: type a numeric knob, e.g. `:tau-max 20` (Enter applies, Esc cancels)
 //! - q: quit
+//!
+//! `--tui-script` drives the same key handler from a fixed key list and prints
+//! one rendered frame (via ratatui's `TestBackend`), for reproducible snapshots.
 
 use std::io;
 use std::time::Duration;
@@ -22,7 +27,8 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
-    backend::CrosstermBackend,
+    backend::{CrosstermBackend, TestBackend},
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
@@ -32,7 +38,7 @@ use ratatui::{
 
 use crate::cli::FitArgs;
 use crate::data::{FredClient, FredSnapshot};
-use crate::domain::{ModelSpec, RatingBand, YKind};
+use crate::domain::{BondResidual, FitConfig, ModelSpec, RatingBand, RatingBlend, YKind, YUnit};
 use crate::error::AppError;
 
 mod plotters_chart;
@@ -42,8 +48,34 @@ use plotters_chart::RvPlottersChart;
 /// Sample count options available in the UI.
 const SAMPLE_COUNTS: &[usize] = &[25, 50, 75, 100, 150, 200, 300, 500];
 
+/// Columns taken by the sidebar and chart borders (see `App::draw`).
+const CHART_CHROME_COLS: u16 = 22;
+
+/// Fitted-curve samples for a terminal `width` columns wide: a few per chart
+/// column, never fewer than the original 200 nor more than 2000.
+fn curve_resolution(width: u16) -> usize {
+    (usize::from(width.saturating_sub(CHART_CHROME_COLS)) * 4).clamp(200, 2000)
+}
+
+/// Off-screen frame size used by `--tui-script`.
+const SCRIPT_WIDTH: u16 = 160;
+const SCRIPT_HEIGHT: u16 = 40;
+
 /// Start the TUI.
-pub fn run(args: FitArgs) -> Result<(), AppError> {
+pub fn run(args: FitArgs, color: bool) -> Result<(), AppError> {
+    use std::io::IsTerminal;
+    if let Some(script) = args.tui_script.clone() {
+        let keys = parse_tui_script(&script)?;
+        let mut app = App::new(args)?;
+        print!("{}", app.run_script(&keys, SCRIPT_WIDTH, SCRIPT_HEIGHT)?);
+        return Ok(());
+    }
+    if !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        return Err(AppError::new(
+            4,
+            "rv tui needs an interactive terminal (stdin/stdout are not a TTY); use `rv fit` instead.",
+        ));
+    }
     let _guard = TerminalGuard::new()?;
 
     let backend = CrosstermBackend::new(io::stdout());
@@ -51,6 +83,10 @@ pub fn run(args: FitArgs) -> Result<(), AppError> {
         .map_err(|e| AppError::new(4, format!("Failed to initialize terminal: {e}")))?;
 
     let mut app = App::new(args)?;
+    app.color = color;
+    if let Ok(size) = terminal.size() {
+        app.curve_points = curve_resolution(size.width);
+    }
     app.event_loop(&mut terminal)
 }
 
@@ -77,7 +113,6 @@ impl Drop for TerminalGuard {
 
 /// Top-level TUI state.
 struct App {
-    #[allow(dead_code)]
     base_args: FitArgs,
     snapshot: FredSnapshot,
     status: String,
@@ -86,17 +121,36 @@ struct App {
     rating_index: usize,
     sample_count_index: usize,
     
+    // `:` input buffer while typing a knob value (`None` = normal key handling).
+    input: Option<String>,
+
+    // Fitted-curve samples per chart, tracking the terminal width.
+    curve_points: usize,
+
+    // ANSI colour on (off for `--no-color`, `NO_COLOR`, and scripted frames).
+    color: bool,
+
+    // Zoomed chart tenor window (`None` = the full fitted range).
+    zoom: Option<[f64; 2]>,
+
     // Fit results
-    run: crate::app::pipeline::RunOutput,
+    run: crate::report::RunOutput,
     config: crate::domain::FitConfig,
 }
 
 impl App {
     fn new(args: FitArgs) -> Result<Self, AppError> {
-        let client = FredClient::from_env()?;
-        let snapshot = client.fetch_snapshot(None)?;
-
         let config = crate::app::fit_config_from_args(&args);
+        let client = FredClient::new(config.api_key_file.as_deref())?;
+        let snapshot = client.fetch_snapshot(&crate::data::SnapshotOptions::from_config(&config)?)?;
+        Self::with_snapshot(args, snapshot)
+    }
+
+    fn with_snapshot(args: FitArgs, snapshot: FredSnapshot) -> Result<Self, AppError> {
+        let mut config = crate::app::fit_config_from_args(&args);
+        // stderr progress lines would corrupt the alternate screen.
+        config.progress = false;
+
         let run = crate::app::pipeline::run_fit_with_snapshot(&config, snapshot.clone())?;
 
         // Find initial indices
@@ -110,7 +164,15 @@ impl App {
             .position(|&n| n == config.sample_count)
             .unwrap_or(3); // Default to 100
 
-        let status = format!("FRED data as of {}", snapshot.date);
+        let status = match run.warnings.first() {
+            Some(warning) => warning.clone(),
+            None => format!("FRED data as of {}", snapshot.date),
+        };
+        // `--random-seed` was resolved by the caller; show it so the sample can be reproduced.
+        let status = match config.rng_seed.filter(|_| args.random_seed) {
+            Some(seed) => format!("{status} | seed {seed} (--deterministic-seed {seed})"),
+            None => status,
+        };
         
         Ok(Self {
             base_args: args,
@@ -118,6 +180,10 @@ impl App {
             status,
             rating_index,
             sample_count_index,
+            input: None,
+            curve_points: curve_resolution(0),
+            color: false,
+            zoom: None,
             run,
             config,
         })
@@ -163,7 +229,11 @@ impl App {
                     }
                     needs_redraw = true;
                 }
-                Event::Resize(_, _) => {
+                Event::Resize(width, _) => {
+                    self.curve_points = curve_resolution(width);
+                    if self.base_args.refit_on_resize {
+                        self.refit()?;
+                    }
                     needs_redraw = true;
                 }
                 _ => {}
@@ -172,45 +242,94 @@ impl App {
         Ok(())
     }
 
+    /// Feed `keys` to `handle_key` as if typed (stopping at `q`), then render
+    /// one `width` x `height` frame off-screen and return it as text.
+    fn run_script(&mut self, keys: &[KeyCode], width: u16, height: u16) -> Result<String, AppError> {
+        for &code in keys {
+            if self.handle_key(code)? {
+                break;
+            }
+        }
+        self.curve_points = curve_resolution(width);
+        let mut terminal = Terminal::new(TestBackend::new(width, height))
+            .map_err(|e| AppError::new(4, format!("Failed to initialize test terminal: {e}")))?;
+        terminal
+            .draw(|f| self.draw(f))
+            .map_err(|e| AppError::new(4, format!("Terminal draw error: {e}")))?;
+        Ok(buffer_text(terminal.backend().buffer()))
+    }
+
+    /// Keys while the `:` input line is open. Nothing refits until Enter, and a
+    /// rejected value or failed fit leaves the previous config and curve intact.
+    fn handle_input_key(&mut self, code: KeyCode) {
+        let Some(buffer) = self.input.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Char(c) => buffer.push(c),
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Enter => {
+                let line = self.input.take().unwrap_or_default();
+                self.status = match apply_knob(&self.config, &line) {
+                    Ok(candidate) => {
+                        match crate::app::pipeline::run_fit_with_snapshot(&candidate, self.snapshot.clone()) {
+                            Ok(run) => {
+                                self.config = candidate;
+                                self.run = run;
+                                format!("Set {}", line.trim())
+                            }
+                            Err(e) => format!("Refit failed, kept previous curve: {e}"),
+                        }
+                    }
+                    Err(e) => e,
+                };
+            }
+            _ => {}
+        }
+    }
+
     fn handle_key(&mut self, code: KeyCode) -> Result<bool, AppError> {
+        if self.input.is_some() {
+            self.handle_input_key(code);
+            return Ok(false);
+        }
         match code {
             KeyCode::Char('q') => return Ok(true),
             
-            // Up/Down: change rating
-            KeyCode::Up => {
-                if self.rating_index > 0 {
-                    self.rating_index -= 1;
-                    self.refit()?;
-                    self.status = format!("Rating: {}", self.current_rating().display_name());
-                }
+            // Up/Down: change rating (leaving any --rating-blend)
+            KeyCode::Up if self.rating_index > 0 => {
+                self.rating_index -= 1;
+                self.config.rating_blend = None;
+                self.refit()?;
+                self.status = format!("Rating: {}", self.current_rating().display_name());
             }
-            KeyCode::Down => {
-                if self.rating_index < RatingBand::ALL.len() - 1 {
-                    self.rating_index += 1;
-                    self.refit()?;
-                    self.status = format!("Rating: {}", self.current_rating().display_name());
-                }
+            KeyCode::Down if self.rating_index < RatingBand::ALL.len() - 1 => {
+                self.rating_index += 1;
+                self.config.rating_blend = None;
+                self.refit()?;
+                self.status = format!("Rating: {}", self.current_rating().display_name());
             }
             
             // Left/Right: change sample count
-            KeyCode::Left => {
-                if self.sample_count_index > 0 {
-                    self.sample_count_index -= 1;
-                    self.refit()?;
-                    self.status = format!("Sample count: {}", self.current_sample_count());
-                }
+            KeyCode::Left if self.sample_count_index > 0 => {
+                self.sample_count_index -= 1;
+                self.refit()?;
+                self.status = format!("Sample count: {}", self.current_sample_count());
             }
-            KeyCode::Right => {
-                if self.sample_count_index < SAMPLE_COUNTS.len() - 1 {
-                    self.sample_count_index += 1;
-                    self.refit()?;
-                    self.status = format!("Sample count: {}", self.current_sample_count());
-                }
+            KeyCode::Right if self.sample_count_index < SAMPLE_COUNTS.len() - 1 => {
+                self.sample_count_index += 1;
+                self.refit()?;
+                self.status = format!("Sample count: {}", self.current_sample_count());
             }
             
             // g: regenerate sample
             KeyCode::Char('g') => {
                 self.config.sample_seed = self.config.sample_seed.wrapping_add(1);
+                // An explicit RNG seed would pin the sample; drop it to regenerate.
+                self.config.rng_seed = None;
                 self.refit()?;
                 self.status = format!("Regenerated (seed={})", self.config.sample_seed);
             }
@@ -231,8 +350,10 @@ impl App {
                         crate::io::export::write_results_csv(
                             path,
                             &self.run.residuals,
+                            &self.run.rankings,
                             &self.run.ingest.input_spec,
-                            &self.config,
+                            self.config.round_tenors,
+                            self.config.export_append,
                         )?;
                     }
                     if let Some(path) = &self.config.export_curve {
@@ -247,6 +368,30 @@ impl App {
                 }
             }
             
+            // `:`: open the numeric input line
+            KeyCode::Char(':') => {
+                self.input = Some(String::new());
+            }
+
+            // +/-: zoom the chart about its centre; [/]: pan; 0: full range.
+            // The view only re-samples the current fit.
+            KeyCode::Char('+') | KeyCode::Char('=') => self.set_zoom(0.5, 0.0),
+            KeyCode::Char('-') => self.set_zoom(2.0, 0.0),
+            KeyCode::Char('[') => self.set_zoom(1.0, -0.25),
+            KeyCode::Char(']') => self.set_zoom(1.0, 0.25),
+            KeyCode::Char('0') => {
+                self.zoom = None;
+                self.status = "Zoom reset".to_string();
+            }
+
+            // p: save chart as SVG
+            KeyCode::Char('p') => {
+                self.status = match self.save_chart_svg() {
+                    Ok(path) => format!("Saved chart: {path}"),
+                    Err(e) => format!("Chart export failed: {e}"),
+                };
+            }
+            
             _ => {}
         }
         Ok(false)
@@ -284,15 +429,26 @@ impl App {
         self.draw_footer(frame, chart_chunks[1]);
     }
 
+    /// Foreground style, plain when colour is off.
+    fn fg(&self, color: Color) -> Style {
+        if self.color {
+            Style::default().fg(color)
+        } else {
+            Style::default()
+        }
+    }
+
     fn draw_ratings(&self, frame: &mut ratatui::Frame<'_>, area: Rect) {
         let items: Vec<ListItem> = RatingBand::ALL
             .iter()
             .enumerate()
             .map(|(i, r)| {
-                let style = if i == self.rating_index {
+                let style = if i == self.rating_index && !self.color {
+                    Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+                } else if i == self.rating_index {
                     Style::default().fg(Color::Black).bg(Color::White).add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::Gray)
+                    self.fg(Color::Gray)
                 };
                 ListItem::new(format!(" {} ", r.display_name())).style(style)
             })
@@ -318,7 +474,7 @@ impl App {
         
         let lines = vec![
             Line::from(""),
-            Line::from(Span::styled(text, Style::default().fg(Color::White).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(text, self.fg(Color::White).add_modifier(Modifier::BOLD))),
         ];
         
         let block = Block::default().title("Samples [←→]").borders(Borders::ALL);
@@ -328,28 +484,34 @@ impl App {
 
     fn draw_info(&self, frame: &mut ratatui::Frame<'_>, area: Rect) {
         let best = &self.run.selection.best;
-        
+        let unit = self.run.ingest.input_spec.y_unit;
+
         let lines = vec![
             Line::from(Span::styled(
                 format!("Model: {}", best.model.display_name),
-                Style::default().fg(Color::Cyan),
+                self.fg(Color::Cyan),
             )),
             Line::from(Span::styled(
-                format!("RMSE: {:.2}bp", best.quality.rmse),
-                Style::default().fg(Color::Gray),
+                format!(
+                    "RMSE: {:.*}{}",
+                    unit.decimals(),
+                    best.quality.rmse,
+                    unit.label()
+                ),
+                self.fg(Color::Gray),
             )),
             Line::from(Span::styled(
                 format!("BIC: {:.1}", best.quality.bic),
-                Style::default().fg(Color::Gray),
+                self.fg(Color::Gray),
             )),
             Line::from(""),
             Line::from(Span::styled(
                 format!("FRED: {}", self.snapshot.date),
-                Style::default().fg(Color::DarkGray),
+                self.fg(Color::DarkGray),
             )),
             Line::from(Span::styled(
                 format!("OAS: {:.0}bp", self.snapshot.ratings_bp.get(&self.current_rating()).copied().unwrap_or(0.0)),
-                Style::default().fg(Color::DarkGray),
+                self.fg(Color::DarkGray),
             )),
         ];
 
@@ -358,109 +520,235 @@ impl App {
         frame.render_widget(p, area);
     }
 
+    /// Full tenor range of the chart (the fitted points' span).
+    fn full_range(&self) -> [f64; 2] {
+        [self.run.ingest.stats.tenor_min, self.run.ingest.stats.tenor_max]
+    }
+
+    fn set_zoom(&mut self, factor: f64, pan: f64) {
+        self.zoom = zoom_view(self.full_range(), self.zoom, factor, pan);
+        self.status = match self.zoom {
+            Some([lo, hi]) => format!("Zoom: {lo:.2}-{hi:.2}y (0 resets)"),
+            None => "Zoom: full range".to_string(),
+        };
+    }
+
     fn draw_chart(&self, frame: &mut ratatui::Frame<'_>, area: Rect) {
-        let y_kind = self.run.ingest.input_spec.y_kind;
         let x_min = self.run.ingest.stats.tenor_min;
-        let (curve, points, cheap, rich, x_bounds, y_bounds) = chart_series(&self.run, x_min);
-
-        let title = format!(
-            "RV Curve - {} (n={})",
-            self.current_rating().display_name(),
-            self.current_sample_count()
+        let series = chart_series(
+            &self.run,
+            x_min,
+            self.curve_points,
+            self.baseline_blend().as_ref(),
+            self.config.plot_vol,
+            self.zoom,
         );
-        let block = Block::default().title(title).borders(Borders::ALL);
+
+        let block = Block::default().title(self.chart_title()).borders(Borders::ALL);
         let inner = block.inner(area);
         frame.render_widget(block, area);
         frame.render_widget(Clear, inner);
 
-        let y_label = format!("{} ({})", y_kind_name(y_kind), self.run.ingest.input_spec.y_unit_label());
-
         let widget = RvPlottersChart {
-            curve: &curve,
-            points: &points,
-            cheap: &cheap,
-            rich: &rich,
-            x_bounds,
-            y_bounds,
+            color: self.color,
+            curve: &series.curve,
+            baseline: &series.baseline,
+            vol: &series.vol,
+            vol_bounds: series.vol_bounds,
+            points: &series.points,
+            cheap: &series.cheap,
+            rich: &series.rich,
+            x_bounds: series.x_bounds,
+            y_bounds: series.y_bounds,
             x_label: "tenor (yrs)",
-            y_label,
+            y_label: self.y_label(),
             fmt_x: fmt_axis_x,
-            fmt_y: fmt_axis_y_bp,
+            fmt_y: match self.run.ingest.input_spec.y_unit {
+                YUnit::Bp => fmt_axis_y_bp,
+                YUnit::Decimal => fmt_axis_y_decimal,
+            },
         };
 
         frame.render_widget(widget, inner);
     }
 
+    fn chart_title(&self) -> String {
+        let zoom = match self.zoom {
+            Some([lo, hi]) => format!(" [zoom {lo:.2}-{hi:.2}y]"),
+            None => String::new(),
+        };
+        format!(
+            "RV Curve - {} (n={}){zoom}",
+            self.current_rating().display_name(),
+            self.current_sample_count()
+        )
+    }
+
+    /// Rating whose generating baseline is overlaid (`--plot-baseline`).
+    fn baseline_blend(&self) -> Option<RatingBlend> {
+        self.config.plot_baseline.then(|| self.config.sample_blend())
+    }
+
+    fn y_label(&self) -> String {
+        let y_kind = self.run.ingest.input_spec.y_kind;
+        format!("{} ({})", y_kind_name(y_kind), self.run.ingest.input_spec.y_unit_label())
+    }
+
+    /// Write the currently displayed chart to a timestamped SVG file in the CWD.
+    ///
+    /// Uses the same series as `draw_chart`, so the file matches what is on
+    /// screen (less the `--plot-vol` overlay, which has no SVG axis yet).
+    fn save_chart_svg(&self) -> Result<String, AppError> {
+        let series = chart_series(
+            &self.run,
+            self.run.ingest.stats.tenor_min,
+            self.curve_points,
+            self.baseline_blend().as_ref(),
+            false,
+            self.zoom,
+        );
+        let title = format!("{} - {}", self.chart_title(), self.run.selection.best.model.display_name);
+        let y_label = self.y_label();
+
+        let svg = crate::plot::render_svg_chart(&crate::plot::SvgChart {
+            title: &title,
+            curve: &series.curve,
+            baseline: &series.baseline,
+            points: &series.points,
+            cheap: &series.cheap,
+            rich: &series.rich,
+            x_bounds: series.x_bounds,
+            y_bounds: series.y_bounds,
+            x_label: "tenor (yrs)",
+            y_label: &y_label,
+            y_unit: self.run.ingest.input_spec.y_unit,
+        });
+
+        let path = format!(
+            "rv-chart-{}-{}.svg",
+            self.current_rating().display_name(),
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        std::fs::write(&path, svg)
+            .map_err(|e| AppError::new(2, format!("Failed to write chart SVG '{path}': {e}")))?;
+        Ok(path)
+    }
+
     fn draw_footer(&self, frame: &mut ratatui::Frame<'_>, area: Rect) {
-        let help = "↑↓ rating  ←→ samples  g regenerate  m model  e export  q quit";
+        if let Some(buffer) = &self.input {
+            let line = Line::from(vec![
+                Span::styled(format!(":{buffer}"), self.fg(Color::White)),
+                Span::styled("█", self.fg(Color::Gray)),
+                Span::styled(
+                    format!("   Enter apply  Esc cancel  knobs: {}", KNOBS.join(" ")),
+                    self.fg(Color::DarkGray),
+                ),
+            ]);
+            let p = Paragraph::new(line).block(Block::default().borders(Borders::ALL));
+            frame.render_widget(p, area);
+            return;
+        }
+
+        let help = "↑↓ rating  ←→ samples  g regenerate  m model  +/-/[/]/0 zoom  e export  p svg  : set  q quit";
         let line = Line::from(vec![
-            Span::styled(help, Style::default().fg(Color::DarkGray)),
+            Span::styled(help, self.fg(Color::DarkGray)),
             Span::raw("  "),
-            Span::styled(&self.status, Style::default().fg(Color::Yellow)),
+            Span::styled(&self.status, self.fg(Color::Yellow)),
         ]);
         let p = Paragraph::new(line).block(Block::default().borders(Borders::ALL));
         frame.render_widget(p, area);
     }
 }
 
-/// Build chart series.
+/// Data series for the results chart (shared by the TUI widget and SVG export).
+struct ChartSeries {
+    curve: Vec<(f64, f64)>,
+    baseline: Vec<(f64, f64)>,
+    /// Bucket volatility in percent (`--plot-vol`), on its own axis.
+    vol: Vec<(f64, f64)>,
+    points: Vec<(f64, f64)>,
+    cheap: Vec<(f64, f64)>,
+    rich: Vec<(f64, f64)>,
+    x_bounds: [f64; 2],
+    /// Spread axis; the vol series never widens it.
+    y_bounds: [f64; 2],
+    /// Right-hand vol axis, when `vol` is non-empty.
+    vol_bounds: Option<[f64; 2]>,
+}
+
+/// Build chart series, sampling the fitted curve (and, for `baseline_blend`,
+/// the generating baseline; with `with_vol`, the bucket volatility) at `n`
+/// tenors.
+///
+/// With a `view` window only that tenor range is sampled and shown, and the
+/// y-range fits what is visible.
 fn chart_series(
-    run: &crate::app::pipeline::RunOutput,
+    run: &crate::report::RunOutput,
     x_min: f64,
-) -> (
-    Vec<(f64, f64)>,
-    Vec<(f64, f64)>,
-    Vec<(f64, f64)>,
-    Vec<(f64, f64)>,
-    [f64; 2],
-    [f64; 2],
-) {
+    n: usize,
+    baseline_blend: Option<&RatingBlend>,
+    with_vol: bool,
+    view: Option<[f64; 2]>,
+) -> ChartSeries {
     let mut t0 = x_min;
     let mut t1 = run.ingest.stats.tenor_max;
     if !t0.is_finite() || !t1.is_finite() || t1 <= t0 {
         t0 = 0.0;
         t1 = 1.0;
     }
+    // A view from before a refit may reach past the new range.
+    if let Some([lo, hi]) = view {
+        let (lo, hi) = (lo.max(t0), hi.min(t1));
+        if hi > lo {
+            (t0, t1) = (lo, hi);
+        }
+    }
     let x_bounds = [t0, t1];
+    let visible = |r: &BondResidual| (t0..=t1).contains(&r.point.tenor);
 
-    let mut points = Vec::with_capacity(run.residuals.len());
-    for r in &run.residuals {
-        points.push((r.point.tenor, r.point.y_obs));
-    }
+    let points: Vec<(f64, f64)> = run
+        .residuals
+        .iter()
+        .filter(|r| visible(r))
+        .map(|r| (r.point.tenor, r.point.y_obs))
+        .collect();
 
     let cheap = run
         .rankings
         .cheap
         .iter()
+        .filter(|r| visible(r))
         .map(|r| (r.point.tenor, r.point.y_obs))
         .collect::<Vec<_>>();
     let rich = run
         .rankings
         .rich
         .iter()
+        .filter(|r| visible(r))
         .map(|r| (r.point.tenor, r.point.y_obs))
         .collect::<Vec<_>>();
 
-    let n = 200usize;
     let mut curve = Vec::with_capacity(n);
     for i in 0..n {
         let u = i as f64 / (n as f64 - 1.0);
         let t = t0 + u * (t1 - t0);
-        let y = crate::models::predict(
-            run.selection.best.model.name,
-            t,
-            &run.selection.best.model.betas,
-            &run.selection.best.model.taus,
-        );
-        curve.push((t, y));
+        curve.push((t, run.selection.best.model.eval(t)));
     }
 
+    let baseline = match baseline_blend {
+        Some(blend) => crate::data::sample::baseline_grid(
+            &run.snapshot,
+            blend,
+            t0,
+            t1,
+            n,
+            run.ingest.input_spec.y_unit.per_bp(),
+        ),
+        None => Vec::new(),
+    };
+
     let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
-    for &(_, y) in &points {
-        y_min = y_min.min(y);
-        y_max = y_max.max(y);
-    }
-    for &(_, y) in &curve {
+    for &(_, y) in points.iter().chain(&curve).chain(&baseline) {
         y_min = y_min.min(y);
         y_max = y_max.max(y);
     }
@@ -473,7 +761,138 @@ fn chart_series(
     let pad = ((y_max - y_min).abs() * 0.05).max(1e-12);
     let y_bounds = [y_min - pad, y_max + pad];
 
-    (curve, points, cheap, rich, x_bounds, y_bounds)
+    let vol = if with_vol { crate::data::sample::vol_grid(&run.snapshot, t0, t1, n) } else { Vec::new() };
+    let vol_bounds = vol_axis_bounds(&vol);
+
+    ChartSeries {
+        curve,
+        baseline,
+        vol,
+        points,
+        cheap,
+        rich,
+        x_bounds,
+        y_bounds,
+        vol_bounds,
+    }
+}
+
+/// Padded range of the vol series, widened to 0.1pp when flat.
+fn vol_axis_bounds(vol: &[(f64, f64)]) -> Option<[f64; 2]> {
+    let (lo, hi) = vol
+        .iter()
+        .filter(|(_, v)| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, v)| (lo.min(v), hi.max(v)));
+    if !(lo.is_finite() && hi.is_finite()) {
+        return None;
+    }
+    let pad = ((hi - lo) * 0.05).max(0.05);
+    Some([lo - pad, hi + pad])
+}
+
+/// Chart window after scaling `view` (`None` = `full`) by `factor` about its
+/// centre (`< 1` zooms in) and shifting it by `pan` of its width, kept inside
+/// `full`. Returns `None` once the window covers the full range.
+fn zoom_view(full: [f64; 2], view: Option<[f64; 2]>, factor: f64, pan: f64) -> Option<[f64; 2]> {
+    let [lo, hi] = view.unwrap_or(full);
+    let full_span = full[1] - full[0];
+    let span = ((hi - lo) * factor).max(full_span / 64.0);
+    if full_span.is_nan() || full_span <= 0.0 || span >= full_span {
+        return None;
+    }
+    let center = (lo + hi) / 2.0 + pan * (hi - lo);
+    let lo = (center - span / 2.0).clamp(full[0], full[1] - span);
+    Some([lo, lo + span])
+}
+
+/// Knobs settable from the `:` input line (named after their CLI flags).
+const KNOBS: &[&str] = &[
+    "tau-min",
+    "tau-max",
+    "tau-steps-ns",
+    "tau-steps-nss",
+    "tau-steps-nssc",
+    "min-n-buffer",
+    "tenor-min",
+    "tenor-max",
+    "top",
+];
+
+/// Parse a `<knob> <value>` line into a copy of `config` with that field set.
+///
+/// Errors are status-line messages; the caller's config is never touched.
+fn apply_knob(config: &FitConfig, line: &str) -> Result<FitConfig, String> {
+    let mut parts = line.split_whitespace();
+    let (Some(knob), Some(raw), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(format!("Expected '<knob> <value>'; knobs: {}", KNOBS.join(", ")));
+    };
+    let years = || match raw.parse::<f64>() {
+        Ok(v) if v.is_finite() && v > 0.0 => Ok(v),
+        _ => Err(format!("{knob}: '{raw}' is not a positive number")),
+    };
+    let count = |min: usize| match raw.parse::<usize>() {
+        Ok(v) if v >= min => Ok(v),
+        _ => Err(format!("{knob}: '{raw}' is not an integer >= {min}")),
+    };
+
+    let mut next = config.clone();
+    match knob {
+        "tau-min" => next.tau_min = years()?,
+        "tau-max" => next.tau_max = years()?,
+        "tau-steps-ns" => next.tau_steps_ns = count(2)?,
+        "tau-steps-nss" => next.tau_steps_nss = count(2)?,
+        "tau-steps-nssc" => next.tau_steps_nssc = count(2)?,
+        "min-n-buffer" => next.min_n_buffer = count(0)?,
+        "tenor-min" => next.tenor_min = years()?,
+        "tenor-max" => next.tenor_max = years()?,
+        "top" => next.top_n = count(1)?,
+        _ => return Err(format!("Unknown knob '{knob}'; knobs: {}", KNOBS.join(", "))),
+    }
+    if next.tau_min >= next.tau_max {
+        return Err(format!("tau-min ({}) must be below tau-max ({})", next.tau_min, next.tau_max));
+    }
+    if next.tenor_min >= next.tenor_max {
+        return Err(format!("tenor-min ({}) must be below tenor-max ({})", next.tenor_min, next.tenor_max));
+    }
+    Ok(next)
+}
+
+/// Parse a `--tui-script` key list such as `"m,m,down,e"`.
+fn parse_tui_script(script: &str) -> Result<Vec<KeyCode>, AppError> {
+    script
+        .split(',')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let mut chars = token.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                return Ok(KeyCode::Char(c));
+            }
+            match token.to_ascii_lowercase().as_str() {
+                "up" => Ok(KeyCode::Up),
+                "down" => Ok(KeyCode::Down),
+                "left" => Ok(KeyCode::Left),
+                "right" => Ok(KeyCode::Right),
+                "enter" => Ok(KeyCode::Enter),
+                "esc" => Ok(KeyCode::Esc),
+                "backspace" => Ok(KeyCode::Backspace),
+                "space" => Ok(KeyCode::Char(' ')),
+                _ => Err(AppError::new(2, format!("Unknown key '{token}' in --tui-script."))),
+            }
+        })
+        .collect()
+}
+
+/// Rendered buffer as plain text, one line per row with trailing blanks trimmed.
+fn buffer_text(buffer: &Buffer) -> String {
+    let width = usize::from(buffer.area.width.max(1));
+    let mut out = String::new();
+    for row in buffer.content().chunks(width) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
 }
 
 fn y_kind_name(kind: YKind) -> &'static str {
@@ -485,7 +904,8 @@ fn y_kind_name(kind: YKind) -> &'static str {
 fn next_model_spec(cur: ModelSpec) -> ModelSpec {
     match cur {
         ModelSpec::Auto => ModelSpec::Ns,
-        ModelSpec::Ns => ModelSpec::Nss,
+        ModelSpec::Ns => ModelSpec::NssShared,
+        ModelSpec::NssShared => ModelSpec::Nss,
         ModelSpec::Nss => ModelSpec::Nssc,
         ModelSpec::Nssc => ModelSpec::Auto,
         ModelSpec::All => ModelSpec::Auto,
@@ -499,3 +919,118 @@ fn fmt_axis_x(v: f64) -> String {
 fn fmt_axis_y_bp(v: f64) -> String {
     format!("{v:.0}")
 }
+
+fn fmt_axis_y_decimal(v: f64) -> String {
+    format!("{v:.4}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit::selection::tests::make_test_config;
+
+    #[test]
+    fn curve_resolution_follows_width_within_bounds() {
+        assert_eq!(curve_resolution(0), 200);
+        assert_eq!(curve_resolution(80), 232);
+        assert!(curve_resolution(200) > curve_resolution(120));
+        assert_eq!(curve_resolution(u16::MAX), 2000);
+    }
+
+    #[test]
+    fn knob_input_validates_before_touching_config() {
+        let config = make_test_config();
+
+        let next = apply_knob(&config, "tau-max 12.5").unwrap();
+        assert_eq!(next.tau_max, 12.5);
+        assert_eq!(apply_knob(&config, " top  7 ").unwrap().top_n, 7);
+
+        for bad in ["tau-max abc", "tau-max -1", "tau-steps-ns 1", "bogus 3", "tau-max", "tau-min 999"] {
+            assert!(apply_knob(&config, bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn tui_script_drives_handle_key_on_a_test_backend() {
+        use clap::Parser;
+
+        assert_eq!(
+            parse_tui_script("m, down ,:,Enter").unwrap(),
+            vec![KeyCode::Char('m'), KeyCode::Down, KeyCode::Char(':'), KeyCode::Enter]
+        );
+        assert_eq!(parse_tui_script("m,pgup").unwrap_err().exit_code(), 2);
+
+        let args = FitArgs::try_parse_from(["rv"]).unwrap();
+        let mut app = App::with_snapshot(args, crate::data::sample::canned_snapshot()).unwrap();
+        let start = app.current_rating();
+        let keys = parse_tui_script("m,down").unwrap();
+        let screen = app.run_script(&keys, 160, 30).unwrap();
+
+        assert_eq!(app.config.model_spec, ModelSpec::Ns);
+        assert_eq!(app.rating_index, RatingBand::ALL.iter().position(|&r| r == start).unwrap() + 1);
+        assert_eq!(screen.lines().count(), 30);
+        assert!(screen.contains(&format!("Rating: {}", app.current_rating().display_name())), "{screen}");
+    }
+
+    #[test]
+    fn zoom_resamples_the_visible_window_without_refitting() {
+        use clap::Parser;
+
+        let full = [1.0, 9.0];
+        assert_eq!(zoom_view(full, None, 0.5, 0.0), Some([3.0, 7.0]));
+        // Panning stops at the edges; zooming back out past full resets.
+        assert_eq!(zoom_view(full, Some([3.0, 7.0]), 1.0, -1.0), Some([1.0, 5.0]));
+        assert_eq!(zoom_view(full, Some([3.0, 7.0]), 2.0, 0.0), None);
+        assert_eq!(zoom_view(full, None, 1.0, 0.25), None);
+
+        let args = FitArgs::try_parse_from(["rv"]).unwrap();
+        let mut app = App::with_snapshot(args, crate::data::sample::canned_snapshot()).unwrap();
+        let betas = app.run.selection.best.model.betas.clone();
+        app.run_script(&parse_tui_script("+,+,]").unwrap(), 160, 30).unwrap();
+        let [lo, hi] = app.zoom.unwrap();
+        assert_eq!(app.run.selection.best.model.betas, betas);
+
+        let series = chart_series(&app.run, app.full_range()[0], 50, None, false, app.zoom);
+        assert_eq!(series.x_bounds, [lo, hi]);
+        assert!(series.curve.first().unwrap().0 == lo && series.curve.last().unwrap().0 == hi);
+        assert!(!series.points.is_empty() && series.points.iter().all(|&(t, _)| (lo..=hi).contains(&t)));
+        let y_max = series.points.iter().chain(&series.curve).map(|p| p.1).fold(f64::MIN, f64::max);
+        assert!(series.y_bounds[1] - y_max < (series.y_bounds[1] - series.y_bounds[0]) * 0.1);
+
+        app.run_script(&parse_tui_script("0").unwrap(), 160, 30).unwrap();
+        assert_eq!(app.zoom, None);
+    }
+
+    #[test]
+    fn random_seed_is_drawn_once_and_shown() {
+        use clap::Parser;
+
+        let mut args = FitArgs::try_parse_from(["rv", "--random-seed"]).unwrap();
+        let seed = crate::app::draw_random_seed(&mut args).unwrap();
+        assert_eq!(crate::app::draw_random_seed(&mut args), None);
+        let app = App::with_snapshot(args, crate::data::sample::canned_snapshot()).unwrap();
+        assert_eq!(app.config.rng_seed, Some(seed));
+        assert!(app.status.contains(&format!("seed {seed}")), "{}", app.status);
+    }
+
+    #[test]
+    fn vol_overlay_has_its_own_axis_and_leaves_the_spread_axis_alone() {
+        use clap::Parser;
+
+        let args = FitArgs::try_parse_from(["rv", "--plot-vol"]).unwrap();
+        let app = App::with_snapshot(args, crate::data::sample::canned_snapshot()).unwrap();
+        let x_min = app.full_range()[0];
+        let plain = chart_series(&app.run, x_min, 40, None, false, None);
+        let series = chart_series(&app.run, x_min, 40, None, true, None);
+        assert!(plain.vol.is_empty() && plain.vol_bounds.is_none());
+        assert_eq!(series.y_bounds, plain.y_bounds);
+        assert_eq!(series.vol.len(), 40);
+        let [lo, hi] = series.vol_bounds.unwrap();
+        assert!(series.vol.iter().all(|&(_, v)| v > 0.0 && (lo..=hi).contains(&v)));
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| app.draw_chart(frame, frame.area())).unwrap();
+        let text: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains(&format!("{hi:.2}%")) && text.contains(&format!("{lo:.2}%")), "{text}");
+    }
+}
@@ -15,6 +15,13 @@ use ratatui::{
 pub struct RvPlottersChart<'a> {
     /// Line series for the fitted curve.
     pub curve: &'a [(f64, f64)],
+    /// Dotted series for the generating baseline (empty unless `--plot-baseline`).
+    pub baseline: &'a [(f64, f64)],
+    /// Line series for bucket volatility in percent (empty unless `--plot-vol`),
+    /// drawn against `vol_bounds` on a right-hand axis.
+    pub vol: &'a [(f64, f64)],
+    /// Right-hand axis bounds for `vol`.
+    pub vol_bounds: Option<[f64; 2]>,
     /// Scatter series for all observed bonds.
     pub points: &'a [(f64, f64)],
     /// Scatter series for the highlighted cheap names.
@@ -35,16 +42,19 @@ pub struct RvPlottersChart<'a> {
     pub fmt_x: fn(f64) -> String,
     /// Formatting of Y tick labels.
     pub fmt_y: fn(f64) -> String,
+    /// Colour the series; without it cheap/rich are told apart by marker.
+    pub color: bool,
 }
 
 impl<'a> Widget for RvPlottersChart<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let fg = |c: Color| if self.color { Style::default().fg(c) } else { Style::default() };
         if area.width < 20 || area.height < 8 {
             buf.set_string(
                 area.x,
                 area.y,
                 "Chart too small",
-                Style::default().fg(Color::Yellow),
+                fg(Color::Yellow),
             );
             return;
         }
@@ -58,6 +68,22 @@ impl<'a> Widget for RvPlottersChart<'a> {
             return;
         }
 
+        // Secondary vol axis: the series is rescaled onto the spread axis and
+        // a strip on the right carries its labels.
+        let vol_axis = self.vol_bounds.filter(|[v0, v1]| v1 > v0 && !self.vol.is_empty());
+        let vol_scaled: Vec<(f64, f64)> = match vol_axis {
+            Some([v0, v1]) => self.vol.iter().map(|&(t, v)| (t, y0 + (v - v0) / (v1 - v0) * (y1 - y0))).collect(),
+            None => Vec::new(),
+        };
+        let (area, vol_strip) = match vol_axis {
+            Some(_) => {
+                let strip = VOL_AXIS_WIDTH.min(area.width / 4);
+                let chart = Rect { width: area.width - strip, ..area };
+                (chart, Some(Rect { x: chart.right(), width: strip, ..area }))
+            }
+            None => (area, None),
+        };
+
         // Generate axis labels
         let x_labels = generate_labels(x0, x1, 5, &self.fmt_x);
         let y_labels = generate_labels(y0, y1, 5, &self.fmt_y);
@@ -72,7 +98,7 @@ impl<'a> Widget for RvPlottersChart<'a> {
                 Dataset::default()
                     .marker(Marker::Braille)
                     .graph_type(GraphType::Scatter)
-                    .style(Style::default().fg(Color::White))
+                    .style(fg(Color::White))
                     .data(self.points),
             );
         }
@@ -81,9 +107,9 @@ impl<'a> Widget for RvPlottersChart<'a> {
         if !self.cheap.is_empty() {
             datasets.push(
                 Dataset::default()
-                    .marker(Marker::Braille)
+                    .marker(if self.color { Marker::Braille } else { Marker::Block })
                     .graph_type(GraphType::Scatter)
-                    .style(Style::default().fg(Color::Green))
+                    .style(fg(Color::Green))
                     .data(self.cheap),
             );
         }
@@ -92,20 +118,42 @@ impl<'a> Widget for RvPlottersChart<'a> {
         if !self.rich.is_empty() {
             datasets.push(
                 Dataset::default()
-                    .marker(Marker::Braille)
+                    .marker(if self.color { Marker::Braille } else { Marker::Bar })
                     .graph_type(GraphType::Scatter)
-                    .style(Style::default().fg(Color::Red))
+                    .style(fg(Color::Red))
                     .data(self.rich),
             );
         }
 
+        // Generating baseline (dotted gray), under the fitted curve
+        if !self.baseline.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .marker(Marker::Dot)
+                    .graph_type(GraphType::Scatter)
+                    .style(fg(Color::DarkGray))
+                    .data(self.baseline),
+            );
+        }
+
+        // Bucket volatility (magenta), under the fitted curve
+        if !vol_scaled.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .marker(if self.color { Marker::Braille } else { Marker::Dot })
+                    .graph_type(GraphType::Line)
+                    .style(fg(Color::Magenta))
+                    .data(&vol_scaled),
+            );
+        }
+
         // Fitted curve (cyan line) - rendered last so it draws on top
         if !self.curve.is_empty() {
             datasets.push(
                 Dataset::default()
                     .marker(Marker::Braille)
                     .graph_type(GraphType::Line)
-                    .style(Style::default().fg(Color::Cyan))
+                    .style(fg(Color::Cyan))
                     .data(self.curve),
             );
         }
@@ -114,21 +162,37 @@ impl<'a> Widget for RvPlottersChart<'a> {
             .block(Block::default())
             .x_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::Gray))
+                    .style(fg(Color::Gray))
                     .bounds(self.x_bounds)
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::Gray))
+                    .style(fg(Color::Gray))
                     .bounds(self.y_bounds)
                     .labels(y_labels),
             );
 
         chart.render(area, buf);
+
+        if let (Some([v0, v1]), Some(strip)) = (vol_axis, vol_strip) {
+            // The plot spans the rows above the x-axis line and its labels.
+            let top = strip.y;
+            let bottom = strip.bottom().saturating_sub(3).max(top);
+            let style = fg(Color::Magenta);
+            let rows = (bottom - top) as f64;
+            for (i, label) in generate_labels(v0, v1, 3, &|v| format!(" {v:.2}%")).into_iter().enumerate() {
+                let row = bottom - (rows * i as f64 / 2.0).round() as u16;
+                buf.set_stringn(strip.x, row, label.content, strip.width as usize, style);
+            }
+            buf.set_stringn(strip.x, bottom + 1, " vol", strip.width as usize, style);
+        }
     }
 }
 
+/// Columns reserved on the right for the vol axis labels.
+const VOL_AXIS_WIDTH: u16 = 8;
+
 /// Generate evenly spaced labels for an axis.
 fn generate_labels(min: f64, max: f64, count: usize, fmt: &dyn Fn(f64) -> String) -> Vec<ratatui::text::Span<'static>> {
     let mut labels = Vec::with_capacity(count);